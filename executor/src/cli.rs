@@ -0,0 +1,96 @@
+//! Command line options of the executor.
+
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Station Iapetus")]
+pub struct Args {
+    /// Level to start on, for example `data/levels/arrival.rgs`.
+    #[arg(long)]
+    pub level: Option<PathBuf>,
+
+    /// Saved game to load right after the start.
+    #[arg(long = "load", value_name = "SAVE")]
+    pub saved_game: Option<PathBuf>,
+
+    /// Settings file to use instead of `data/configs/settings.ron`.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Seed of the game random number generator.
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Starts a new game right away instead of showing the main menu.
+    #[arg(long)]
+    pub skip_menu: bool,
+
+    /// Difficulty of the game started from the command line.
+    #[arg(long, value_parser = ["story", "normal", "hard", "nightmare"])]
+    pub difficulty: Option<String>,
+
+    /// Width of the window in logical pixels.
+    #[arg(long, requires = "height")]
+    pub width: Option<u32>,
+
+    /// Height of the window in logical pixels.
+    #[arg(long, requires = "width")]
+    pub height: Option<u32>,
+
+    /// Runs the game in borderless fullscreen mode.
+    #[arg(long)]
+    pub fullscreen: bool,
+
+    /// Enables vertical synchronization.
+    #[arg(long)]
+    pub vsync: bool,
+
+    /// Amount of frames the executor waits before it starts to throttle the update rate when the
+    /// frames take too long.
+    #[arg(long, value_name = "FRAMES", default_value_t = 1000)]
+    pub throttle_frame_interval: usize,
+
+    /// Runs the game without a window for the given amount of frames, then exits. Useful for
+    /// automated runs of levels and saved games.
+    #[arg(long, requires = "frames")]
+    pub headless: bool,
+
+    /// Amount of frames of a headless run.
+    #[arg(long, requires = "headless")]
+    pub frames: Option<usize>,
+}
+
+impl Args {
+    /// Path of the level, it is passed to the plugins as the scene to start on, so it works for
+    /// both statically and dynamically linked game.
+    pub fn scene_path(&self) -> Option<String> {
+        self.level
+            .as_ref()
+            .map(|level| level.to_string_lossy().into_owned())
+    }
+
+    /// Returns `true` if any of the options that must be passed to the game plugin is set.
+    #[cfg(feature = "dylib")]
+    pub fn has_game_options(&self) -> bool {
+        self.saved_game.is_some()
+            || self.config.is_some()
+            || self.seed.is_some()
+            || self.skip_menu
+            || self.difficulty.is_some()
+    }
+
+    #[cfg(not(feature = "dylib"))]
+    pub fn launch_options(&self) -> station_iapetus::launch::LaunchOptions {
+        station_iapetus::launch::LaunchOptions {
+            saved_game: self.saved_game.clone(),
+            config: self.config.clone(),
+            seed: self.seed,
+            skip_menu: self.skip_menu,
+            difficulty: self
+                .difficulty
+                .as_deref()
+                .and_then(|difficulty| difficulty.parse().ok()),
+        }
+    }
+}
@@ -1,21 +1,43 @@
 //! Executor with your game connected to it as a plugin.
+mod cli;
+
+use clap::Parser;
+use cli::Args;
+use fyrox::core::log::Log;
+use fyrox::dpi::LogicalSize;
 use fyrox::engine::executor::Executor;
 use fyrox::engine::GraphicsContextParams;
 use fyrox::event_loop::EventLoop;
+use fyrox::window::{Fullscreen, WindowAttributes};
 
 fn main() {
+    let args = Args::parse();
+
+    if args.headless {
+        run_headless(&args);
+        return;
+    }
+
+    let mut window_attributes = WindowAttributes::default();
+    if let (Some(width), Some(height)) = (args.width, args.height) {
+        window_attributes = window_attributes.with_inner_size(LogicalSize::new(width, height));
+    }
+    if args.fullscreen {
+        window_attributes = window_attributes.with_fullscreen(Some(Fullscreen::Borderless(None)));
+    }
+
     let mut executor = Executor::from_params(
         Some(EventLoop::new().unwrap()),
         GraphicsContextParams {
-            window_attributes: Default::default(),
-            vsync: false,
+            window_attributes,
+            vsync: args.vsync,
             msaa_sample_count: None,
             graphics_server_constructor: Default::default(),
             named_objects: false,
         },
     );
 
-    executor.set_throttle_frame_interval(1000);
+    executor.set_throttle_frame_interval(args.throttle_frame_interval);
 
     // Dynamic linking with hot reloading.
     #[cfg(feature = "dylib")]
@@ -27,14 +49,51 @@ fn main() {
         #[cfg(target_os = "macos")]
         let file_name = "libgame_dylib.dylib";
         executor.add_dynamic_plugin(file_name, true, true).unwrap();
+
+        if args.has_game_options() {
+            Log::warn("Game options are ignored when the game is linked dynamically!");
+        }
     }
 
     // Static linking.
     #[cfg(not(feature = "dylib"))]
     {
         use station_iapetus::Game;
-        executor.add_plugin(Game::default());
+        executor.add_plugin(Game::default().with_launch_options(args.launch_options()));
+    }
+
+    // The plugins are enabled before the executor does it, so they get the level the same way as
+    // the scene passed by the editor. The executor does not enable them again.
+    if let Some(scene_path) = args.scene_path() {
+        if let Err(err) = executor.enable_plugins(Some(&scene_path), true, None) {
+            Log::err(format!("Unable to start on {scene_path}: {err}"));
+            std::process::exit(1);
+        }
     }
 
     executor.run()
 }
+
+#[cfg(not(feature = "dylib"))]
+fn run_headless(args: &Args) {
+    use station_iapetus::{simulation::Simulation, Game};
+
+    let frames = args.frames.unwrap_or_default();
+    let game = Game::headless().with_launch_options(args.launch_options());
+    let result = Simulation::with_game(game, args.scene_path().as_deref(), Simulation::DEFAULT_DT)
+        .and_then(|mut simulation| simulation.run_frames(frames));
+
+    match result {
+        Ok(_) => Log::info(format!("Headless run of {frames} frames has finished.")),
+        Err(err) => {
+            Log::err(format!("Headless run has failed: {err}"));
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "dylib")]
+fn run_headless(_args: &Args) {
+    Log::err("Headless runs are not supported when the game is linked dynamically!");
+    std::process::exit(1);
+}
@@ -0,0 +1,344 @@
+//! Achievements. The list of achievements is defined in `data/configs/achievements.ron`, every
+//! achievement has a condition that is evaluated against gameplay events. Unlocked achievements and
+//! progress counters do not belong to a particular game, they are kept by an
+//! [`AchievementBackend`] independently of saved games.
+
+use crate::{saved_game, statistics::StatisticsEvent};
+use fyrox::core::log::Log;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum AchievementCondition {
+    /// Kill the given amount of enemies, kills of every game are counted.
+    Kills(u32),
+    /// Sever the given amount of limbs of enemies.
+    LimbsSevered(u32),
+    /// Finish a level without taking any damage.
+    LevelWithoutDamage,
+    /// Kill the given amount of enemies with explosions of explosive barrels.
+    ExplosiveBarrelKills(u32),
+    /// Throw the given amount of objects with the kinetic gun.
+    KineticGunThrows(u32),
+}
+
+impl AchievementCondition {
+    /// Returns the name of the counter of the condition and the value it should reach, `None` if
+    /// the condition is checked by a single event.
+    pub fn counter(&self) -> Option<(&'static str, u32)> {
+        match *self {
+            Self::Kills(count) => Some((KILLS, count)),
+            Self::LimbsSevered(count) => Some((LIMBS_SEVERED, count)),
+            Self::ExplosiveBarrelKills(count) => Some((EXPLOSIVE_BARREL_KILLS, count)),
+            Self::KineticGunThrows(count) => Some((KINETIC_GUN_THROWS, count)),
+            Self::LevelWithoutDamage => None,
+        }
+    }
+
+    fn is_satisfied_by(&self, event: &AchievementEvent) -> bool {
+        match (self, event) {
+            (Self::LevelWithoutDamage, AchievementEvent::LevelFinished { damage_taken }) => {
+                *damage_taken <= 0.0
+            }
+            _ => false,
+        }
+    }
+}
+
+const KILLS: &str = "kills";
+const LIMBS_SEVERED: &str = "limbs_severed";
+const EXPLOSIVE_BARREL_KILLS: &str = "explosive_barrel_kills";
+const KINETIC_GUN_THROWS: &str = "kinetic_gun_throws";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AchievementDefinition {
+    /// Unique id of the achievement. The name and the description of the achievement are
+    /// localized with `achievement.<id>.name` and `achievement.<id>.description` keys.
+    pub id: String,
+    pub condition: AchievementCondition,
+}
+
+impl AchievementDefinition {
+    pub fn name_key(&self) -> String {
+        format!("achievement.{}.name", self.id)
+    }
+
+    pub fn description_key(&self) -> String {
+        format!("achievement.{}.description", self.id)
+    }
+}
+
+#[derive(Deserialize, Default, Debug)]
+pub struct AchievementDefinitionContainer {
+    pub achievements: Vec<AchievementDefinition>,
+}
+
+impl AchievementDefinitionContainer {
+    const PATH: &'static str = "data/configs/achievements.ron";
+
+    pub fn new() -> Self {
+        File::open(Self::PATH)
+            .map_err(|e| e.to_string())
+            .and_then(|file| ron::de::from_reader(file).map_err(|e| e.to_string()))
+            .unwrap_or_else(|err| {
+                Log::err(format!(
+                    "Unable to load achievements from {}. Reason: {err}",
+                    Self::PATH
+                ));
+                Default::default()
+            })
+    }
+}
+
+pub static DEFINITIONS: LazyLock<AchievementDefinitionContainer> =
+    LazyLock::new(AchievementDefinitionContainer::new);
+
+/// Gameplay events that could unlock achievements. Only the actions of the player are reported.
+#[derive(Debug, Clone)]
+pub enum AchievementEvent {
+    Kill,
+    LimbSevered,
+    ExplosiveBarrelKill,
+    KineticGunThrow,
+    /// A level was finished, `damage_taken` is the total damage the player took in the level.
+    LevelFinished {
+        damage_taken: f32,
+    },
+}
+
+impl AchievementEvent {
+    pub fn from_statistics(event: &StatisticsEvent) -> Option<Self> {
+        match event {
+            StatisticsEvent::Kill { .. } => Some(Self::Kill),
+            StatisticsEvent::LimbSevered => Some(Self::LimbSevered),
+            _ => None,
+        }
+    }
+
+    fn counter(&self) -> Option<&'static str> {
+        match self {
+            Self::Kill => Some(KILLS),
+            Self::LimbSevered => Some(LIMBS_SEVERED),
+            Self::ExplosiveBarrelKill => Some(EXPLOSIVE_BARREL_KILLS),
+            Self::KineticGunThrow => Some(KINETIC_GUN_THROWS),
+            Self::LevelFinished { .. } => None,
+        }
+    }
+}
+
+/// Storage of unlocked achievements and progress counters. The game uses
+/// [`LocalAchievementBackend`], a platform service (Steam, etc.) could be plugged in by
+/// implementing this trait.
+pub trait AchievementBackend: Debug {
+    fn is_unlocked(&self, id: &str) -> bool;
+
+    fn unlock(&mut self, id: &str);
+
+    fn counter(&self, name: &str) -> u32;
+
+    fn set_counter(&mut self, name: &str, value: u32);
+
+    /// Persists the changes made since the last flush.
+    fn flush(&mut self);
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+struct Profile {
+    unlocked: BTreeSet<String>,
+    counters: BTreeMap<String, u32>,
+}
+
+impl Profile {
+    /// Writes the profile atomically the same way as saved games, an existing profile is left
+    /// intact if the game crashes in the middle of writing.
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let text = ron::ser::to_string_pretty(self, PrettyConfig::default())
+            .map_err(|e| format!("Unable to serialize profile. Reason: {e}"))?;
+
+        if let Some(folder) = path.parent() {
+            std::fs::create_dir_all(folder).map_err(|e| {
+                format!("Unable to create folder {}. Reason: {e}", folder.display())
+            })?;
+        }
+
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let mut file = File::create(&temp_path).map_err(|e| {
+            format!(
+                "Unable to create profile {}. Reason: {e}",
+                temp_path.display()
+            )
+        })?;
+        file.write_all(text.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| format!("Unable to write profile {}. Reason: {e}", path.display()))?;
+        drop(file);
+
+        std::fs::rename(&temp_path, path)
+            .map_err(|e| format!("Unable to replace profile {}. Reason: {e}", path.display()))
+    }
+}
+
+/// Keeps achievements in a profile file in the per-user data directory.
+#[derive(Default, Debug)]
+pub struct LocalAchievementBackend {
+    /// Path of the profile file, `None` keeps everything in memory.
+    path: Option<PathBuf>,
+    profile: Profile,
+    need_save: bool,
+}
+
+impl LocalAchievementBackend {
+    /// Loads the profile from the given path, the changes will be saved to the same path. Empty
+    /// profile is used if the file does not exist or could not be read, the file is left intact
+    /// until the profile changes.
+    pub fn load(path: PathBuf) -> Self {
+        let profile = match std::fs::read_to_string(&path) {
+            Ok(text) => ron::from_str(&text).unwrap_or_else(|err| {
+                Log::err(format!(
+                    "Unable to parse profile {}, an empty one is used. Reason: {err}",
+                    path.display()
+                ));
+                Profile::default()
+            }),
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    Log::err(format!(
+                        "Unable to read profile {}, an empty one is used. Reason: {err}",
+                        path.display()
+                    ));
+                }
+                Profile::default()
+            }
+        };
+        Self {
+            path: Some(path),
+            profile,
+            need_save: false,
+        }
+    }
+
+    /// Returns the path of the profile in the per-user data directory, it falls back to the
+    /// working directory if the platform has no such directory.
+    pub fn profile_path() -> PathBuf {
+        match dirs::data_dir() {
+            Some(data_dir) => data_dir
+                .join(saved_game::GAME_DATA_FOLDER)
+                .join("profile.ron"),
+            None => PathBuf::from("data/profile.ron"),
+        }
+    }
+}
+
+impl AchievementBackend for LocalAchievementBackend {
+    fn is_unlocked(&self, id: &str) -> bool {
+        self.profile.unlocked.contains(id)
+    }
+
+    fn unlock(&mut self, id: &str) {
+        self.need_save |= self.profile.unlocked.insert(id.to_string());
+    }
+
+    fn counter(&self, name: &str) -> u32 {
+        self.profile.counters.get(name).cloned().unwrap_or_default()
+    }
+
+    fn set_counter(&mut self, name: &str, value: u32) {
+        self.profile.counters.insert(name.to_string(), value);
+        self.need_save = true;
+    }
+
+    fn flush(&mut self) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        if !self.need_save {
+            return;
+        }
+        self.need_save = false;
+
+        if let Err(err) = self.profile.save(path) {
+            Log::err(err);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Achievements {
+    backend: Box<dyn AchievementBackend>,
+    /// Achievements that were unlocked since the last call of [`Self::take_newly_unlocked`].
+    newly_unlocked: Vec<&'static AchievementDefinition>,
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        Self::new(Box::new(LocalAchievementBackend::default()))
+    }
+}
+
+impl Achievements {
+    pub fn new(backend: Box<dyn AchievementBackend>) -> Self {
+        Self {
+            backend,
+            newly_unlocked: Default::default(),
+        }
+    }
+
+    pub fn is_unlocked(&self, definition: &AchievementDefinition) -> bool {
+        self.backend.is_unlocked(&definition.id)
+    }
+
+    /// Returns current value of the counter of the achievement and the value it should reach.
+    pub fn progress(&self, definition: &AchievementDefinition) -> Option<(u32, u32)> {
+        let (counter, target) = definition.condition.counter()?;
+        Some((self.backend.counter(counter).min(target), target))
+    }
+
+    pub fn handle_event(&mut self, event: &AchievementEvent) {
+        if let Some(counter) = event.counter() {
+            let value = self.backend.counter(counter).saturating_add(1);
+            self.backend.set_counter(counter, value);
+        }
+
+        let mut unlocked_any = false;
+        for definition in DEFINITIONS.achievements.iter() {
+            if self.backend.is_unlocked(&definition.id) {
+                continue;
+            }
+
+            let satisfied = match definition.condition.counter() {
+                Some((counter, target)) => self.backend.counter(counter) >= target,
+                None => definition.condition.is_satisfied_by(event),
+            };
+            if satisfied {
+                Log::info(format!("Achievement unlocked: {}", definition.id));
+                self.backend.unlock(&definition.id);
+                self.newly_unlocked.push(definition);
+                unlocked_any = true;
+            }
+        }
+
+        // Counters change often, so they are written only together with something important.
+        if unlocked_any || matches!(event, AchievementEvent::LevelFinished { .. }) {
+            self.backend.flush();
+        }
+    }
+
+    pub fn take_newly_unlocked(&mut self) -> Vec<&'static AchievementDefinition> {
+        std::mem::take(&mut self.newly_unlocked)
+    }
+
+    pub fn flush(&mut self) {
+        self.backend.flush();
+    }
+}
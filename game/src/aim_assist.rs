@@ -0,0 +1,90 @@
+//! Aim assist for aiming with a gamepad stick. Turning slows down while the aim is near a target
+//! (slowdown) and the aim is pulled towards the target while the stick is deflected (magnetism).
+//! Nothing happens when the stick is released, so the aim is never locked on a target.
+//!
+//! Targets are the most vulnerable points of visible hostile characters, see
+//! [`crate::character::Character::most_vulnerable_point`].
+
+use fyrox::core::algebra::{Vector2, Vector3};
+use std::f32::consts::{PI, TAU};
+
+/// Maximum angle between the aim and a target, in radians.
+pub const MAX_ANGLE: f32 = 8.0 * PI / 180.0;
+/// Targets further than this are ignored.
+pub const MAX_DISTANCE: f32 = 40.0;
+/// Fraction of the turning speed that is taken away when the aim is right at a target with full
+/// strength of the assist.
+pub const MAX_SLOWDOWN: f32 = 0.6;
+/// Fraction of the aim error that is corrected per second with full strength of the assist and
+/// full deflection of the stick.
+pub const MAGNETISM: f32 = 4.0;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AimAssistTarget {
+    /// Angle that must be added to the yaw to aim at the target, in radians.
+    pub yaw_error: f32,
+    /// Angle that must be added to the pitch (which grows downwards) to aim at the target, in
+    /// radians.
+    pub pitch_error: f32,
+    /// Angle between the aim and the target, in radians.
+    pub angle: f32,
+}
+
+fn yaw_pitch(direction: Vector3<f32>) -> (f32, f32) {
+    let length = direction.norm().max(f32::EPSILON);
+    (
+        direction.x.atan2(direction.z),
+        (-direction.y / length).clamp(-1.0, 1.0).asin(),
+    )
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// Finds the target closest to the aim. `eye` is the position of the camera and `look` is its look
+/// vector, `targets` must contain only the points that are visible from the eye.
+pub fn find_target(
+    eye: Vector3<f32>,
+    look: Vector3<f32>,
+    targets: impl IntoIterator<Item = Vector3<f32>>,
+) -> Option<AimAssistTarget> {
+    let look = look.try_normalize(f32::EPSILON)?;
+    let (yaw, pitch) = yaw_pitch(look);
+    targets
+        .into_iter()
+        .filter_map(|point| {
+            let direction = point - eye;
+            let distance = direction.norm();
+            if distance > MAX_DISTANCE || distance <= f32::EPSILON {
+                return None;
+            }
+            let angle = look.angle(&direction);
+            if angle > MAX_ANGLE {
+                return None;
+            }
+            let (target_yaw, target_pitch) = yaw_pitch(direction);
+            Some(AimAssistTarget {
+                yaw_error: wrap_angle(target_yaw - yaw),
+                pitch_error: target_pitch - pitch,
+                angle,
+            })
+        })
+        .min_by(|a, b| a.angle.total_cmp(&b.angle))
+}
+
+impl AimAssistTarget {
+    /// Multiplier of the turning speed, the closer the aim is to the target, the slower it turns.
+    pub fn slowdown(&self, strength: f32) -> f32 {
+        let closeness = 1.0 - (self.angle / MAX_ANGLE).clamp(0.0, 1.0);
+        1.0 - MAX_SLOWDOWN * strength.clamp(0.0, 1.0) * closeness
+    }
+
+    /// Correction of the yaw (`x`) and the pitch (`y`) towards the target for a frame. It is
+    /// proportional to the deflection of the stick, so a released stick is not moved.
+    pub fn magnetism(&self, strength: f32, stick_magnitude: f32, dt: f32) -> Vector2<f32> {
+        let rate =
+            (MAGNETISM * strength.clamp(0.0, 1.0) * stick_magnitude.clamp(0.0, 1.0) * dt).min(1.0);
+        Vector2::new(self.yaw_error, self.pitch_error).scale(rate)
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
     bot::{behavior::Action, behavior::BehaviorContext},
     level::hit_box::LimbType,
+    rng::GameRng,
+    Game,
 };
 use fyrox::graph::SceneGraph;
 use fyrox::plugin::error::GameError;
@@ -12,7 +14,7 @@ use fyrox::{
         pool::Handle,
         visitor::prelude::*,
     },
-    rand::{thread_rng, Rng},
+    rand::Rng,
     scene::{graph::Graph, node::Node, Scene},
     utils::behavior::{Behavior, Status},
 };
@@ -43,9 +45,9 @@ impl AimOnTarget {
     }
 }
 
-fn random_offset(no_head: bool) -> f32 {
+fn random_offset(no_head: bool, rng: &mut impl Rng) -> f32 {
     if no_head {
-        thread_rng().gen_range(-90.0f32.to_radians()..90.0f32.to_radians())
+        rng.gen_range(-90.0f32.to_radians()..90.0f32.to_radians())
     } else {
         0.0
     }
@@ -60,11 +62,12 @@ impl AimOnTarget {
         dt: f32,
         angle_hack: f32,
         no_head: bool,
+        mut rng: &GameRng,
     ) -> Result<bool, GameError> {
         if no_head {
             if self.pitch_random_smooth_angle.at_target() {
                 self.pitch_random_smooth_angle
-                    .set_target(random_offset(no_head));
+                    .set_target(random_offset(no_head, &mut rng));
             }
             self.pitch_random_smooth_angle.update(dt);
         }
@@ -100,11 +103,12 @@ impl AimOnTarget {
         body: Handle<RigidBody>,
         angle_hack: f32,
         no_head: bool,
+        mut rng: &GameRng,
     ) -> Result<bool, GameError> {
         if no_head {
             if self.yaw_random_smooth_angle.at_target() {
                 self.yaw_random_smooth_angle
-                    .set_target(random_offset(no_head));
+                    .set_target(random_offset(no_head, &mut rng));
             }
             self.yaw_random_smooth_angle.update(dt);
         }
@@ -146,6 +150,7 @@ impl<'a> Behavior<'a> for AimOnTarget {
         let no_head = ctx
             .character
             .is_limb_sliced_off(&ctx.scene.graph, LimbType::Head);
+        let rng = ctx.plugins.get::<Game>().rng();
 
         let aimed_horizontally = self.aim_horizontally(
             ctx.yaw,
@@ -156,6 +161,7 @@ impl<'a> Behavior<'a> for AimOnTarget {
             ctx.character.body,
             ctx.h_aim_angle_hack.to_radians(),
             no_head,
+            rng,
         )?;
         let aimed_vertically = self.aim_vertically(
             ctx.pitch,
@@ -164,6 +170,7 @@ impl<'a> Behavior<'a> for AimOnTarget {
             ctx.dt,
             ctx.v_aim_angle_hack.to_radians(),
             no_head,
+            rng,
         )?;
 
         if no_head || aimed_horizontally && aimed_vertically {
@@ -1,7 +1,7 @@
 use crate::{
     bot::{behavior::BehaviorContext, Bot, BotHostility, Target},
     character::Character,
-    Game,
+    profiler, Game,
 };
 use fyrox::graph::SceneGraph;
 use fyrox::plugin::error::GameError;
@@ -47,6 +47,7 @@ impl<'a> Behavior<'a> for FindTarget {
         let position = ctx.character.position(graph);
 
         self.update_frustum(position, graph, ctx.model);
+        ctx.frustum.clone_from(&self.frustum);
 
         // Check if existing target is valid.
         if let Some(target) = ctx.target {
@@ -108,6 +109,7 @@ impl<'a> Behavior<'a> for FindTarget {
                 || self.frustum.is_contains_point(character_position)
             {
                 let ray = Ray::from_two_points(character_position, position);
+                profiler::count("Raycasts");
                 ctx.scene.graph.physics.cast_ray(
                     RayCastOptions {
                         ray_origin: Point3::from(ray.origin),
@@ -1,4 +1,4 @@
-use crate::bot::behavior::BehaviorContext;
+use crate::{bot::behavior::BehaviorContext, Game};
 use fyrox::plugin::error::GameError;
 use fyrox::{
     core::visitor::prelude::*,
@@ -18,13 +18,14 @@ impl<'a> Behavior<'a> for DoMeleeAttack {
     fn tick(&mut self, ctx: &mut Self::Context) -> Result<Status, GameError> {
         if let Some(upper_body_layer) = ctx.state_machine.upper_body_layer(&ctx.scene.graph) {
             if upper_body_layer.active_state() == ctx.state_machine.attack_state {
-                self.attack_timeout = 0.3;
+                // Exhausted bots attack slower and rest longer between the attacks.
+                self.attack_timeout = 0.3 / ctx.character.stamina.melee_speed();
             } else if self.attack_timeout <= 0.0 {
                 ctx.need_to_melee_attack = true;
 
-                self.attack_animation_index = fyrox::core::rand::thread_rng()
-                    .gen_range(0..ctx.state_machine.attack_animations.len())
-                    as u32;
+                let mut rng = ctx.plugins.get::<Game>().rng();
+                self.attack_animation_index =
+                    rng.gen_range(0..ctx.state_machine.attack_animations.len()) as u32;
             }
 
             self.attack_timeout -= ctx.dt;
@@ -8,6 +8,8 @@ use crate::{
             movement::MoveToTarget,
             range::IsTargetCloseBy,
             shoot::{CanShootTarget, ShootTarget},
+            stamina::TakeBreath,
+            status::Stunned,
             threat::{NeedsThreatenTarget, ThreatenTarget},
         },
         state_machine::StateMachine,
@@ -17,7 +19,11 @@ use crate::{
     utils::BodyImpactHandler,
 };
 use fyrox::{
-    core::{math::SmoothAngle, pool::Handle, visitor::prelude::*},
+    core::{
+        math::{frustum::Frustum, SmoothAngle},
+        pool::Handle,
+        visitor::prelude::*,
+    },
     dispatch_behavior_variants,
     scene::{node::Node, Scene},
     script::{PluginsRefMut, ScriptMessageSender},
@@ -31,6 +37,8 @@ pub mod melee;
 pub mod movement;
 pub mod range;
 pub mod shoot;
+pub mod stamina;
+pub mod status;
 pub mod threat;
 
 #[derive(Debug, PartialEq, Visit, Clone)]
@@ -47,6 +55,8 @@ pub enum Action {
     ShootTarget(ShootTarget),
     NeedsThreatenTarget(NeedsThreatenTarget),
     ThreatenTarget(ThreatenTarget),
+    TakeBreath(TakeBreath),
+    Stunned(Stunned),
 }
 
 impl Default for Action {
@@ -69,7 +79,9 @@ dispatch_behavior_variants!(
     CanShootTarget,
     ShootTarget,
     NeedsThreatenTarget,
-    ThreatenTarget
+    ThreatenTarget,
+    TakeBreath,
+    Stunned
 );
 
 pub struct BehaviorContext<'a> {
@@ -88,6 +100,8 @@ pub struct BehaviorContext<'a> {
     pub v_recoil: &'a mut SmoothAngle,
     pub h_recoil: &'a mut SmoothAngle,
     pub move_speed: f32,
+    /// Running bots spend stamina while they move, see [`crate::bot::Bot::RUN_SPEED_THRESHOLD`].
+    pub is_running: bool,
     pub threaten_timeout: &'a mut f32,
     pub script_message_sender: &'a ScriptMessageSender,
     pub navmesh: Handle<Node>,
@@ -98,6 +112,8 @@ pub struct BehaviorContext<'a> {
     pub yaw: &'a mut SmoothAngle,
     pub pitch: &'a mut SmoothAngle,
     pub plugins: &'a PluginsRefMut<'a>,
+    /// Frustum the bot uses to find its targets, it is kept for debug drawing.
+    pub frustum: &'a mut Frustum,
 
     // Output
     pub attack_animation_index: usize,
@@ -179,10 +195,17 @@ impl BotBehavior {
                                     AimOnTarget::new_action(spine, AimTarget::SteeringTarget),
                                     bt,
                                 ),
-                                leaf(
-                                    Action::MoveToTarget(MoveToTarget {
-                                        min_distance: close_combat_distance,
-                                    }),
+                                // Charging bots tire and stop for a while.
+                                selector(
+                                    [
+                                        leaf(Action::TakeBreath(TakeBreath), bt),
+                                        leaf(
+                                            Action::MoveToTarget(MoveToTarget {
+                                                min_distance: close_combat_distance,
+                                            }),
+                                            bt,
+                                        ),
+                                    ],
                                     bt,
                                 ),
                             ],
@@ -201,6 +224,7 @@ impl BotBehavior {
         let entry = selector(
             [
                 dead_seq,
+                leaf(Action::Stunned(Stunned), bt),
                 sequence(
                     [
                         leaf(Action::FindTarget(FindTarget::default()), bt),
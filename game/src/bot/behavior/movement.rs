@@ -1,6 +1,9 @@
 use crate::{
-    bot::behavior::BehaviorContext, door::door_mut, level::hit_box::HitBox,
-    utils::BodyImpactHandler, Game,
+    bot::behavior::BehaviorContext,
+    door::{door_mut, Door},
+    level::hit_box::HitBox,
+    utils::BodyImpactHandler,
+    Game,
 };
 use fyrox::{
     core::{algebra::Vector3, pool::Handle, visitor::prelude::*},
@@ -28,7 +31,8 @@ impl MoveToTarget {
             .doors;
         for &door in doors {
             let door = door_mut(door, &mut ctx.scene.graph);
-            let close_enough = self_position.metric_distance(&door.initial_position()) < 1.25;
+            let close_enough =
+                self_position.metric_distance(&door.initial_position()) < Door::INTERACTION_RADIUS;
             if close_enough {
                 door.try_open(Some(&ctx.character.inventory));
             }
@@ -115,6 +119,9 @@ impl<'a> Behavior<'a> for MoveToTarget {
             ctx.is_moving = false;
             Ok(Status::Success)
         } else {
+            if ctx.is_running {
+                ctx.character.stamina.spend_on_run(ctx.dt);
+            }
             ctx.is_moving = true;
             Ok(Status::Running)
         }
@@ -3,6 +3,7 @@ use crate::{
     character::{CharacterMessage, CharacterMessageData},
     level::hit_box::LimbType,
     weapon::{weapon_ref, Weapon, WeaponMessage, WeaponMessageData},
+    Game,
 };
 use fyrox::core::ok_or_return;
 use fyrox::graph::SceneGraph;
@@ -41,8 +42,13 @@ impl<'a> Behavior<'a> for ShootTarget {
                         .try_extract_exact_items(ammo_item, ammo_per_shot)
                         == ammo_per_shot
                     {
-                        context.v_recoil.set_target(weapon.gen_v_recoil_angle());
-                        context.h_recoil.set_target(weapon.gen_h_recoil_angle());
+                        let mut rng = context.plugins.get::<Game>().rng();
+                        context
+                            .v_recoil
+                            .set_target(weapon.gen_v_recoil_angle(&mut rng));
+                        context
+                            .h_recoil
+                            .set_target(weapon.gen_h_recoil_angle(&mut rng));
 
                         context.script_message_sender.send_to_target(
                             weapon_handle,
@@ -0,0 +1,26 @@
+//! Running bots spend stamina while chasing their targets, an exhausted bot stops to take a breath.
+
+use crate::bot::behavior::BehaviorContext;
+use fyrox::plugin::error::GameError;
+use fyrox::{
+    core::visitor::prelude::*,
+    utils::behavior::{Behavior, Status},
+};
+
+/// Keeps the bot in place while it is exhausted, fails otherwise.
+#[derive(Default, Debug, PartialEq, Visit, Eq, Clone)]
+pub struct TakeBreath;
+
+impl<'a> Behavior<'a> for TakeBreath {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, ctx: &mut Self::Context) -> Result<Status, GameError> {
+        if ctx.character.stamina.is_exhausted() {
+            ctx.character.stand_still(&mut ctx.scene.graph);
+            ctx.is_moving = false;
+            Ok(Status::Running)
+        } else {
+            Ok(Status::Failure)
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Reactions of bots to their status effects.
+
+use crate::bot::behavior::BehaviorContext;
+use fyrox::plugin::error::GameError;
+use fyrox::{
+    core::visitor::prelude::*,
+    utils::behavior::{Behavior, Status},
+};
+
+/// Keeps the bot in place while it is stunned, fails otherwise.
+#[derive(Default, Debug, PartialEq, Visit, Eq, Clone)]
+pub struct Stunned;
+
+impl<'a> Behavior<'a> for Stunned {
+    type Context = BehaviorContext<'a>;
+
+    fn tick(&mut self, ctx: &mut Self::Context) -> Result<Status, GameError> {
+        if ctx.character.status_effects.is_stunned() {
+            ctx.character.stand_still(&mut ctx.scene.graph);
+            ctx.is_moving = false;
+            Ok(Status::Running)
+        } else {
+            Ok(Status::Failure)
+        }
+    }
+}
@@ -1,10 +1,9 @@
 //! Bots can threaten the player before attack, this mod has behavior nodes for this.
 
-use crate::{bot::behavior::BehaviorContext, utils};
+use crate::{bot::behavior::BehaviorContext, utils, Game};
 use fyrox::plugin::error::GameError;
 use fyrox::{
     core::{rand::Rng, visitor::prelude::*},
-    rand::{self},
     utils::behavior::{Behavior, Status},
 };
 
@@ -17,10 +16,12 @@ impl<'a> Behavior<'a> for ThreatenTarget {
     type Context = BehaviorContext<'a>;
 
     fn tick(&mut self, ctx: &mut Self::Context) -> Result<Status, GameError> {
+        let game = ctx.plugins.get::<Game>();
+        let mut rng = game.rng();
         if let Some(upper_body_layer) = ctx.state_machine.upper_body_layer(&ctx.scene.graph) {
             if upper_body_layer.active_state() == ctx.state_machine.threaten_state {
                 if !self.in_progress {
-                    utils::try_play_random_sound(ctx.scream_sounds, &mut ctx.scene.graph);
+                    utils::try_play_random_sound(ctx.scream_sounds, &mut ctx.scene.graph, &mut rng);
                 }
 
                 self.in_progress = true;
@@ -28,7 +29,8 @@ impl<'a> Behavior<'a> for ThreatenTarget {
                 Ok(Status::Running)
             } else if self.in_progress {
                 self.in_progress = false;
-                *ctx.threaten_timeout = rand::thread_rng().gen_range(20.0..60.0);
+                *ctx.threaten_timeout = rng.gen_range(20.0..60.0)
+                    * game.difficulty().tuning().bot_threaten_timeout_factor;
                 Ok(Status::Success)
             } else {
                 ctx.is_screaming = true;
@@ -1,16 +1,23 @@
 use crate::level::hit_box::HitBoxDamage;
 use crate::{
+    achievements::AchievementEvent,
     bot::{
         behavior::{BehaviorContext, BotBehavior},
         state_machine::{StateMachine, StateMachineInput},
     },
-    character::{Character, CharacterMessage, CharacterMessageData},
-    door::{door_mut, door_ref, DoorContainer},
+    character::{Character, CharacterMessage, CharacterMessageData, DamageDealer},
+    debug_layers::{DebugLayer, DebugLayers},
+    door::{door_mut, door_ref, Door, DoorContainer},
     level::{
+        explosive_barrel::ExplosiveBarrel,
         hit_box::LimbType,
         hit_box::{HitBox, HitBoxMessage},
     },
+    profiler,
+    rng::GameRng,
     sound::SoundManager,
+    statistics::StatisticsEvent,
+    telemetry::{self, TelemetryEvent},
     utils::{self, BodyImpactHandler},
     weapon::Weapon,
     weapon::WeaponMessage,
@@ -24,7 +31,7 @@ use fyrox::{
         algebra::{Point3, UnitQuaternion, Vector3},
         arrayvec::ArrayVec,
         color::Color,
-        math::SmoothAngle,
+        math::{frustum::Frustum, SmoothAngle},
         pool::Handle,
         reflect::prelude::*,
         stub_uuid_provider,
@@ -152,6 +159,12 @@ pub struct Bot {
     despawn_asset: Option<ModelResource>,
     despawn_timeout: f32,
     last_position: Vector3<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    last_damage_dealer: DamageDealer,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    frustum: Frustum,
 }
 
 impl Deref for Bot {
@@ -207,11 +220,17 @@ impl Default for Bot {
             despawn_timeout: 30.0,
             prev_is_dead: false,
             last_position: Default::default(),
+            last_damage_dealer: Default::default(),
+            frustum: Default::default(),
         }
     }
 }
 
 impl Bot {
+    /// Bots that are faster than this (without the difficulty applied) run when they chase their
+    /// targets and spend stamina on it, slower ones just walk.
+    pub const RUN_SPEED_THRESHOLD: f32 = 2.0;
+
     #[allow(clippy::unnecessary_to_owned)] // false positive
     fn check_doors(&mut self, scene: &mut Scene, door_container: &DoorContainer) {
         if let Some(target) = self.target.as_ref() {
@@ -220,6 +239,7 @@ impl Bot {
             let position = self.position(&scene.graph);
             let ray_direction = target.position - position;
 
+            profiler::count("Raycasts");
             scene.graph.physics.cast_ray(
                 RayCastOptions {
                     ray_origin: Point3::from(position),
@@ -235,7 +255,8 @@ impl Bot {
                 for &door_handle in &door_container.doors {
                     let door = door_ref(door_handle, &scene.graph);
 
-                    let close_enough = position.metric_distance(&door.initial_position()) < 1.25;
+                    let close_enough = position.metric_distance(&door.initial_position())
+                        < Door::INTERACTION_RADIUS;
                     if !close_enough {
                         continue;
                     }
@@ -255,18 +276,22 @@ impl Bot {
         }
     }
 
-    pub fn debug_draw(&self, context: &mut SceneDrawingContext) {
-        for pts in self.agent.path().windows(2) {
-            let a = pts[0];
-            let b = pts[1];
-            context.add_line(scene::debug::Line {
-                begin: a,
-                end: b,
-                color: Color::from_rgba(255, 0, 0, 255),
-            });
+    pub fn debug_draw(&self, layers: &DebugLayers, context: &mut SceneDrawingContext) {
+        if layers.is_enabled(DebugLayer::BotPaths) {
+            for pts in self.agent.path().windows(2) {
+                let a = pts[0];
+                let b = pts[1];
+                context.add_line(scene::debug::Line {
+                    begin: a,
+                    end: b,
+                    color: Color::from_rgba(255, 0, 0, 255),
+                });
+            }
         }
 
-        // context.draw_frustum(&self.frustum, Color::from_rgba(0, 200, 0, 255)); TODO
+        if layers.is_enabled(DebugLayer::BotFrustums) {
+            context.draw_frustum(&self.frustum, Color::from_rgba(0, 200, 0, 255));
+        }
     }
 
     pub fn set_target(&mut self, handle: Handle<Node>, position: Vector3<f32>) {
@@ -277,6 +302,7 @@ impl Bot {
         &mut self,
         scene: &mut Scene,
         sound_manager: &SoundManager,
+        mut rng: &GameRng,
     ) -> GameResult {
         let absm = scene
             .graph
@@ -315,14 +341,15 @@ impl Bot {
                 let begin = scene.graph[self.model].global_position() + Vector3::new(0.0, 0.5, 0.0);
 
                 self.character
-                    .footstep_ray_check(begin, scene, sound_manager);
+                    .footstep_ray_check(begin, scene, sound_manager, rng);
             }
         }
 
         for (_, event) in upper_layer_events.events {
             if event.name == StateMachine::HIT_BEGIN_SIGNAL {
                 self.melee_attack_context = Some(Default::default());
-                utils::try_play_random_sound(&self.attack_sounds, &mut scene.graph);
+                self.stamina.spend_on_melee();
+                utils::try_play_random_sound(&self.attack_sounds, &mut scene.graph, &mut rng);
             } else if event.name == StateMachine::HIT_END_SIGNAL {
                 self.melee_attack_context = None;
             }
@@ -339,6 +366,8 @@ impl Bot {
     }
 
     fn on_damage(&mut self, damage: &HitBoxDamage, ctx: &mut ScriptMessageContext) -> GameResult {
+        self.last_damage_dealer = damage.dealer;
+
         if let Some((character_handle, character)) = damage.dealer.as_character(&ctx.scene.graph) {
             self.set_target(character_handle, character.position(&ctx.scene.graph));
         }
@@ -361,7 +390,8 @@ impl Bot {
         let graph = &ctx.scene.graph;
         if !self.is_dead(graph) && !utils::is_any_sound_playing(&self.pain_sounds, graph) {
             self.restoration_time = 0.8;
-            utils::try_play_random_sound(&self.pain_sounds, &mut ctx.scene.graph);
+            let mut rng = ctx.plugins.get::<Game>().rng();
+            utils::try_play_random_sound(&self.pain_sounds, &mut ctx.scene.graph, &mut rng);
         }
         Ok(())
     }
@@ -437,6 +467,7 @@ impl ScriptTrait for Bot {
         if let Some(despawn_asset) = self.despawn_asset.as_ref() {
             let mut intersections = Vec::new();
 
+            profiler::count("Raycasts");
             ctx.scene.graph.physics.cast_ray(
                 RayCastOptions {
                     ray_origin: Point3::from(self.last_position),
@@ -466,7 +497,16 @@ impl ScriptTrait for Bot {
                 return Ok(());
             }
 
-            let level = ctx.plugins.get::<Game>().level.as_ref().unwrap();
+            let game = ctx.plugins.get::<Game>();
+            let level = game.level.as_ref().unwrap();
+
+            game.record_telemetry(|| {
+                TelemetryEvent::from_character_message(
+                    &ctx.scene.graph,
+                    ctx.handle,
+                    &char_message.data,
+                )
+            });
 
             self.character.on_character_message(
                 &char_message.data,
@@ -487,8 +527,19 @@ impl ScriptTrait for Bot {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Bot");
+        // The world could be frozen in photo mode.
+        if ctx.plugins.get::<Game>().is_paused() {
+            return Ok(());
+        }
+
         let game = ctx.plugins.get::<Game>();
         let level = game.level.as_ref().unwrap();
+        let tuning = game.difficulty().tuning();
+
+        self.character.stamina.update(ctx.dt);
+        self.character
+            .update_status_effects(ctx.dt, &ctx.scene.graph, ctx.message_sender);
 
         let need_to_melee_attack;
 
@@ -497,6 +548,14 @@ impl ScriptTrait for Bot {
         let attack_animation_index;
         let is_screaming;
         {
+            let _scope = profiler::scope_with(|| {
+                format!(
+                    "Bot behavior: {} #{}",
+                    ctx.scene.graph[ctx.handle].name(),
+                    ctx.handle.index()
+                )
+            });
+
             let mut behavior_ctx = BehaviorContext {
                 scene: ctx.scene,
                 actors: &level.actors,
@@ -512,7 +571,8 @@ impl ScriptTrait for Bot {
                 restoration_time: self.restoration_time,
                 v_recoil: &mut self.v_recoil,
                 h_recoil: &mut self.h_recoil,
-                move_speed: self.walk_speed,
+                move_speed: self.walk_speed * tuning.bot_walk_speed_factor,
+                is_running: self.walk_speed > Self::RUN_SPEED_THRESHOLD,
                 threaten_timeout: &mut self.threaten_timeout,
                 script_message_sender: ctx.message_sender,
                 navmesh: level.navmesh,
@@ -520,11 +580,12 @@ impl ScriptTrait for Bot {
                 pitch: &mut self.pitch,
                 scream_sounds: &self.scream_sounds,
                 plugins: &ctx.plugins,
+                frustum: &mut self.frustum,
 
                 // Output
                 hostility: self.hostility,
-                v_aim_angle_hack: self.v_aim_angle_hack,
-                h_aim_angle_hack: self.h_aim_angle_hack,
+                v_aim_angle_hack: self.v_aim_angle_hack * tuning.bot_aim_angle_hack_factor,
+                h_aim_angle_hack: self.h_aim_angle_hack * tuning.bot_aim_angle_hack_factor,
                 attack_animation_index: 0,
                 movement_speed_factor: 1.0,
                 is_moving: false,
@@ -552,7 +613,7 @@ impl ScriptTrait for Bot {
                 .set_value_and_mark_modified(true);
         }
 
-        self.update_melee_attack(ctx.scene, ctx.message_sender, ctx.handle)?;
+        self.update_melee_attack(ctx.scene, ctx.message_sender, ctx.handle, game.rng())?;
         self.check_doors(ctx.scene, &level.doors_container);
 
         let no_leg = self
@@ -567,8 +628,9 @@ impl ScriptTrait for Bot {
                 dead: is_dead,
                 attack: need_to_melee_attack,
                 attack_animation_index: attack_animation_index as u32,
+                attack_speed: self.stamina.melee_speed(),
                 aim: is_aiming,
-                badly_damaged: self.restoration_time > 0.0,
+                badly_damaged: self.restoration_time > 0.0 || self.status_effects.is_stunned(),
                 movement_type: if no_leg {
                     MovementType::Crawl
                 } else {
@@ -592,7 +654,7 @@ impl ScriptTrait for Bot {
                 * UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.h_recoil.angle()),
         );
 
-        self.handle_animation_events(ctx.scene, &level.sound_manager)?;
+        self.handle_animation_events(ctx.scene, &level.sound_manager, game.rng())?;
 
         let node = &mut ctx.scene.graph[ctx.handle];
 
@@ -613,6 +675,29 @@ impl ScriptTrait for Bot {
         self.last_position = node.global_position();
 
         if died {
+            game.record_telemetry(|| {
+                TelemetryEvent::kill(&ctx.scene.graph, ctx.handle, &self.last_damage_dealer)
+            });
+
+            let killed_by_player = self
+                .last_damage_dealer
+                .as_character(&ctx.scene.graph)
+                .is_some_and(|(handle, _)| handle == level.player);
+            if killed_by_player {
+                game.record_statistics(StatisticsEvent::Kill {
+                    victim: telemetry::entity_name(&ctx.scene.graph, ctx.handle),
+                });
+            }
+
+            if ctx
+                .scene
+                .graph
+                .try_get_script_of::<ExplosiveBarrel>(self.last_damage_dealer.entity)
+                .is_ok()
+            {
+                game.record_achievement_event(AchievementEvent::ExplosiveBarrelKill);
+            }
+
             for (_, node) in ctx.scene.graph.traverse_iter_mut(ctx.handle) {
                 let sound = some_or_continue!(node.component_mut::<Sound>());
                 sound.set_gain(0.0);
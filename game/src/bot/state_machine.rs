@@ -1,4 +1,4 @@
-use crate::bot::MovementType;
+use crate::{bot::MovementType, utils};
 use fyrox::graph::SceneGraph;
 use fyrox::{
     core::pool::Handle,
@@ -11,6 +11,8 @@ pub struct StateMachineInput {
     pub dead: bool,
     pub attack: bool,
     pub attack_animation_index: u32,
+    /// Speed of the attack animations, see [`crate::stamina::Stamina::melee_speed`].
+    pub attack_speed: f32,
     pub aim: bool,
     pub badly_damaged: bool,
     pub movement_type: MovementType,
@@ -56,11 +58,13 @@ impl StateMachine {
     }
 
     pub fn apply(&mut self, scene: &mut Scene, input: StateMachineInput) {
-        scene
+        let absm = scene
             .graph
             .try_get_mut_of_type::<AnimationBlendingStateMachine>(self.absm)
-            .unwrap()
-            .machine_mut()
+            .unwrap();
+
+        let animation_player = absm.animation_player();
+        absm.machine_mut()
             .get_value_mut_silent()
             .set_rule("Attack", input.attack)
             .set_index("AttackAnimation", input.attack_animation_index)
@@ -70,6 +74,11 @@ impl StateMachine {
             .set_rule("Dead", input.dead)
             .set_rule("WasHit", input.badly_damaged)
             .set_index("MovementType", input.movement_type as u32);
+
+        let animations = utils::fetch_animation_container_mut(&mut scene.graph, animation_player);
+        for animation in self.attack_animations.iter() {
+            animations[*animation].set_speed(input.attack_speed);
+        }
     }
 
     pub fn fetch_layer<'a>(&self, graph: &'a Graph, idx: usize) -> Option<&'a MachineLayer> {
@@ -1,10 +1,15 @@
 use crate::{
+    damage::{DamageType, Resistances},
     inventory::Inventory,
     level::{
         hit_box::{HitBox, HitBoxDamage, HitBoxHeal, HitBoxMessage, LimbType},
         item::ItemAction,
     },
+    profiler,
+    rng::GameRng,
     sound::{SoundKind, SoundManager},
+    stamina::Stamina,
+    status_effect::{StatusEffectKind, StatusEffects},
     utils,
     weapon::{weapon_mut, WeaponMessage, WeaponMessageData},
     Item, Weapon,
@@ -73,8 +78,17 @@ pub enum CharacterMessageData {
     SelectWeapon(ModelResource),
     AddWeapon(ModelResource),
     PickupItem(Handle<Node>),
-    DropItems { item: ModelResource, count: u32 },
-    UseItem { item: ModelResource },
+    DropItems {
+        item: ModelResource,
+        count: u32,
+    },
+    UseItem {
+        item: ModelResource,
+    },
+    ApplyStatusEffect {
+        kind: StatusEffectKind,
+        dealer: Handle<Node>,
+    },
 }
 
 #[derive(Debug, ScriptMessagePayload)]
@@ -97,6 +111,13 @@ pub struct Character {
     pub punch_sounds: InheritableVariable<Vec<Handle<Node>>>,
     #[reflect(min_value = 0.0, max_value = 20.0)]
     melee_attack_damage: InheritableVariable<f32>,
+    /// An effect that is applied to the characters hit by melee attacks.
+    pub melee_status_effect: InheritableVariable<Option<StatusEffectKind>>,
+    pub stamina: Stamina,
+    /// Applied to the damage of every hit box of the character.
+    pub resistances: Resistances,
+    #[reflect(hidden)]
+    pub status_effects: StatusEffects,
     #[visit(skip)]
     #[reflect(hidden)]
     pub hit_boxes: FxHashSet<Handle<Collider>>,
@@ -125,6 +146,10 @@ impl Default for Character {
             attack_sounds: Default::default(),
             punch_sounds: Default::default(),
             melee_attack_damage: 20.0.into(),
+            melee_status_effect: Default::default(),
+            stamina: Default::default(),
+            resistances: Default::default(),
+            status_effects: Default::default(),
             melee_attack_context: None,
         }
     }
@@ -286,7 +311,57 @@ impl Character {
                         }),
                     )
                 }
+                self.status_effects.clear(StatusEffectKind::Burning);
             }
+            ItemAction::Bandage => self.status_effects.clear(StatusEffectKind::Bleeding),
+            ItemAction::Antidote => self.status_effects.clear(StatusEffectKind::Poison),
+        }
+    }
+
+    /// Advances the status effects and deals their damage, the effects are removed on death.
+    pub fn update_status_effects(
+        &mut self,
+        dt: f32,
+        graph: &Graph,
+        script_message_sender: &ScriptMessageSender,
+    ) {
+        if self.is_dead(graph) {
+            self.status_effects.clear_all();
+            return;
+        }
+
+        let mut damages = Vec::new();
+        self.status_effects.update(dt, &mut damages);
+        if damages.is_empty() {
+            return;
+        }
+
+        // The whole tick is taken by a single hit box, so the damage does not depend on the
+        // amount of hit boxes and on their hierarchy.
+        let Some(hit_box) = self
+            .hit_box_iter(graph)
+            .find(|(_, hit_box)| *hit_box.critical_for_survival && !hit_box.is_sliced_off())
+            .or_else(|| self.hit_box_iter(graph).next())
+            .map(|(handle, _)| handle)
+        else {
+            return;
+        };
+        for damage in damages {
+            script_message_sender.send_hierarchical(
+                hit_box,
+                RoutingStrategy::Up,
+                HitBoxMessage::Damage(HitBoxDamage {
+                    hit_box,
+                    damage: damage.amount,
+                    damage_type: damage.damage_type,
+                    dealer: DamageDealer {
+                        entity: damage.dealer,
+                    },
+                    position: None,
+                    is_melee: false,
+                    is_status_effect: true,
+                }),
+            );
         }
     }
 
@@ -311,6 +386,7 @@ impl Character {
         scene: &mut Scene,
         message_sender: &ScriptMessageSender,
         self_handle: Handle<Node>,
+        mut rng: &GameRng,
     ) -> GameResult {
         let attack_context = some_or_return!(self.melee_attack_context.as_mut(), Ok(()));
 
@@ -365,6 +441,19 @@ impl Character {
                         continue;
                     }
                     attack_context.damaged_characters.insert(parent_character);
+
+                    if let Some(kind) = *self.melee_status_effect {
+                        message_sender.send_to_target(
+                            parent_character,
+                            CharacterMessage {
+                                character: parent_character,
+                                data: CharacterMessageData::ApplyStatusEffect {
+                                    kind,
+                                    dealer: self_handle,
+                                },
+                            },
+                        );
+                    }
                 }
 
                 need_play_punch_sound = true;
@@ -375,6 +464,7 @@ impl Character {
                     HitBoxMessage::Damage(HitBoxDamage {
                         hit_box: intersected_hit_box,
                         damage: *self.melee_attack_damage,
+                        damage_type: DamageType::Blunt,
                         dealer: DamageDealer {
                             entity: self_handle,
                         },
@@ -383,13 +473,14 @@ impl Character {
                             direction: Vector3::new(0.0, 0.0, 1.0),
                         }),
                         is_melee: true,
+                        is_status_effect: false,
                     }),
                 );
             }
         }
 
         if need_play_punch_sound {
-            utils::try_play_random_sound(&self.punch_sounds, &mut scene.graph);
+            utils::try_play_random_sound(&self.punch_sounds, &mut scene.graph, &mut rng);
         }
 
         Ok(())
@@ -522,6 +613,11 @@ impl Character {
                     }
                 });
             }
+            &CharacterMessageData::ApplyStatusEffect { kind, dealer } => {
+                if !self.is_dead(&scene.graph) {
+                    self.status_effects.apply(kind, dealer);
+                }
+            }
             _ => (),
         }
     }
@@ -608,11 +704,13 @@ impl Character {
         begin: Vector3<f32>,
         scene: &mut Scene,
         manager: &SoundManager,
+        mut rng: &GameRng,
     ) {
         let mut query_buffer = Vec::new();
 
         let ray = Ray::from_two_points(begin, begin + Vector3::new(0.0, -100.0, 0.0));
 
+        profiler::count("Raycasts");
         scene.graph.physics.cast_ray(
             RayCastOptions {
                 ray_origin: Point3::from(ray.origin),
@@ -637,6 +735,7 @@ impl Character {
                 0.45,
                 1.0,
                 0.3,
+                &mut rng,
             );
         }
     }
@@ -1,31 +1,63 @@
-use crate::control_scheme::ControlScheme;
+use crate::{
+    control_scheme::{ControlScheme, LegacyControlScheme},
+    debug_layers::DebugLayers,
+};
 use fyrox::{core::log::Log, core::visitor::prelude::*, renderer::QualitySettings};
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
 use std::{
     fs::File,
     ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
 };
 
 #[derive(Debug, Clone)]
 pub struct Config {
     need_save: bool,
+    // `None` for configs that are never written to disk.
+    path: Option<PathBuf>,
     data: ConfigData,
 }
 
 impl Config {
     pub fn load() -> Self {
+        Self::load_from(ConfigData::PATH)
+    }
+
+    /// Loads the config from the given path, the changes will be saved to the same path. Default
+    /// config is used if the file does not exist or could not be read.
+    pub fn load_from(path: impl AsRef<Path>) -> Self {
+        let (data, migrated) = ConfigData::load(path.as_ref());
+        Self {
+            // Migrated settings are written back in the new format.
+            need_save: migrated,
+            path: Some(path.as_ref().to_path_buf()),
+            data,
+        }
+    }
+
+    /// Creates the default config that is not read from and never saved to disk, so headless runs
+    /// do not depend on the settings of the user and do not change them.
+    pub fn in_memory() -> Self {
         Self {
             need_save: false,
-            data: ConfigData::load(),
+            path: None,
+            data: Default::default(),
         }
     }
 
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn save_if_needed(&mut self) {
-        if self.need_save {
-            self.data.save();
-            self.need_save = false;
+        if !self.need_save {
+            return;
         }
+        if let Some(path) = self.path.as_ref() {
+            self.data.save(path);
+        }
+        self.need_save = false;
     }
 }
 
@@ -61,27 +93,135 @@ impl Default for SoundConfig {
     }
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TelemetryConfig {
+    pub enabled: bool,
+    /// Path of a file that the events will be appended to.
+    pub path: PathBuf,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: "data/telemetry.jsonl".into(),
+        }
+    }
+}
+
+/// Gameplay events that could trigger an autosave.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AutosaveTrigger {
+    LevelLoaded,
+    EnemyTrapFinished,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AutosaveConfig {
+    pub enabled: bool,
+    /// Amount of autosave slots, the oldest autosave is overwritten when every slot is used.
+    pub slots: usize,
+    pub triggers: Vec<AutosaveTrigger>,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            slots: 3,
+            triggers: vec![
+                AutosaveTrigger::LevelLoaded,
+                AutosaveTrigger::EnemyTrapFinished,
+            ],
+        }
+    }
+}
+
+impl AutosaveConfig {
+    pub fn is_triggered_by(&self, trigger: AutosaveTrigger) -> bool {
+        self.enabled && self.triggers.contains(&trigger)
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct ConfigData {
     pub graphics: QualitySettings,
     pub controls: ControlScheme,
     pub sound: SoundConfig,
     pub show_debug_info: bool,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    /// Writes a human-readable copy of every saved game next to it, for debugging.
+    #[serde(default)]
+    pub dump_saved_games: bool,
+    #[serde(default)]
+    pub autosave: AutosaveConfig,
+    /// Code of the language, see [`crate::localization`]. English is used if empty.
+    #[serde(default)]
+    pub language: String,
+    /// Debug visualization layers that are drawn over the level.
+    #[serde(default)]
+    pub debug_layers: DebugLayers,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LegacyConfigData {
+    controls: LegacyControlScheme,
 }
 
 impl ConfigData {
     const PATH: &'static str = "data/configs/settings.ron";
 
-    fn load() -> Self {
-        File::open(Self::PATH)
-            .ok()
-            .and_then(|file| ron::de::from_reader(file).ok())
-            .unwrap_or_default()
+    /// Loads the settings and migrates them from older formats. Returns `true` along with the
+    /// settings if they were migrated. Defaults are used if the settings could not be read, the
+    /// file is left intact until the settings are changed.
+    fn load(path: &Path) -> (Self, bool) {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    Log::err(format!(
+                        "Unable to read config from {}, defaults are used. Reason: {err}",
+                        path.display()
+                    ));
+                }
+                return Default::default();
+            }
+        };
+        let mut data = match ron::from_str::<Self>(&text) {
+            Ok(data) => data,
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to parse config {}, defaults are used. Reason: {err}",
+                    path.display()
+                ));
+                return Default::default();
+            }
+        };
+
+        let migrated = data.controls.version < ControlScheme::VERSION;
+        if data.controls.version == 0 {
+            // Settings written before the action map was introduced have one button per action.
+            match ron::from_str::<LegacyConfigData>(&text) {
+                Ok(legacy) => legacy.controls.migrate(&mut data.controls),
+                Err(err) => {
+                    Log::warn(format!(
+                        "Unable to migrate the controls, defaults are used. Reason: {err}"
+                    ));
+                    data.controls.reset();
+                }
+            }
+        }
+        data.controls.upgrade();
+        data.controls.add_missing_actions();
+
+        (data, migrated)
     }
 
-    fn save(&self) {
-        let Ok(file) = File::create(Self::PATH) else {
-            Log::err("Unable to save config!");
+    fn save(&self, path: &Path) {
+        let Ok(file) = File::create(path) else {
+            Log::err(format!("Unable to save config to {}!", path.display()));
             return;
         };
 
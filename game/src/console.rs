@@ -0,0 +1,683 @@
+//! Developer console. It is an overlay in the main user interface that parses entered lines and
+//! executes registered commands. Commands could be registered by anyone who has access to the
+//! [`Game`] plugin (including scripts) using [`Console::register`].
+
+use crate::{
+    bot::Bot, character::Character, debug_layers::DebugLayer, level::hit_box::HitBox,
+    message::Message, CameraController, Game, Item, Player,
+};
+use fyrox::{
+    core::{
+        algebra::{Point3, UnitQuaternion, Vector3},
+        color::Color,
+        futures::executor::block_on,
+        pool::Handle,
+    },
+    event::{ElementState, Event, WindowEvent},
+    graph::SceneGraph,
+    gui::{
+        border::{Border, BorderBuilder},
+        brush::Brush,
+        font::FontResource,
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        text::{Text, TextBuilder, TextMessage},
+        text_box::{TextBox, TextBoxBuilder, TextCommitMode},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Thickness, UserInterface, VerticalAlignment,
+    },
+    keyboard::{KeyCode, PhysicalKey},
+    plugin::PluginContext,
+    resource::model::{Model, ModelResourceExtension},
+    scene::{
+        collider::Collider,
+        graph::{physics::RayCastOptions, Graph},
+        Scene,
+    },
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::{Debug, Formatter},
+    path::PathBuf,
+    rc::Rc,
+};
+
+/// A command handler. It takes arguments of a command (without its name) and returns either a
+/// text that will be printed to the console or an error.
+pub type ConsoleCommandHandler =
+    Rc<dyn Fn(&[&str], &mut Game, &mut PluginContext) -> Result<String, String>>;
+
+#[derive(Clone)]
+pub struct ConsoleCommand {
+    pub usage: String,
+    pub description: String,
+    pub handler: ConsoleCommandHandler,
+}
+
+/// Gameplay cheats, that could be toggled from the console.
+#[derive(Default, Debug, Clone)]
+pub struct Cheats {
+    /// Player's hit boxes ignore any damage.
+    pub god_mode: bool,
+    /// Player ignores gravity and collisions and flies in the direction of the camera.
+    pub noclip: bool,
+}
+
+#[derive(Default)]
+pub struct Console {
+    root: Handle<Border>,
+    output: Handle<Text>,
+    input: Handle<TextBox>,
+    input_text: String,
+    lines: VecDeque<String>,
+    commands: BTreeMap<String, ConsoleCommand>,
+    history: Vec<String>,
+    history_position: Option<usize>,
+    visible: bool,
+}
+
+impl Debug for Console {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Console")
+    }
+}
+
+const NO_LEVEL: &str = "There is no level loaded!";
+
+impl Console {
+    const MAX_LINES: usize = 64;
+    const HEIGHT: f32 = 300.0;
+
+    pub fn new(font: FontResource, ctx: &mut BuildContext) -> Self {
+        let output;
+        let input;
+        let root = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_height(Self::HEIGHT)
+                .with_vertical_alignment(VerticalAlignment::Top)
+                .with_background(Brush::Solid(Color::from_rgba(0, 0, 0, 200)).into())
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child({
+                                output = TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_row(0)
+                                        .with_margin(Thickness::uniform(2.0))
+                                        .with_vertical_alignment(VerticalAlignment::Bottom),
+                                )
+                                .with_font(font.clone())
+                                .with_font_size(14.0.into())
+                                .build(ctx);
+                                output
+                            })
+                            .with_child({
+                                input = TextBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_row(1)
+                                        .with_margin(Thickness::uniform(2.0)),
+                                )
+                                .with_font(font)
+                                .with_font_size(16.0.into())
+                                .with_text_commit_mode(TextCommitMode::Immediate)
+                                .build(ctx);
+                                input
+                            }),
+                    )
+                    .add_row(Row::stretch())
+                    .add_row(Row::strict(26.0))
+                    .add_column(Column::stretch())
+                    .build(ctx),
+                ),
+        )
+        .build(ctx);
+
+        let mut console = Self {
+            root,
+            output,
+            input,
+            ..Default::default()
+        };
+        console.register_builtin_commands();
+        console
+    }
+
+    /// Registers a new command. Existing command with the same name will be replaced. `usage`
+    /// should list the arguments of the command, for example `<x> <y> <z>`.
+    pub fn register<F>(&mut self, name: &str, usage: &str, description: &str, handler: F)
+    where
+        F: Fn(&[&str], &mut Game, &mut PluginContext) -> Result<String, String> + 'static,
+    {
+        self.commands.insert(
+            name.to_string(),
+            ConsoleCommand {
+                usage: usage.to_string(),
+                description: description.to_string(),
+                handler: Rc::new(handler),
+            },
+        );
+    }
+
+    pub fn unregister(&mut self, name: &str) -> Option<ConsoleCommand> {
+        self.commands.remove(name)
+    }
+
+    pub fn command(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.commands.get(name)
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool, ui: &UserInterface) {
+        self.visible = visible;
+        ui.send(self.root, WidgetMessage::Visibility(visible));
+        self.set_input(String::new(), ui);
+        if visible {
+            ui.send(self.input, WidgetMessage::Focus);
+        }
+    }
+
+    pub fn print(&mut self, text: impl AsRef<str>) {
+        for line in text.as_ref().lines() {
+            if self.lines.len() >= Self::MAX_LINES {
+                self.lines.pop_front();
+            }
+            self.lines.push_back(line.to_string());
+        }
+    }
+
+    fn sync_output(&self, ui: &UserInterface) {
+        let text = self.lines.iter().fold(String::new(), |mut text, line| {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(line);
+            text
+        });
+        ui.send(self.output, TextMessage::Text(text));
+    }
+
+    fn set_input(&mut self, text: String, ui: &UserInterface) {
+        ui.send(self.input, TextMessage::Text(text.clone()));
+        self.input_text = text;
+    }
+
+    fn navigate_history(&mut self, up: bool, ui: &UserInterface) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        let position = match (self.history_position, up) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => None,
+            (Some(position), true) => Some(position.saturating_sub(1)),
+            (Some(position), false) => {
+                if position + 1 < self.history.len() {
+                    Some(position + 1)
+                } else {
+                    None
+                }
+            }
+        };
+
+        self.history_position = position;
+        let text = position
+            .and_then(|position| self.history.get(position).cloned())
+            .unwrap_or_default();
+        self.set_input(text, ui);
+    }
+
+    fn autocomplete(&mut self, ui: &UserInterface) {
+        let prefix = self.input_text.trim_start();
+        if prefix.contains(' ') {
+            return;
+        }
+
+        let candidates = self
+            .commands
+            .keys()
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match candidates.as_slice() {
+            [] => (),
+            [single] => self.set_input(format!("{single} "), ui),
+            [first, rest @ ..] => {
+                let common_prefix = rest.iter().fold(first.clone(), |common, name| {
+                    common
+                        .chars()
+                        .zip(name.chars())
+                        .take_while(|(a, b)| a == b)
+                        .map(|(a, _)| a)
+                        .collect()
+                });
+                self.print(candidates.join("  "));
+                self.sync_output(ui);
+                self.set_input(common_prefix, ui);
+            }
+        }
+    }
+
+    /// Handles input while the console is open. Returns a line that should be executed, if any.
+    pub fn process_input_event(&mut self, event: &Event<()>, ui: &UserInterface) -> Option<String> {
+        if !self.visible {
+            return None;
+        }
+
+        let Event::WindowEvent {
+            event: WindowEvent::KeyboardInput { event: input, .. },
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        if input.state != ElementState::Pressed {
+            return None;
+        }
+
+        match input.physical_key {
+            PhysicalKey::Code(KeyCode::Enter | KeyCode::NumpadEnter) => {
+                // Console toggle key could be typed in the input field, remove it.
+                let line = self.input_text.replace('`', "").trim().to_string();
+                self.set_input(String::new(), ui);
+                self.history_position = None;
+                if !line.is_empty() {
+                    if self.history.last() != Some(&line) {
+                        self.history.push(line.clone());
+                    }
+                    return Some(line);
+                }
+            }
+            PhysicalKey::Code(KeyCode::ArrowUp) => self.navigate_history(true, ui),
+            PhysicalKey::Code(KeyCode::ArrowDown) => self.navigate_history(false, ui),
+            PhysicalKey::Code(KeyCode::Tab) => self.autocomplete(ui),
+            _ => (),
+        }
+
+        None
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage) {
+        if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.input
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.input_text.clone_from(text);
+            }
+        }
+    }
+
+    fn register_builtin_commands(&mut self) {
+        self.register("help", "", "Prints all available commands.", help);
+        self.register("clear", "", "Clears the console.", |_, game, ctx| {
+            game.console.lines.clear();
+            game.console.sync_output(ctx.user_interfaces.first());
+            Ok(String::new())
+        });
+        self.register(
+            "load_level",
+            "<path>",
+            "Loads a level.",
+            path_message(|path| Message::LoadLevel { path }),
+        );
+        self.register(
+            "save",
+            "<path>",
+            "Saves the game.",
+            path_message(Message::SaveGame),
+        );
+        self.register(
+            "load",
+            "<path>",
+            "Loads a saved game.",
+            path_message(Message::LoadGame),
+        );
+        self.register("end_game", "", "Ends the game.", |_, game, _| {
+            game.message_sender.send(Message::EndGame);
+            Ok(String::new())
+        });
+        self.register(
+            "spawn",
+            "<prefab>",
+            "Spawns a prefab at the crosshair.",
+            spawn,
+        );
+        self.register(
+            "give",
+            "<item> [count]",
+            "Adds an item to the player's inventory.",
+            give,
+        );
+        self.register("god", "", "Toggles god mode.", |_, game, _| {
+            game.cheats.god_mode = !game.cheats.god_mode;
+            Ok(format!("God mode: {}", on_off(game.cheats.god_mode)))
+        });
+        self.register("noclip", "", "Toggles noclip mode.", |_, game, _| {
+            game.cheats.noclip = !game.cheats.noclip;
+            Ok(format!("Noclip: {}", on_off(game.cheats.noclip)))
+        });
+        self.register(
+            "free_camera",
+            "",
+            "Toggles the free camera (photo mode).",
+            |_, game, ctx| {
+                if game.level.is_none() {
+                    return Err(NO_LEVEL.to_string());
+                }
+                game.toggle_free_camera(ctx)
+                    .map_err(|err| format!("{err:?}"))?;
+                Ok(format!(
+                    "Free camera: {}",
+                    on_off(game.free_camera.is_active())
+                ))
+            },
+        );
+        self.register(
+            "debug_layer",
+            "[layer] [on|off]",
+            "Toggles a debug visualization layer, prints all layers if no layer is specified.",
+            debug_layer,
+        );
+        self.register(
+            "profiler",
+            "",
+            "Toggles the script profiler overlay.",
+            |_, game, ctx| {
+                let visible = !game.profiler.is_visible();
+                game.profiler
+                    .set_visible(visible, ctx.user_interfaces.first());
+                Ok(format!("Profiler: {}", on_off(visible)))
+            },
+        );
+        self.register(
+            "profiler_capture",
+            "<frames> [path]",
+            "Writes the profiler samples of the given amount of frames to a CSV file.",
+            profiler_capture,
+        );
+        self.register("kill_bots", "", "Kills every bot on the level.", kill_bots);
+        self.register(
+            "teleport",
+            "<x> <y> <z>",
+            "Teleports the player to the given position.",
+            teleport,
+        );
+    }
+}
+
+impl Game {
+    pub fn console(&self) -> &Console {
+        &self.console
+    }
+
+    /// Gives access to the console, use it to register custom commands.
+    pub fn console_mut(&mut self) -> &mut Console {
+        &mut self.console
+    }
+
+    pub fn cheats(&self) -> &Cheats {
+        &self.cheats
+    }
+
+    /// Returns `true` if the hit box belongs to the player and god mode is enabled.
+    pub fn is_invulnerable_hit_box(&self, graph: &Graph, hit_box: Handle<Collider>) -> bool {
+        self.cheats.god_mode
+            && self.level.as_ref().is_some_and(|level| {
+                graph
+                    .try_get_script_component_of::<Character>(level.player)
+                    .is_ok_and(|character| character.has_hit_box(hit_box))
+            })
+    }
+
+    pub fn toggle_console(&mut self, ctx: &mut PluginContext) {
+        let visible = !self.console.is_visible();
+        self.console
+            .set_visible(visible, ctx.user_interfaces.first());
+    }
+
+    pub fn execute_console_command(&mut self, line: &str, ctx: &mut PluginContext) {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            return;
+        };
+        let args = words.collect::<Vec<_>>();
+
+        self.console.print(format!("> {line}"));
+
+        // Handler is cloned, because it needs mutable access to the game (and the console).
+        match self.console.command(name).map(|c| c.handler.clone()) {
+            Some(handler) => match handler(&args, self, ctx) {
+                Ok(output) => {
+                    if !output.is_empty() {
+                        self.console.print(output);
+                    }
+                }
+                Err(err) => self.console.print(format!("Error: {err}")),
+            },
+            None => self
+                .console
+                .print(format!("Unknown command `{name}`. Type `help` for help.")),
+        }
+
+        self.console.sync_output(ctx.user_interfaces.first());
+    }
+}
+
+fn on_off(state: bool) -> &'static str {
+    if state {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn path_message(
+    make_message: impl Fn(PathBuf) -> Message + 'static,
+) -> impl Fn(&[&str], &mut Game, &mut PluginContext) -> Result<String, String> {
+    move |args, game, _| {
+        let path = args.first().ok_or("Path is not specified!")?;
+        game.message_sender.send(make_message(PathBuf::from(path)));
+        Ok(String::new())
+    }
+}
+
+fn help(_: &[&str], game: &mut Game, _: &mut PluginContext) -> Result<String, String> {
+    Ok(game
+        .console
+        .commands
+        .iter()
+        .map(|(name, command)| format!("{name} {} - {}", command.usage, command.description))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn debug_layer(args: &[&str], game: &mut Game, _: &mut PluginContext) -> Result<String, String> {
+    let Some(name) = args.first() else {
+        return Ok(DebugLayer::ALL
+            .iter()
+            .map(|layer| {
+                format!(
+                    "{} - {}",
+                    layer.name(),
+                    on_off(game.config.debug_layers.is_enabled(*layer))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"));
+    };
+    let layer = DebugLayer::from_name(name).ok_or_else(|| format!("Unknown layer `{name}`!"))?;
+    let enabled = match args.get(1) {
+        Some(&"on") => true,
+        Some(&"off") => false,
+        Some(state) => return Err(format!("Invalid state `{state}`, use `on` or `off`!")),
+        None => !game.config.debug_layers.is_enabled(layer),
+    };
+    game.config.debug_layers.set_enabled(layer, enabled);
+    Ok(format!("{}: {}", layer.name(), on_off(enabled)))
+}
+
+fn profiler_capture(
+    args: &[&str],
+    game: &mut Game,
+    _: &mut PluginContext,
+) -> Result<String, String> {
+    let frames = args
+        .first()
+        .ok_or("Amount of frames is not specified!")?
+        .parse::<usize>()
+        .map_err(|err| format!("Invalid amount of frames: {err}"))?;
+    if frames == 0 {
+        return Err("Amount of frames must be greater than zero!".to_string());
+    }
+    let path = PathBuf::from(args.get(1).copied().unwrap_or("profiler_capture.csv"));
+    game.profiler.start_capture(&path, frames)?;
+    Ok(format!("Capturing {frames} frames to {}", path.display()))
+}
+
+fn level_scene<'a>(game: &Game, ctx: &'a mut PluginContext) -> Result<&'a mut Scene, String> {
+    let level = game.level.as_ref().ok_or(NO_LEVEL)?;
+    ctx.scenes
+        .try_get_mut(level.scene)
+        .map_err(|err| err.to_string())
+}
+
+fn crosshair_position(game: &Game, scene: &Scene) -> Result<Vector3<f32>, String> {
+    let level = game.level.as_ref().ok_or(NO_LEVEL)?;
+    let graph = &scene.graph;
+    let player = graph
+        .try_get_script_of::<Player>(level.player)
+        .map_err(|err| err.to_string())?;
+    let camera_controller = graph
+        .try_get_script_of::<CameraController>(player.camera_controller)
+        .map_err(|err| err.to_string())?;
+    let camera = graph
+        .try_get(camera_controller.camera())
+        .map_err(|err| err.to_string())?;
+
+    let origin = camera.global_position();
+    let direction = camera
+        .look_vector()
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector3::z);
+
+    let mut intersections = Vec::new();
+    graph.physics.cast_ray(
+        RayCastOptions {
+            ray_origin: Point3::from(origin),
+            ray_direction: direction,
+            max_len: 100.0,
+            groups: Default::default(),
+            sort_results: true,
+        },
+        &mut intersections,
+    );
+
+    Ok(intersections
+        .iter()
+        .find(|i| i.collider != player.capsule_collider && !player.has_hit_box(i.collider))
+        .map(|i| i.position.coords)
+        .unwrap_or_else(|| origin + direction.scale(5.0)))
+}
+
+fn spawn(args: &[&str], game: &mut Game, ctx: &mut PluginContext) -> Result<String, String> {
+    let path = args.first().ok_or("Prefab path is not specified!")?;
+    let prefab = block_on(ctx.resource_manager.request::<Model>(path))
+        .map_err(|err| format!("Unable to load {path}: {err:?}"))?;
+    let scene = level_scene(game, ctx)?;
+    let position = crosshair_position(game, scene)?;
+    prefab.instantiate_at(scene, position, UnitQuaternion::default());
+    Ok(format!("{path} was spawned at {position:?}"))
+}
+
+fn give(args: &[&str], game: &mut Game, ctx: &mut PluginContext) -> Result<String, String> {
+    let path = args.first().ok_or("Item path is not specified!")?;
+    let count = match args.get(1) {
+        Some(count) => count
+            .parse::<u32>()
+            .map_err(|err| format!("Invalid count: {err}"))?,
+        None => 1,
+    };
+    if count == 0 {
+        return Err("Count must be greater than zero!".to_string());
+    }
+
+    let item = block_on(ctx.resource_manager.request::<Model>(path))
+        .map_err(|err| format!("Unable to load {path}: {err:?}"))?;
+    if !Item::from_resource(&item, |item| item.is_some()) {
+        return Err(format!("{path} is not an item!"));
+    }
+
+    let player = game.level.as_ref().ok_or(NO_LEVEL)?.player;
+    let scene = level_scene(game, ctx)?;
+    scene
+        .graph
+        .try_get_script_of_mut::<Player>(player)
+        .map_err(|err| err.to_string())?
+        .inventory_mut()
+        .add_item(&item, count);
+
+    Ok(format!("{count} x {path} was added to the inventory"))
+}
+
+fn kill_bots(_: &[&str], game: &mut Game, ctx: &mut PluginContext) -> Result<String, String> {
+    let level = game.level.as_ref().ok_or(NO_LEVEL)?;
+    let scene = ctx
+        .scenes
+        .try_get_mut(level.scene)
+        .map_err(|err| err.to_string())?;
+
+    let mut hit_boxes = Vec::new();
+    let mut count = 0;
+    for actor in level.actors.iter() {
+        if let Ok(bot) = scene.graph.try_get_script_of::<Bot>(*actor) {
+            if !bot.is_dead(&scene.graph) {
+                hit_boxes.extend(bot.hit_boxes.iter().cloned());
+                count += 1;
+            }
+        }
+    }
+
+    for hit_box in hit_boxes {
+        if let Ok(hit_box) = scene
+            .graph
+            .try_get_script_component_of_mut::<HitBox>(hit_box)
+        {
+            hit_box.health.set_value_and_mark_modified(0.0);
+        }
+    }
+
+    Ok(format!("{count} bots were killed"))
+}
+
+fn teleport(args: &[&str], game: &mut Game, ctx: &mut PluginContext) -> Result<String, String> {
+    let [x, y, z] = args else {
+        return Err("Expected three coordinates!".to_string());
+    };
+    let parse = |value: &str| {
+        value
+            .parse::<f32>()
+            .map_err(|err| format!("Invalid coordinate {value}: {err}"))
+    };
+    let position = Vector3::new(parse(x)?, parse(y)?, parse(z)?);
+
+    let player = game.level.as_ref().ok_or(NO_LEVEL)?.player;
+    let scene = level_scene(game, ctx)?;
+    let body = scene
+        .graph
+        .try_get_script_component_of::<Character>(player)
+        .map_err(|err| err.to_string())?
+        .body;
+    scene
+        .graph
+        .try_get_mut(body)
+        .map_err(|err| err.to_string())?
+        .local_transform_mut()
+        .set_position(position);
+
+    Ok(format!("Teleported to {position:?}"))
+}
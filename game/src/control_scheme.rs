@@ -1,5 +1,11 @@
-use fyrox::keyboard::KeyCode;
-use serde::{Deserialize, Serialize};
+//! Control scheme maps the actions of the game to the buttons and axes of input devices. Every
+//! action could have several bindings, a binding could require modifier keys to be held. The
+//! scheme is stored in the settings, settings of older versions (one button per action) are
+//! migrated when loaded, see [`LegacyControlScheme`].
+
+use fyrox::{core::algebra::Vector2, keyboard::KeyCode};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{borrow::Cow, collections::BTreeMap};
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Deserialize, Serialize)]
 pub enum ControlButton {
@@ -7,232 +13,861 @@ pub enum ControlButton {
     Key(KeyCode),
     WheelUp,
     WheelDown,
+    Gamepad(GamepadButton),
 }
 
 impl ControlButton {
-    pub fn name(self) -> &'static str {
+    pub fn name(self) -> Cow<'static, str> {
         match self {
             ControlButton::Mouse(index) => match index {
-                0 => "LMB",
-                1 => "RMB",
-                2 => "MMB",
-                3 => "MB4",
-                4 => "MB5",
-                _ => "Unknown",
+                0 => "LMB".into(),
+                1 => "RMB".into(),
+                2 => "MMB".into(),
+                // Extra buttons are numbered the same way as the side ones.
+                _ => format!("MB{}", index + 1).into(),
+            },
+            ControlButton::Key(code) => fyrox::utils::virtual_key_code_name(code).into(),
+            ControlButton::WheelUp => "Wheel Up".into(),
+            ControlButton::WheelDown => "Wheel Down".into(),
+            ControlButton::Gamepad(button) => button.name().into(),
+        }
+    }
+
+    /// Reserved buttons open and close the menus, they could not be bound to actions.
+    pub fn is_reserved(self) -> bool {
+        matches!(
+            self,
+            ControlButton::Key(KeyCode::Escape) | ControlButton::Gamepad(GamepadButton::Start)
+        )
+    }
+
+    pub fn is_gamepad(self) -> bool {
+        matches!(self, ControlButton::Gamepad(_))
+    }
+
+    /// Returns the modifier the button represents, it is empty for non-modifier buttons.
+    pub fn modifier(self) -> Modifiers {
+        match self {
+            ControlButton::Key(KeyCode::ShiftLeft | KeyCode::ShiftRight) => Modifiers {
+                shift: true,
+                ..Default::default()
+            },
+            ControlButton::Key(KeyCode::ControlLeft | KeyCode::ControlRight) => Modifiers {
+                ctrl: true,
+                ..Default::default()
+            },
+            ControlButton::Key(KeyCode::AltLeft | KeyCode::AltRight) => Modifiers {
+                alt: true,
+                ..Default::default()
             },
-            ControlButton::Key(code) => fyrox::utils::virtual_key_code_name(code),
-            ControlButton::WheelUp => "Wheel Up",
-            ControlButton::WheelDown => "Wheel Down",
+            _ => Default::default(),
+        }
+    }
+
+    /// Mouse wheel "buttons" are never held, they are released right after they are pressed.
+    pub fn is_momentary(self) -> bool {
+        matches!(self, ControlButton::WheelUp | ControlButton::WheelDown)
+    }
+}
+
+/// Buttons of a gamepad, they are named after their position, so the layout of every gamepad is
+/// the same.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Deserialize, Serialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    pub fn name(self) -> &'static str {
+        match self {
+            GamepadButton::South => "Pad A",
+            GamepadButton::East => "Pad B",
+            GamepadButton::North => "Pad Y",
+            GamepadButton::West => "Pad X",
+            GamepadButton::LeftBumper => "Pad LB",
+            GamepadButton::RightBumper => "Pad RB",
+            GamepadButton::LeftTrigger => "Pad LT",
+            GamepadButton::RightTrigger => "Pad RT",
+            GamepadButton::Select => "Pad Back",
+            GamepadButton::Start => "Pad Start",
+            GamepadButton::LeftThumb => "Pad LS",
+            GamepadButton::RightThumb => "Pad RS",
+            GamepadButton::DPadUp => "Pad Up",
+            GamepadButton::DPadDown => "Pad Down",
+            GamepadButton::DPadLeft => "Pad Left",
+            GamepadButton::DPadRight => "Pad Right",
+        }
+    }
+}
+
+/// Modifier keys that must be held for a binding to trigger. Left and right keys are the same.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl Modifiers {
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    pub fn count(&self) -> usize {
+        self.shift as usize + self.ctrl as usize + self.alt as usize
+    }
+
+    /// Returns `true` if every modifier of `other` is also in `self`.
+    pub fn contains(&self, other: Modifiers) -> bool {
+        (self.shift || !other.shift) && (self.ctrl || !other.ctrl) && (self.alt || !other.alt)
+    }
+
+    pub fn union(self, other: Modifiers) -> Self {
+        Self {
+            shift: self.shift || other.shift,
+            ctrl: self.ctrl || other.ctrl,
+            alt: self.alt || other.alt,
+        }
+    }
+
+    pub fn difference(self, other: Modifiers) -> Self {
+        Self {
+            shift: self.shift && !other.shift,
+            ctrl: self.ctrl && !other.ctrl,
+            alt: self.alt && !other.alt,
         }
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
-pub struct ControlButtonDefinition {
-    pub description: String,
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Deserialize, Serialize)]
+pub struct Binding {
     pub button: ControlButton,
+    #[serde(default, skip_serializing_if = "Modifiers::is_empty")]
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    pub fn new(button: ControlButton) -> Self {
+        Self {
+            button,
+            modifiers: Default::default(),
+        }
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn name(&self) -> String {
+        let mut name = String::new();
+        for (enabled, modifier) in [
+            (self.modifiers.ctrl, "Ctrl+"),
+            (self.modifiers.shift, "Shift+"),
+            (self.modifiers.alt, "Alt+"),
+        ] {
+            if enabled {
+                name.push_str(modifier);
+            }
+        }
+        name.push_str(&self.button.name());
+        name
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Deserialize, Serialize)]
+pub enum Action {
+    MoveForward,
+    MoveBackward,
+    MoveLeft,
+    MoveRight,
+    /// Interaction with doors, elevators and items.
+    Interact,
+    DropItem,
+    Jump,
+    Shoot,
+    NextWeapon,
+    PrevWeapon,
+    Run,
+    Aim,
+    Inventory,
+    TossGrenade,
+    Journal,
+    FlashLight,
+    GrabPistol,
+    GrabAk47,
+    GrabM4,
+    GrabPlasmaGun,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    QuickHeal,
+    QuickSave,
+    QuickLoad,
+    PhotoMode,
+}
+
+impl Action {
+    /// Every action in the order they are shown in the options.
+    pub const ALL: [Self; 28] = [
+        Self::MoveForward,
+        Self::MoveBackward,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Interact,
+        Self::DropItem,
+        Self::Jump,
+        Self::Shoot,
+        Self::NextWeapon,
+        Self::PrevWeapon,
+        Self::Run,
+        Self::Aim,
+        Self::Inventory,
+        Self::TossGrenade,
+        Self::Journal,
+        Self::FlashLight,
+        Self::GrabPistol,
+        Self::GrabAk47,
+        Self::GrabM4,
+        Self::GrabPlasmaGun,
+        Self::CursorUp,
+        Self::CursorDown,
+        Self::CursorLeft,
+        Self::CursorRight,
+        Self::QuickHeal,
+        Self::QuickSave,
+        Self::QuickLoad,
+        Self::PhotoMode,
+    ];
+
+    /// Actions that have a state, they could be either held or toggled, see [`ActivationMode`].
+    pub const HAS_ACTIVATION_MODE: [Self; 3] = [Self::Aim, Self::Run, Self::FlashLight];
+
+    fn default_activation_mode(self) -> ActivationMode {
+        match self {
+            // The flashlight has always been switched by presses.
+            Self::FlashLight => ActivationMode::Toggle,
+            _ => ActivationMode::Hold,
+        }
+    }
+
+    /// Localization key of the description, see [`crate::localization`].
+    pub fn description_key(self) -> &'static str {
+        match self {
+            Self::MoveForward => "controls.move_forward",
+            Self::MoveBackward => "controls.move_backward",
+            Self::MoveLeft => "controls.move_left",
+            Self::MoveRight => "controls.move_right",
+            Self::Interact => "controls.action",
+            Self::DropItem => "controls.drop_item",
+            Self::Jump => "controls.jump",
+            Self::Shoot => "controls.shoot",
+            Self::NextWeapon => "controls.next_weapon",
+            Self::PrevWeapon => "controls.prev_weapon",
+            Self::Run => "controls.run",
+            Self::Aim => "controls.aim",
+            Self::Inventory => "controls.inventory",
+            Self::TossGrenade => "controls.toss_grenade",
+            Self::Journal => "controls.journal",
+            Self::FlashLight => "controls.flash_light",
+            Self::GrabPistol => "controls.grab_pistol",
+            Self::GrabAk47 => "controls.grab_ak47",
+            Self::GrabM4 => "controls.grab_m4",
+            Self::GrabPlasmaGun => "controls.grab_plasma_gun",
+            Self::CursorUp => "controls.cursor_up",
+            Self::CursorDown => "controls.cursor_down",
+            Self::CursorLeft => "controls.cursor_left",
+            Self::CursorRight => "controls.cursor_right",
+            Self::QuickHeal => "controls.quick_heal",
+            Self::QuickSave => "controls.quick_save",
+            Self::QuickLoad => "controls.quick_load",
+            Self::PhotoMode => "controls.photo_mode",
+        }
+    }
+
+    fn default_bindings(self) -> Vec<Binding> {
+        let mut bindings = vec![Binding::new(self.default_button())];
+        if let Some(button) = self.default_gamepad_button() {
+            bindings.push(Binding::new(ControlButton::Gamepad(button)));
+        }
+        bindings
+    }
+
+    // Movement has no gamepad buttons, it is bound to the left stick.
+    fn default_gamepad_button(self) -> Option<GamepadButton> {
+        Some(match self {
+            Self::Interact => GamepadButton::West,
+            Self::DropItem => GamepadButton::North,
+            Self::Jump => GamepadButton::South,
+            Self::Shoot => GamepadButton::RightTrigger,
+            Self::NextWeapon => GamepadButton::RightBumper,
+            Self::PrevWeapon => GamepadButton::LeftBumper,
+            Self::Run => GamepadButton::LeftThumb,
+            Self::Aim => GamepadButton::LeftTrigger,
+            Self::Inventory => GamepadButton::Select,
+            Self::TossGrenade => GamepadButton::East,
+            Self::FlashLight => GamepadButton::RightThumb,
+            Self::CursorUp => GamepadButton::DPadUp,
+            Self::CursorDown => GamepadButton::DPadDown,
+            Self::CursorLeft => GamepadButton::DPadLeft,
+            Self::CursorRight => GamepadButton::DPadRight,
+            _ => return None,
+        })
+    }
+
+    fn default_button(self) -> ControlButton {
+        match self {
+            Self::MoveForward => ControlButton::Key(KeyCode::KeyW),
+            Self::MoveBackward => ControlButton::Key(KeyCode::KeyS),
+            Self::MoveLeft => ControlButton::Key(KeyCode::KeyA),
+            Self::MoveRight => ControlButton::Key(KeyCode::KeyD),
+            Self::Interact => ControlButton::Key(KeyCode::KeyE),
+            Self::DropItem => ControlButton::Key(KeyCode::KeyR),
+            Self::Jump => ControlButton::Key(KeyCode::Space),
+            Self::Shoot => ControlButton::Mouse(0),
+            Self::NextWeapon => ControlButton::WheelUp,
+            Self::PrevWeapon => ControlButton::WheelDown,
+            Self::Run => ControlButton::Key(KeyCode::ShiftLeft),
+            Self::Aim => ControlButton::Mouse(1),
+            Self::Inventory => ControlButton::Key(KeyCode::KeyI),
+            Self::TossGrenade => ControlButton::Key(KeyCode::KeyG),
+            Self::Journal => ControlButton::Key(KeyCode::KeyJ),
+            Self::FlashLight => ControlButton::Key(KeyCode::KeyF),
+            Self::GrabPistol => ControlButton::Key(KeyCode::Digit1),
+            Self::GrabAk47 => ControlButton::Key(KeyCode::Digit2),
+            Self::GrabM4 => ControlButton::Key(KeyCode::Digit3),
+            Self::GrabPlasmaGun => ControlButton::Key(KeyCode::Digit4),
+            Self::CursorUp => ControlButton::Key(KeyCode::ArrowUp),
+            Self::CursorDown => ControlButton::Key(KeyCode::ArrowDown),
+            Self::CursorLeft => ControlButton::Key(KeyCode::ArrowLeft),
+            Self::CursorRight => ControlButton::Key(KeyCode::ArrowRight),
+            Self::QuickHeal => ControlButton::Key(KeyCode::KeyQ),
+            Self::QuickSave => ControlButton::Key(KeyCode::F5),
+            Self::QuickLoad => ControlButton::Key(KeyCode::F9),
+            Self::PhotoMode => ControlButton::Key(KeyCode::F8),
+        }
+    }
+}
+
+/// Analog axis of an input device.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Deserialize, Serialize)]
+pub enum ControlAxis {
+    /// Relative motion of the mouse, it has no absolute value.
+    MouseX,
+    MouseY,
+    /// Sticks of a gamepad, positive values are to the right and up.
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    /// Triggers of a gamepad, in `[0; 1]` range.
+    LeftTrigger,
+    RightTrigger,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+impl ControlAxis {
+    /// Relative axes report motion instead of their position.
+    pub fn is_relative(self) -> bool {
+        matches!(self, ControlAxis::MouseX | ControlAxis::MouseY)
+    }
+
+    pub fn is_gamepad(self) -> bool {
+        !self.is_relative()
+    }
+
+    /// Horizontal and vertical axes of the stick the axis belongs to.
+    pub fn stick(self) -> Option<(ControlAxis, ControlAxis)> {
+        match self {
+            ControlAxis::LeftStickX | ControlAxis::LeftStickY => {
+                Some((ControlAxis::LeftStickX, ControlAxis::LeftStickY))
+            }
+            ControlAxis::RightStickX | ControlAxis::RightStickY => {
+                Some((ControlAxis::RightStickX, ControlAxis::RightStickY))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Analog counterparts of the actions.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Deserialize, Serialize)]
+pub enum Axis {
+    /// Strafing, positive values move to the right.
+    MoveX,
+    /// Positive values move forward.
+    MoveY,
+    LookX,
+    LookY,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct AxisBinding {
+    pub axis: ControlAxis,
+    pub target: Axis,
+    /// Multiplier of the value of the axis, negative values invert the axis.
+    pub scale: f32,
+}
+
+/// How a button drives an action that has a state (aiming, running, the flashlight).
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub enum ActivationMode {
+    /// The action is active while the button is held.
+    #[default]
+    Hold,
+    /// Each press of the button switches the action on or off.
+    Toggle,
+}
+
+impl ActivationMode {
+    pub const ALL: [Self; 2] = [Self::Hold, Self::Toggle];
+
+    /// Updates the state of the action by a press or a release of its button.
+    pub fn apply(self, pressed: bool, state: &mut bool) {
+        match self {
+            ActivationMode::Hold => *state = pressed,
+            ActivationMode::Toggle => {
+                if pressed {
+                    *state = !*state;
+                }
+            }
+        }
+    }
+
+    /// Localization key of the name, see [`crate::localization`].
+    pub fn localization_key(self) -> &'static str {
+        match self {
+            ActivationMode::Hold => "options.activation_mode.hold",
+            ActivationMode::Toggle => "options.activation_mode.toggle",
+        }
+    }
+}
+
+/// Maps the deflection of a stick (after the dead zone) to the value of the axis. Steeper curves
+/// give more precision near the center of the stick.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub enum ResponseCurve {
+    Linear,
+    #[default]
+    Quadratic,
+    Cubic,
+}
+
+impl ResponseCurve {
+    pub const ALL: [Self; 3] = [Self::Linear, Self::Quadratic, Self::Cubic];
+
+    pub fn apply(self, value: f32) -> f32 {
+        match self {
+            ResponseCurve::Linear => value,
+            ResponseCurve::Quadratic => value.powi(2),
+            ResponseCurve::Cubic => value.powi(3),
+        }
+    }
+
+    /// Localization key of the name, see [`crate::localization`].
+    pub fn localization_key(self) -> &'static str {
+        match self {
+            ResponseCurve::Linear => "options.response_curve.linear",
+            ResponseCurve::Quadratic => "options.response_curve.quadratic",
+            ResponseCurve::Cubic => "options.response_curve.cubic",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GamepadSettings {
+    /// Deflection of the sticks below the dead zone is ignored, the rest of the range is scaled to
+    /// `[0; 1]`.
+    pub dead_zone: f32,
+    pub response_curve: ResponseCurve,
+    /// Multiplier of the turning speed of the look stick, it is separate from the mouse one.
+    pub look_sensitivity: f32,
+    /// Same as `look_sensitivity`, but used while aiming.
+    pub aim_look_sensitivity: f32,
+    /// Slows the aim down near visible enemies and pulls it towards them, see
+    /// [`crate::aim_assist`].
+    pub aim_assist: bool,
+    /// Strength of the aim assist in `[0; 1]` range.
+    pub aim_assist_strength: f32,
+}
+
+impl Default for GamepadSettings {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.15,
+            response_curve: Default::default(),
+            look_sensitivity: 1.0,
+            aim_look_sensitivity: 0.6,
+            aim_assist: true,
+            aim_assist_strength: 0.5,
+        }
+    }
+}
+
+impl GamepadSettings {
+    /// Applies the radial dead zone and the response curve to the position of a stick.
+    pub fn shape_stick(&self, stick: Vector2<f32>) -> Vector2<f32> {
+        let magnitude = stick.norm();
+        if magnitude <= self.dead_zone {
+            return Vector2::default();
+        }
+        let deflection =
+            ((magnitude - self.dead_zone) / (1.0 - self.dead_zone).max(f32::EPSILON)).min(1.0);
+        stick.scale(self.response_curve.apply(deflection) / magnitude)
+    }
+
+    /// Applies the dead zone to the value of a trigger.
+    pub fn shape_trigger(&self, value: f32) -> f32 {
+        if value.abs() <= self.dead_zone {
+            0.0
+        } else {
+            value
+        }
+    }
+}
+
+/// What happens to the other actions that have the binding given to an action.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ConflictResolution {
+    /// The other actions get the previous binding of the action.
+    Swap,
+    /// The binding is removed from the other actions.
+    Clear,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(default)]
 pub struct ControlScheme {
-    pub move_forward: ControlButtonDefinition,
-    pub move_backward: ControlButtonDefinition,
-    pub move_left: ControlButtonDefinition,
-    pub move_right: ControlButtonDefinition,
-    pub jump: ControlButtonDefinition,
-    pub shoot: ControlButtonDefinition,
-    pub next_weapon: ControlButtonDefinition,
-    pub prev_weapon: ControlButtonDefinition,
-    pub run: ControlButtonDefinition,
-    pub aim: ControlButtonDefinition,
-    pub toss_grenade: ControlButtonDefinition,
-    pub journal: ControlButtonDefinition,
-    pub flash_light: ControlButtonDefinition,
-    pub grab_ak47: ControlButtonDefinition,
-    pub grab_m4: ControlButtonDefinition,
-    pub grab_pistol: ControlButtonDefinition,
-    pub grab_plasma_gun: ControlButtonDefinition,
-    pub inventory: ControlButtonDefinition,
-    pub action: ControlButtonDefinition,
-    pub drop_item: ControlButtonDefinition,
-    pub cursor_up: ControlButtonDefinition,
-    pub cursor_down: ControlButtonDefinition,
-    pub cursor_left: ControlButtonDefinition,
-    pub cursor_right: ControlButtonDefinition,
-    pub quick_heal: ControlButtonDefinition,
+    /// Version of the format. Settings without it were written before the action map was
+    /// introduced and must be migrated.
+    #[serde(default)]
+    pub version: u32,
+    pub bindings: BTreeMap<Action, Vec<Binding>>,
+    pub axes: Vec<AxisBinding>,
+    /// Activation modes of the actions that have a state, see [`Action::HAS_ACTIVATION_MODE`].
+    pub activation_modes: BTreeMap<Action, ActivationMode>,
+    /// Sensitivity of the mouse when not aiming, see [`Self::MOUSE_SCALE`].
     pub mouse_sens: f32,
+    /// Sensitivity of the mouse while aiming.
+    pub mouse_aim_sens: f32,
+    /// Both inversions are applied to every look axis, including the sticks.
+    pub mouse_x_inverse: bool,
     pub mouse_y_inverse: bool,
+    /// Time (in seconds) the motion of the mouse is spread over, zero disables the smoothing.
+    pub mouse_smoothing: f32,
+    /// Extra sensitivity per thousand counts per second of the mouse speed, zero disables the
+    /// acceleration.
+    pub mouse_acceleration: f32,
+    pub gamepad: GamepadSettings,
 }
 
 impl Default for ControlScheme {
     fn default() -> Self {
         Self {
-            move_forward: ControlButtonDefinition {
-                description: "Move Forward".to_string(),
-                button: ControlButton::Key(KeyCode::KeyW),
-            },
-            move_backward: ControlButtonDefinition {
-                description: "Move Backward".to_string(),
-                button: ControlButton::Key(KeyCode::KeyS),
-            },
-            move_left: ControlButtonDefinition {
-                description: "Move Left".to_string(),
-                button: ControlButton::Key(KeyCode::KeyA),
-            },
-            move_right: ControlButtonDefinition {
-                description: "Move Right".to_string(),
-                button: ControlButton::Key(KeyCode::KeyD),
-            },
-            jump: ControlButtonDefinition {
-                description: "Jump".to_string(),
-                button: ControlButton::Key(KeyCode::Space),
-            },
-            shoot: ControlButtonDefinition {
-                description: "Shoot".to_string(),
-                button: ControlButton::Mouse(0),
-            },
-            next_weapon: ControlButtonDefinition {
-                description: "Next Weapon".to_string(),
-                button: ControlButton::WheelUp,
-            },
-            prev_weapon: ControlButtonDefinition {
-                description: "Previous Weapon".to_string(),
-                button: ControlButton::WheelDown,
-            },
-            run: ControlButtonDefinition {
-                description: "Run".to_string(),
-                button: ControlButton::Key(KeyCode::ShiftLeft),
-            },
-            aim: ControlButtonDefinition {
-                description: "Aim".to_string(),
-                button: ControlButton::Mouse(1),
-            },
-            toss_grenade: ControlButtonDefinition {
-                description: "Toss Grenade".to_string(),
-                button: ControlButton::Key(KeyCode::KeyG),
-            },
-            journal: ControlButtonDefinition {
-                description: "Journal".to_string(),
-                button: ControlButton::Key(KeyCode::KeyJ),
-            },
-            flash_light: ControlButtonDefinition {
-                description: "Flash Light".to_string(),
-                button: ControlButton::Key(KeyCode::KeyF),
-            },
-            grab_pistol: ControlButtonDefinition {
-                description: "Grab Pistol".to_string(),
-                button: ControlButton::Key(KeyCode::Digit1),
-            },
-            grab_ak47: ControlButtonDefinition {
-                description: "Grab AK47".to_string(),
-                button: ControlButton::Key(KeyCode::Digit2),
-            },
-            grab_m4: ControlButtonDefinition {
-                description: "Grab M4".to_string(),
-                button: ControlButton::Key(KeyCode::Digit3),
-            },
-            grab_plasma_gun: ControlButtonDefinition {
-                description: "Grab Plasma Gun".to_string(),
-                button: ControlButton::Key(KeyCode::Digit4),
-            },
-            inventory: ControlButtonDefinition {
-                description: "Inventory".to_string(),
-                button: ControlButton::Key(KeyCode::KeyI),
-            },
-            action: ControlButtonDefinition {
-                description: "Action".to_string(),
-                button: ControlButton::Key(KeyCode::KeyE),
-            },
-            drop_item: ControlButtonDefinition {
-                description: "Drop Item".to_string(),
-                button: ControlButton::Key(KeyCode::KeyR),
-            },
-            cursor_up: ControlButtonDefinition {
-                description: "Cursor Up".to_string(),
-                button: ControlButton::Key(KeyCode::ArrowUp),
-            },
-            cursor_down: ControlButtonDefinition {
-                description: "Cursor Down".to_string(),
-                button: ControlButton::Key(KeyCode::ArrowDown),
-            },
-            cursor_left: ControlButtonDefinition {
-                description: "Cursor Left".to_string(),
-                button: ControlButton::Key(KeyCode::ArrowLeft),
-            },
-            cursor_right: ControlButtonDefinition {
-                description: "Cursor Right".to_string(),
-                button: ControlButton::Key(KeyCode::ArrowRight),
-            },
-            quick_heal: ControlButtonDefinition {
-                description: "Quick Heal".to_string(),
-                button: ControlButton::Key(KeyCode::KeyQ),
-            },
+            version: Self::VERSION,
+            bindings: Action::ALL
+                .into_iter()
+                .map(|action| (action, action.default_bindings()))
+                .collect(),
+            axes: vec![
+                AxisBinding {
+                    axis: ControlAxis::MouseX,
+                    target: Axis::LookX,
+                    scale: 1.0,
+                },
+                AxisBinding {
+                    axis: ControlAxis::MouseY,
+                    target: Axis::LookY,
+                    scale: 1.0,
+                },
+                AxisBinding {
+                    axis: ControlAxis::LeftStickX,
+                    target: Axis::MoveX,
+                    scale: 1.0,
+                },
+                AxisBinding {
+                    axis: ControlAxis::LeftStickY,
+                    target: Axis::MoveY,
+                    scale: 1.0,
+                },
+                AxisBinding {
+                    axis: ControlAxis::RightStickX,
+                    target: Axis::LookX,
+                    scale: 1.0,
+                },
+                // Pushing the stick up looks up, the pitch grows downwards.
+                AxisBinding {
+                    axis: ControlAxis::RightStickY,
+                    target: Axis::LookY,
+                    scale: -1.0,
+                },
+            ],
+            activation_modes: Action::HAS_ACTIVATION_MODE
+                .into_iter()
+                .map(|action| (action, action.default_activation_mode()))
+                .collect(),
             mouse_sens: 0.3,
+            mouse_aim_sens: 0.2,
+            mouse_x_inverse: false,
             mouse_y_inverse: false,
+            mouse_smoothing: 0.0,
+            mouse_acceleration: 0.0,
+            gamepad: Default::default(),
         }
     }
 }
 
 impl ControlScheme {
-    pub fn buttons_mut(&mut self) -> [&mut ControlButtonDefinition; 24] {
-        [
-            &mut self.move_forward,
-            &mut self.move_backward,
-            &mut self.move_left,
-            &mut self.move_right,
-            &mut self.action,
-            &mut self.drop_item,
-            &mut self.jump,
-            &mut self.shoot,
-            &mut self.next_weapon,
-            &mut self.prev_weapon,
-            &mut self.run,
-            &mut self.aim,
-            &mut self.inventory,
-            &mut self.toss_grenade,
-            &mut self.journal,
-            &mut self.flash_light,
-            &mut self.grab_pistol,
-            &mut self.grab_ak47,
-            &mut self.grab_m4,
-            &mut self.grab_plasma_gun,
-            &mut self.cursor_up,
-            &mut self.cursor_down,
-            &mut self.cursor_left,
-            &mut self.cursor_right,
-        ]
-    }
-
-    pub fn buttons(&self) -> [&ControlButtonDefinition; 24] {
-        [
-            &self.move_forward,
-            &self.move_backward,
-            &self.move_left,
-            &self.move_right,
-            &self.action,
-            &self.drop_item,
-            &self.jump,
-            &self.shoot,
-            &self.next_weapon,
-            &self.prev_weapon,
-            &self.run,
-            &self.aim,
-            &self.inventory,
-            &self.toss_grenade,
-            &self.journal,
-            &self.flash_light,
-            &self.grab_pistol,
-            &self.grab_ak47,
-            &self.grab_m4,
-            &self.grab_plasma_gun,
-            &self.cursor_up,
-            &self.cursor_down,
-            &self.cursor_left,
-            &self.cursor_right,
-        ]
+    pub const VERSION: u32 = 3;
+
+    /// Radians per count of the mouse at sensitivity 1. The look used to be scaled by the frame
+    /// time, the scale matches the old look at 60 FPS, so the old sensitivity feels the same.
+    pub const MOUSE_SCALE: f32 = 1.0 / 60.0;
+
+    /// Amount of bindings per action that could be changed in the options.
+    pub const EDITABLE_BINDINGS: usize = 2;
+
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], |bindings| bindings)
+    }
+
+    /// Sets or removes (if `binding` is `None`) a binding of the action at the given index.
+    pub fn set_binding(&mut self, action: Action, index: usize, binding: Option<Binding>) {
+        let bindings = self.bindings.entry(action).or_default();
+        match binding {
+            Some(binding) if index < bindings.len() => bindings[index] = binding,
+            Some(binding) => bindings.push(binding),
+            None if index < bindings.len() => {
+                bindings.remove(index);
+            }
+            None => (),
+        }
+    }
+
+    /// Bindings of the other actions that are the same as the given one, as pairs of an action and
+    /// an index of its binding.
+    pub fn conflicts(&self, action: Action, binding: Binding) -> Vec<(Action, usize)> {
+        self.bindings
+            .iter()
+            .filter(|(other, _)| **other != action)
+            .flat_map(|(other, bindings)| {
+                bindings
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, other_binding)| **other_binding == binding)
+                    .map(|(index, _)| (*other, index))
+            })
+            .collect()
+    }
+
+    /// Sets a binding of the action at the given index and resolves the conflicts with the other
+    /// actions, see [`Self::conflicts`].
+    pub fn rebind(
+        &mut self,
+        action: Action,
+        index: usize,
+        binding: Binding,
+        resolution: ConflictResolution,
+    ) {
+        let previous = self.bindings(action).get(index).copied();
+        // Removal shifts the next bindings, so the conflicts are resolved from the last one.
+        for (other, other_index) in self.conflicts(action, binding).into_iter().rev() {
+            let replacement = match resolution {
+                ConflictResolution::Swap => previous,
+                ConflictResolution::Clear => None,
+            };
+            self.set_binding(other, other_index, replacement);
+        }
+        self.set_binding(action, index, Some(binding));
+    }
+
+    /// Restores the default bindings and the activation mode of the action.
+    pub fn reset_action(&mut self, action: Action) {
+        self.bindings.insert(action, action.default_bindings());
+        if Action::HAS_ACTIVATION_MODE.contains(&action) {
+            self.activation_modes
+                .insert(action, action.default_activation_mode());
+        }
+    }
+
+    /// Name of the first binding of the action, it is used in hints like "Press E to open".
+    pub fn binding_name(&self, action: Action) -> String {
+        self.bindings(action)
+            .first()
+            .map(|binding| binding.name())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn activation_mode(&self, action: Action) -> ActivationMode {
+        self.activation_modes
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_activation_mode())
+    }
+
+    pub fn set_activation_mode(&mut self, action: Action, mode: ActivationMode) {
+        self.activation_modes.insert(action, mode);
+    }
+
+    /// Sensitivity of the mouse for the current state of the aim.
+    pub fn mouse_sensitivity(&self, aiming: bool) -> f32 {
+        if aiming {
+            self.mouse_aim_sens
+        } else {
+            self.mouse_sens
+        }
     }
 
     pub fn reset(&mut self) {
         *self = Default::default();
     }
+
+    /// Brings the scheme of an older version up to date, new default bindings are added to the
+    /// ones configured by the player.
+    pub fn upgrade(&mut self) {
+        if self.version < 2 {
+            // Gamepad bindings were added in the second version.
+            let defaults = Self::default();
+            for (action, default_bindings) in defaults.bindings {
+                let bindings = self.bindings.entry(action).or_default();
+                for binding in default_bindings {
+                    if binding.button.is_gamepad() && !bindings.contains(&binding) {
+                        bindings.push(binding);
+                    }
+                }
+            }
+            for axis in defaults.axes {
+                if axis.axis.is_gamepad() && !self.axes.contains(&axis) {
+                    self.axes.push(axis);
+                }
+            }
+        }
+        if self.version < 3 {
+            // The sensitivity while aiming was added in the third version, it used to be the
+            // same as the regular one.
+            self.mouse_aim_sens = self.mouse_sens;
+        }
+        self.version = Self::VERSION;
+    }
+
+    /// Adds default bindings of the actions that are missing in the scheme, for example the ones
+    /// that were added after the settings were saved.
+    pub fn add_missing_actions(&mut self) {
+        for action in Action::ALL {
+            self.bindings
+                .entry(action)
+                .or_insert_with(|| action.default_bindings());
+        }
+    }
+}
+
+/// Button of an action in the legacy scheme, it is empty if the action is missing.
+#[derive(Default)]
+pub struct LegacyButtonDefinition(Option<ControlButton>);
+
+impl<'de> Deserialize<'de> for LegacyButtonDefinition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Descriptions were stored along with the buttons, they are not needed anymore.
+        #[derive(Deserialize)]
+        struct Definition {
+            button: ControlButton,
+        }
+
+        Definition::deserialize(deserializer).map(|definition| Self(Some(definition.button)))
+    }
+}
+
+/// Control scheme of the settings written before the action map was introduced. Every action had
+/// exactly one button, the buttons become the only bindings of the actions.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct LegacyControlScheme {
+    move_forward: LegacyButtonDefinition,
+    move_backward: LegacyButtonDefinition,
+    move_left: LegacyButtonDefinition,
+    move_right: LegacyButtonDefinition,
+    jump: LegacyButtonDefinition,
+    shoot: LegacyButtonDefinition,
+    next_weapon: LegacyButtonDefinition,
+    prev_weapon: LegacyButtonDefinition,
+    run: LegacyButtonDefinition,
+    aim: LegacyButtonDefinition,
+    toss_grenade: LegacyButtonDefinition,
+    journal: LegacyButtonDefinition,
+    flash_light: LegacyButtonDefinition,
+    grab_ak47: LegacyButtonDefinition,
+    grab_m4: LegacyButtonDefinition,
+    grab_pistol: LegacyButtonDefinition,
+    grab_plasma_gun: LegacyButtonDefinition,
+    inventory: LegacyButtonDefinition,
+    action: LegacyButtonDefinition,
+    drop_item: LegacyButtonDefinition,
+    cursor_up: LegacyButtonDefinition,
+    cursor_down: LegacyButtonDefinition,
+    cursor_left: LegacyButtonDefinition,
+    cursor_right: LegacyButtonDefinition,
+    quick_heal: LegacyButtonDefinition,
+    quick_save: LegacyButtonDefinition,
+    quick_load: LegacyButtonDefinition,
+    photo_mode: LegacyButtonDefinition,
+}
+
+impl LegacyControlScheme {
+    /// Replaces the bindings of the scheme with the buttons of the legacy scheme. Actions that
+    /// are missing in the legacy scheme keep their bindings. The scheme becomes the first version
+    /// of the action map, see [`ControlScheme::upgrade`].
+    pub fn migrate(self, scheme: &mut ControlScheme) {
+        for (action, definition) in [
+            (Action::MoveForward, self.move_forward),
+            (Action::MoveBackward, self.move_backward),
+            (Action::MoveLeft, self.move_left),
+            (Action::MoveRight, self.move_right),
+            (Action::Jump, self.jump),
+            (Action::Shoot, self.shoot),
+            (Action::NextWeapon, self.next_weapon),
+            (Action::PrevWeapon, self.prev_weapon),
+            (Action::Run, self.run),
+            (Action::Aim, self.aim),
+            (Action::TossGrenade, self.toss_grenade),
+            (Action::Journal, self.journal),
+            (Action::FlashLight, self.flash_light),
+            (Action::GrabAk47, self.grab_ak47),
+            (Action::GrabM4, self.grab_m4),
+            (Action::GrabPistol, self.grab_pistol),
+            (Action::GrabPlasmaGun, self.grab_plasma_gun),
+            (Action::Inventory, self.inventory),
+            (Action::Interact, self.action),
+            (Action::DropItem, self.drop_item),
+            (Action::CursorUp, self.cursor_up),
+            (Action::CursorDown, self.cursor_down),
+            (Action::CursorLeft, self.cursor_left),
+            (Action::CursorRight, self.cursor_right),
+            (Action::QuickHeal, self.quick_heal),
+            (Action::QuickSave, self.quick_save),
+            (Action::QuickLoad, self.quick_load),
+            (Action::PhotoMode, self.photo_mode),
+        ] {
+            if let LegacyButtonDefinition(Some(button)) = definition {
+                scheme.bindings.insert(action, vec![Binding::new(button)]);
+            }
+        }
+        scheme.version = 1;
+    }
 }
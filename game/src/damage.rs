@@ -0,0 +1,120 @@
+//! Damage types and resistances to them. Resistances of a character apply to every hit box of it,
+//! resistances of a hit box apply on top of them, so a single prefab could have armored limbs.
+
+use fyrox::core::{
+    reflect::prelude::*, type_traits::prelude::*, variable::InheritableVariable,
+    visitor::prelude::*,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "5c1f7a3e-2b0d-4e8a-9f61-0d7e3b4a8c25")]
+pub enum DamageType {
+    /// Bullets, slugs and shrapnel.
+    #[default]
+    Kinetic,
+    Plasma,
+    Explosive,
+    Fire,
+    /// Melee attacks.
+    Blunt,
+    /// Collisions with the environment, falls and death zones.
+    Environmental,
+    Bleeding,
+    Poison,
+}
+
+impl DamageType {
+    /// Armor protects only from the damage that comes from the outside.
+    pub fn ignores_armor(self) -> bool {
+        matches!(self, Self::Bleeding | Self::Poison)
+    }
+}
+
+#[derive(Visit, Reflect, Debug, Clone)]
+#[visit(optional)]
+pub struct Resistances {
+    /// Amount subtracted from every hit after the resistance is applied, so armor protects well
+    /// from many weak hits and badly from a few strong ones.
+    #[reflect(min_value = 0.0, max_value = 1000.0)]
+    pub armor: InheritableVariable<f32>,
+    /// Fraction of the damage that is absorbed, negative values make the damage stronger.
+    #[reflect(min_value = -1.0, max_value = 1.0)]
+    pub kinetic: InheritableVariable<f32>,
+    #[reflect(min_value = -1.0, max_value = 1.0)]
+    pub plasma: InheritableVariable<f32>,
+    #[reflect(min_value = -1.0, max_value = 1.0)]
+    pub explosive: InheritableVariable<f32>,
+    #[reflect(min_value = -1.0, max_value = 1.0)]
+    pub fire: InheritableVariable<f32>,
+    #[reflect(min_value = -1.0, max_value = 1.0)]
+    pub blunt: InheritableVariable<f32>,
+    #[reflect(min_value = -1.0, max_value = 1.0)]
+    pub environmental: InheritableVariable<f32>,
+    #[reflect(min_value = -1.0, max_value = 1.0)]
+    pub bleeding: InheritableVariable<f32>,
+    #[reflect(min_value = -1.0, max_value = 1.0)]
+    pub poison: InheritableVariable<f32>,
+}
+
+impl Default for Resistances {
+    fn default() -> Self {
+        Self {
+            armor: 0.0.into(),
+            kinetic: 0.0.into(),
+            plasma: 0.0.into(),
+            explosive: 0.0.into(),
+            fire: 0.0.into(),
+            blunt: 0.0.into(),
+            environmental: 0.0.into(),
+            bleeding: 0.0.into(),
+            poison: 0.0.into(),
+        }
+    }
+}
+
+impl Resistances {
+    /// Armor never absorbs more than this fraction of a hit.
+    pub const MAX_ARMOR_ABSORPTION: f32 = 0.8;
+
+    pub fn resistance(&self, damage_type: DamageType) -> f32 {
+        let resistance = match damage_type {
+            DamageType::Kinetic => *self.kinetic,
+            DamageType::Plasma => *self.plasma,
+            DamageType::Explosive => *self.explosive,
+            DamageType::Fire => *self.fire,
+            DamageType::Blunt => *self.blunt,
+            DamageType::Environmental => *self.environmental,
+            DamageType::Bleeding => *self.bleeding,
+            DamageType::Poison => *self.poison,
+        };
+        resistance.clamp(-1.0, 1.0)
+    }
+
+    /// Returns the amount of damage that gets through the resistance and the armor.
+    pub fn apply(&self, amount: f32, damage_type: DamageType) -> f32 {
+        if amount <= 0.0 {
+            return 0.0;
+        }
+        let resisted = amount * (1.0 - self.resistance(damage_type));
+        if damage_type.ignores_armor() {
+            return resisted;
+        }
+        let armored = resisted - self.armor.max(0.0);
+        armored.max(resisted * (1.0 - Self::MAX_ARMOR_ABSORPTION))
+    }
+}
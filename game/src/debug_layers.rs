@@ -0,0 +1,95 @@
+//! Debug visualization layers. Every layer could be toggled at runtime from the console
+//! (`debug_layer`) or from the options menu, enabled layers are drawn into the drawing context of
+//! the level scene by [`crate::level::Level::debug_draw`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[derive(Deserialize, Serialize, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum DebugLayer {
+    /// Every collider and joint of the physics world.
+    Physics,
+    Navmesh,
+    /// Paths that bots are following.
+    BotPaths,
+    /// Frustums that bots use to find their targets.
+    BotFrustums,
+    /// Frustums that turrets use to find their targets.
+    TurretFrustums,
+    /// Hit box colliders, the color goes from green to red as the hit box loses its health.
+    HitBoxes,
+    /// Volumes of triggers and death zones.
+    Volumes,
+    /// Radius in which the doors could be opened.
+    Doors,
+    /// Paths of elevators through their floors.
+    Elevators,
+    PointsOfInterest,
+}
+
+impl DebugLayer {
+    pub const ALL: [Self; 10] = [
+        Self::Physics,
+        Self::Navmesh,
+        Self::BotPaths,
+        Self::BotFrustums,
+        Self::TurretFrustums,
+        Self::HitBoxes,
+        Self::Volumes,
+        Self::Doors,
+        Self::Elevators,
+        Self::PointsOfInterest,
+    ];
+
+    /// Name of the layer that is used in the console.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Physics => "physics",
+            Self::Navmesh => "navmesh",
+            Self::BotPaths => "bot_paths",
+            Self::BotFrustums => "bot_frustums",
+            Self::TurretFrustums => "turret_frustums",
+            Self::HitBoxes => "hit_boxes",
+            Self::Volumes => "volumes",
+            Self::Doors => "doors",
+            Self::Elevators => "elevators",
+            Self::PointsOfInterest => "pois",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|layer| layer.name() == name)
+    }
+
+    /// Localization key of the name of the layer, see [`crate::localization`].
+    pub fn localization_key(self) -> String {
+        format!("debug_layer.{}", self.name())
+    }
+}
+
+/// A set of enabled debug layers, it is stored in the settings.
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+#[serde(transparent)]
+pub struct DebugLayers(BTreeSet<DebugLayer>);
+
+impl DebugLayers {
+    pub fn is_enabled(&self, layer: DebugLayer) -> bool {
+        self.0.contains(&layer)
+    }
+
+    pub fn set_enabled(&mut self, layer: DebugLayer, enabled: bool) {
+        if enabled {
+            self.0.insert(layer);
+        } else {
+            self.0.remove(&layer);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = DebugLayer> + '_ {
+        self.0.iter().copied()
+    }
+}
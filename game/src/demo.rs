@@ -0,0 +1,247 @@
+//! Demo files are recordings of the input stream the player receives each frame. A demo
+//! remembers the level or the saved game it was started from, the random seed and the difficulty,
+//! so replaying it on the same data reproduces the run. Demos are stored as RON files.
+
+use crate::{
+    control_scheme::{ControlAxis, ControlButton},
+    difficulty::Difficulty,
+};
+use fyrox::core::log::Log;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+/// Input event in a form that does not depend on the OS or the window.
+#[derive(Deserialize, Serialize, Copy, Clone, Debug, PartialEq)]
+pub enum InputEvent {
+    Button {
+        button: ControlButton,
+        pressed: bool,
+    },
+    MouseMotion {
+        dx: f32,
+        dy: f32,
+    },
+    /// New position of an absolute analog axis (gamepad sticks and triggers).
+    Axis {
+        axis: ControlAxis,
+        value: f32,
+    },
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum DemoSource {
+    Level(PathBuf),
+    SavedGame {
+        path: PathBuf,
+        /// Hash of the contents of the saved game, used to detect that the save was overwritten.
+        hash: u64,
+    },
+}
+
+impl DemoSource {
+    pub fn level(path: &Path) -> Self {
+        Self::Level(path.to_path_buf())
+    }
+
+    pub fn saved_game(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read(path)
+            .map_err(|e| format!("Unable to read saved game {}. Reason: {e}", path.display()))?;
+        Ok(Self::SavedGame {
+            path: path.to_path_buf(),
+            hash: content_hash(&data),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            DemoSource::Level(path) | DemoSource::SavedGame { path, .. } => path,
+        }
+    }
+}
+
+// FNV-1a, the hash must be stable between builds.
+fn content_hash(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DemoHeader {
+    pub version: u32,
+    pub source: DemoSource,
+    pub seed: u64,
+    /// Older demos were recorded on the default difficulty.
+    #[serde(default)]
+    pub difficulty: Difficulty,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct DemoFrame {
+    pub dt: f32,
+    pub events: Vec<InputEvent>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Demo {
+    pub header: DemoHeader,
+    pub frames: Vec<DemoFrame>,
+}
+
+impl Demo {
+    /// Must be incremented every time the format of demos (or the input events) changes.
+    ///
+    /// - 2: analog axes of gamepads were added to the input events.
+    pub const VERSION: u32 = 2;
+
+    pub fn new(source: DemoSource, seed: u64, difficulty: Difficulty) -> Self {
+        Self {
+            header: DemoHeader {
+                version: Self::VERSION,
+                source,
+                seed,
+                difficulty,
+            },
+            frames: Default::default(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = File::open(path)
+            .map_err(|e| format!("Unable to open demo {}. Reason: {e}", path.display()))?;
+        let demo: Self = ron::de::from_reader(file)
+            .map_err(|e| format!("Unable to read demo {}. Reason: {e}", path.display()))?;
+
+        if demo.header.version != Self::VERSION {
+            return Err(format!(
+                "Demo {} has unsupported version {} (expected {})!",
+                path.display(),
+                demo.header.version,
+                Self::VERSION
+            ));
+        }
+
+        Ok(demo)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Unable to create demo {}. Reason: {e}", path.display()))?;
+        ron::ser::to_writer_pretty(file, self, PrettyConfig::default())
+            .map_err(|e| format!("Unable to write demo {}. Reason: {e}", path.display()))
+    }
+
+    /// Checks whether the demo could be played from the current state of the source it was
+    /// recorded from.
+    pub fn validate_source(&self) -> Result<(), String> {
+        match &self.header.source {
+            DemoSource::Level(path) => {
+                if path.exists() {
+                    Ok(())
+                } else {
+                    Err(format!("Level {} does not exist!", path.display()))
+                }
+            }
+            DemoSource::SavedGame { path, .. } => {
+                if DemoSource::saved_game(path)? == self.header.source {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Saved game {} differs from the one the demo was recorded from!",
+                        path.display()
+                    ))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DemoRecorder {
+    path: PathBuf,
+    demo: Demo,
+    pending: Vec<InputEvent>,
+}
+
+impl DemoRecorder {
+    pub fn new(path: PathBuf, demo: Demo) -> Self {
+        Self {
+            path,
+            demo,
+            pending: Default::default(),
+        }
+    }
+
+    pub fn record(&mut self, event: InputEvent) {
+        self.pending.push(event);
+    }
+
+    /// Closes current frame, every event recorded after this call goes to the next frame.
+    pub fn end_frame(&mut self, dt: f32) {
+        self.demo.frames.push(DemoFrame {
+            dt,
+            events: std::mem::take(&mut self.pending),
+        });
+    }
+
+    pub fn finish(self) {
+        match self.demo.save(&self.path) {
+            Ok(_) => Log::info(format!(
+                "Demo {} was saved, {} frames recorded.",
+                self.path.display(),
+                self.demo.frames.len()
+            )),
+            Err(err) => Log::err(err),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DemoPlayer {
+    demo: Demo,
+    frame: usize,
+    dt_mismatch_reported: bool,
+}
+
+impl DemoPlayer {
+    pub fn new(demo: Demo) -> Self {
+        Self {
+            demo,
+            frame: 0,
+            dt_mismatch_reported: false,
+        }
+    }
+
+    /// Returns the events of the next frame, or `None` if the demo has ended.
+    pub fn next_frame(&mut self, dt: f32) -> Option<Vec<InputEvent>> {
+        let frame = self.demo.frames.get(self.frame)?;
+        if !self.dt_mismatch_reported && (frame.dt - dt).abs() > f32::EPSILON {
+            self.dt_mismatch_reported = true;
+            Log::warn(format!(
+                "Demo frame {} was recorded with dt {}, but played with {dt}. \
+                The playback could diverge!",
+                self.frame, frame.dt
+            ));
+        }
+        self.frame += 1;
+        Some(frame.events.clone())
+    }
+
+    pub fn source(&self) -> &DemoSource {
+        &self.demo.header.source
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.demo.frames.len()
+    }
+}
+
+#[derive(Debug)]
+pub enum DemoState {
+    Recording(DemoRecorder),
+    Playback(DemoPlayer),
+}
@@ -0,0 +1,130 @@
+//! Difficulty presets. A difficulty is chosen when a new game is started and it is stored in saved
+//! games. Every preset scales combat parameters of the game, the scale factors are defined in
+//! `data/configs/difficulty.ron`.
+
+use fyrox::core::{log::Log, visitor::prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs::File, sync::LazyLock};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Visit,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+#[strum(ascii_case_insensitive)]
+#[repr(u32)]
+pub enum Difficulty {
+    Story,
+    #[default]
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Story,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Nightmare,
+    ];
+
+    /// Localization key of the name of the difficulty.
+    pub fn name_key(self) -> &'static str {
+        match self {
+            Difficulty::Story => "difficulty.story",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+            Difficulty::Nightmare => "difficulty.nightmare",
+        }
+    }
+
+    /// Localization key of the description of the difficulty.
+    pub fn description_key(self) -> &'static str {
+        match self {
+            Difficulty::Story => "difficulty.story.description",
+            Difficulty::Normal => "difficulty.normal.description",
+            Difficulty::Hard => "difficulty.hard.description",
+            Difficulty::Nightmare => "difficulty.nightmare.description",
+        }
+    }
+
+    pub fn tuning(self) -> &'static DifficultyTuning {
+        TUNING.map.get(&self).unwrap_or(&DifficultyTuning::NEUTRAL)
+    }
+}
+
+/// Scale factors of combat parameters for a difficulty. Factors of `1.0` keep the values that are
+/// set in the prefabs.
+#[derive(Deserialize, Clone, Debug)]
+pub struct DifficultyTuning {
+    /// Scales [`crate::level::hit_box::HitBox::damage_factor`] of the hit boxes of the player.
+    pub player_damage_factor: f32,
+    /// Scales [`crate::level::hit_box::HitBox::damage_factor`] of the hit boxes of everyone but the
+    /// player.
+    pub enemy_damage_factor: f32,
+    /// Scales [`crate::bot::Bot::walk_speed`].
+    pub bot_walk_speed_factor: f32,
+    /// Scales the time between two threats of a bot.
+    pub bot_threaten_timeout_factor: f32,
+    /// Scales [`crate::bot::Bot::v_aim_angle_hack`] and [`crate::bot::Bot::h_aim_angle_hack`].
+    pub bot_aim_angle_hack_factor: f32,
+    /// Scales [`crate::weapon::Weapon::ammo_consumption_per_shot`] of the weapons of the player.
+    pub ammo_consumption_factor: f32,
+    /// Scales the amount of characters produced by character spawn points.
+    pub spawn_amount_factor: f32,
+}
+
+impl DifficultyTuning {
+    pub const NEUTRAL: Self = Self {
+        player_damage_factor: 1.0,
+        enemy_damage_factor: 1.0,
+        bot_walk_speed_factor: 1.0,
+        bot_threaten_timeout_factor: 1.0,
+        bot_aim_angle_hack_factor: 1.0,
+        ammo_consumption_factor: 1.0,
+        spawn_amount_factor: 1.0,
+    };
+}
+
+impl Default for DifficultyTuning {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct DifficultyTuningContainer {
+    map: HashMap<Difficulty, DifficultyTuning>,
+}
+
+impl DifficultyTuningContainer {
+    const PATH: &'static str = "data/configs/difficulty.ron";
+
+    pub fn new() -> Self {
+        File::open(Self::PATH)
+            .map_err(|e| e.to_string())
+            .and_then(|file| ron::de::from_reader(file).map_err(|e| e.to_string()))
+            .unwrap_or_else(|err| {
+                Log::err(format!(
+                    "Unable to load difficulty presets from {}, all difficulties are the same. \
+                    Reason: {err}",
+                    Self::PATH
+                ));
+                Default::default()
+            })
+    }
+}
+
+static TUNING: LazyLock<DifficultyTuningContainer> = LazyLock::new(DifficultyTuningContainer::new);
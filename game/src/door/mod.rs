@@ -1,5 +1,8 @@
 use crate::character::Character;
-use crate::{door::ui::DoorUi, inventory::Inventory, utils, Game};
+use crate::{
+    door::ui::DoorUi, inventory::Inventory, localization::tr, profiler, telemetry,
+    telemetry::TelemetryEvent, utils, Game,
+};
 use fyrox::plugin::error::GameResult;
 use fyrox::{
     asset::{manager::ResourceManager, Resource},
@@ -141,14 +144,19 @@ impl ScriptTrait for Door {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Door");
         let game = ctx.plugins.get_mut::<Game>();
+        if game.is_paused() {
+            return Ok(());
+        }
         let level = game.level.as_ref().unwrap();
 
         let mut closest_actor = None;
         let someone_nearby = level.actors.iter().any(|a| {
             if let Ok(actor) = ctx.scene.graph.try_get_script_component_of::<Character>(*a) {
                 let actor_position = actor.position(&ctx.scene.graph);
-                let close_enough = actor_position.metric_distance(&self.initial_position) < 1.25;
+                let close_enough = actor_position.metric_distance(&self.initial_position)
+                    < Self::INTERACTION_RADIUS;
                 if close_enough {
                     closest_actor = Some(a);
                 }
@@ -169,6 +177,8 @@ impl ScriptTrait for Door {
             .set_rule("Open", open_request.as_ref().is_some_and(|r| r.open));
 
         let mut sound = Handle::NONE;
+        let mut opened = false;
+        let mut access_denied = false;
 
         if let Some(layer) = machine.layers_mut().first_mut() {
             while let Some(event) = layer.pop_event() {
@@ -177,6 +187,7 @@ impl ScriptTrait for Door {
 
                     if new_state_name == self.opening_state.as_str() {
                         sound = *self.open_sound;
+                        opened = true;
                     } else if new_state_name == self.closing_state.as_str() {
                         sound = *self.close_sound;
                     }
@@ -188,38 +199,54 @@ impl ScriptTrait for Door {
             let mut locked = false;
             let text;
             if current_state.name == self.opening_state.as_str() {
-                text = "Opening...";
+                text = "door.opening";
             } else if current_state.name == self.opened_state.as_str() {
-                text = "Opened";
+                text = "door.opened";
             } else if current_state.name == self.closing_state.as_str() {
-                text = "Closing..";
+                text = "door.closing";
             } else if current_state.name == self.closed_state.as_str() {
                 if someone_nearby {
                     can_interact = true;
-                    text = "Open?";
+                    text = "door.open";
                 } else {
-                    text = "Closed";
+                    text = "door.closed";
                 }
             } else if current_state.name == self.locked_state.as_str() {
-                text = "Locked";
+                text = "door.locked";
                 locked = true;
 
                 if let Some(open_request) = open_request.as_ref() {
                     sound = if open_request.open {
                         *self.access_granted_sound
                     } else {
+                        access_denied = true;
                         *self.access_denied_sound
                     };
                 }
             } else {
-                text = "Unknown";
+                text = "door.unknown";
             };
 
             if let Some(ui) = self.ui.as_mut() {
-                ui.update_text(text.to_owned(), &game.config.controls, can_interact, locked);
+                ui.update_text(tr(text), &game.config.controls, can_interact, locked);
             }
         }
 
+        if opened || access_denied {
+            game.record_telemetry(|| {
+                let graph = &ctx.scene.graph;
+                let door = telemetry::entity_name(graph, ctx.handle);
+                let actor = closest_actor
+                    .map(|actor| telemetry::entity_name(graph, *actor))
+                    .unwrap_or_default();
+                if opened {
+                    TelemetryEvent::DoorOpened { door, actor }
+                } else {
+                    TelemetryEvent::DoorAccessDenied { door, actor }
+                }
+            });
+        }
+
         let _ = utils::try_play_sound(sound, &mut ctx.scene.graph);
 
         if let Some(ui) = self.ui.as_mut() {
@@ -234,6 +261,9 @@ impl ScriptTrait for Door {
 }
 
 impl Door {
+    /// Actors closer than this distance to the door could open it.
+    pub const INTERACTION_RADIUS: f32 = 1.25;
+
     pub fn initial_position(&self) -> Vector3<f32> {
         self.initial_position
     }
@@ -1,4 +1,8 @@
-use crate::{control_scheme::ControlScheme, UiNode};
+use crate::{
+    control_scheme::{Action, ControlScheme},
+    localization::tr_args,
+    UiNode,
+};
 use fyrox::asset::manager::ResourceManager;
 use fyrox::gui::texture::TexturePixelKind;
 use fyrox::renderer::ui_renderer::UiRenderInfo;
@@ -55,7 +59,13 @@ impl DoorUi {
         if can_interact {
             self.try_update_text(
                 self.action_text,
-                format!("[{}] - Interact", control_scheme.action.button.name()),
+                tr_args(
+                    "door.interact",
+                    &[(
+                        "button",
+                        control_scheme.binding_name(Action::Interact).as_str(),
+                    )],
+                ),
             );
         }
 
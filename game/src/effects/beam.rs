@@ -1,6 +1,7 @@
 //! Small helper script that does a ray cast and scales the parent node with the distance
 //! from the position of the node to the intersection point.
 
+use crate::profiler;
 use fyrox::plugin::error::GameResult;
 use fyrox::{
     core::{
@@ -39,6 +40,7 @@ impl ScriptTrait for Beam {
 
         let mut query_buffer = Vec::default();
 
+        profiler::count("Raycasts");
         physics.cast_ray(
             RayCastOptions {
                 ray_origin: Point3::from(ray.origin),
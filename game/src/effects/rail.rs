@@ -1,5 +1,6 @@
 //! Particles generator for rail gun's rail effect.
 
+use crate::profiler;
 use fyrox::graph::SceneGraph;
 use fyrox::plugin::error::GameResult;
 use fyrox::{
@@ -50,6 +51,7 @@ impl ScriptTrait for Rail {
 
         let mut query_buffer = Vec::default();
 
+        profiler::count("Raycasts");
         physics.cast_ray(
             RayCastOptions {
                 ray_origin: Point3::from(ray.origin),
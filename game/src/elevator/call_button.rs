@@ -1,4 +1,8 @@
-use crate::elevator::{ui::CallButtonUi, Elevator};
+use crate::{
+    elevator::{ui::CallButtonUi, Elevator},
+    localization::{tr, tr_args},
+    profiler,
+};
 use fyrox::{
     asset::{manager::ResourceManager, Resource},
     core::{
@@ -93,6 +97,7 @@ impl ScriptTrait for CallButton {
     }
 
     fn on_update(&mut self, context: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("CallButton");
         if let Some(ui) = self.ui.as_mut() {
             ui.update(context.dt);
 
@@ -107,18 +112,18 @@ impl ScriptTrait for CallButton {
             .try_get_script_of::<Elevator>(self.elevator)?;
 
         if let Some(ui) = self.ui.as_mut() {
-            ui.set_text(
-                if self.floor == elevator.current_floor {
-                    "Ready"
-                } else if elevator.k.abs() > f32::EPSILON {
-                    "Called"
-                } else {
-                    "Call?"
-                }
-                .to_string(),
-            );
-
-            ui.set_floor_text(format!("Floor {}", self.floor));
+            ui.set_text(tr(if self.floor == elevator.current_floor {
+                "elevator.ready"
+            } else if elevator.k.abs() > f32::EPSILON {
+                "elevator.called"
+            } else {
+                "elevator.call"
+            }));
+
+            ui.set_floor_text(tr_args(
+                "elevator.floor",
+                &[("floor", self.floor.to_string().as_str())],
+            ));
         }
 
         Ok(())
@@ -1,4 +1,4 @@
-use crate::Game;
+use crate::{profiler, Game};
 use fyrox::plugin::error::GameResult;
 use fyrox::{
     core::{pool::Handle, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
@@ -50,6 +50,11 @@ impl ScriptTrait for Elevator {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Elevator");
+        if ctx.plugins.get::<Game>().is_paused() {
+            return Ok(());
+        }
+
         if self.current_floor != self.dest_floor {
             self.k += 0.5 * ctx.dt;
 
@@ -1,4 +1,4 @@
-use crate::{gui, UiNode};
+use crate::{gui, localization::tr_args, UiNode};
 use fyrox::asset::manager::ResourceManager;
 use fyrox::graph::SceneGraph;
 use fyrox::renderer::ui_renderer::UiRenderInfo;
@@ -23,7 +23,13 @@ impl CallButtonUi {
 
     pub fn new(ui: UserInterface, floor: u32) -> Self {
         let floor_text = ui.find_handle_by_name_from_root("FloorText");
-        ui.send(floor_text, TextMessage::Text(format!("Floor {floor}")));
+        ui.send(
+            floor_text,
+            TextMessage::Text(tr_args(
+                "elevator.floor",
+                &[("floor", floor.to_string().as_str())],
+            )),
+        );
         Self {
             text: ui.find_handle_by_name_from_root("Text"),
             ui,
@@ -0,0 +1,485 @@
+//! Free-fly camera, it is also used as a photo mode. The camera takes over the view from the
+//! player's camera, so the level could be inspected from anywhere. The player does not receive any
+//! input while the free camera is active, the world keeps simulating unless it is frozen.
+
+use crate::{
+    control_scheme::{Action, ControlButton, ControlScheme},
+    demo::InputEvent,
+    input::{ActionEvent, MouseFilter},
+    localization::{tr, tr_args},
+};
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        pool::Handle,
+    },
+    graph::SceneGraph,
+    gui::{
+        font::FontResource,
+        grid::{Column, Grid, GridBuilder, Row},
+        text::{Text, TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, HorizontalAlignment, Thickness, UserInterface, VerticalAlignment,
+    },
+    keyboard::KeyCode,
+    plugin::error::GameResult,
+    scene::{
+        animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+        camera::{Camera, Projection},
+        graph::Graph,
+        node::Node,
+        particle_system::ParticleSystem,
+    },
+};
+
+const UP: KeyCode = KeyCode::Space;
+const DOWN: KeyCode = KeyCode::KeyC;
+const ROLL_LEFT: KeyCode = KeyCode::KeyQ;
+const ROLL_RIGHT: KeyCode = KeyCode::KeyE;
+const ZOOM_IN: KeyCode = KeyCode::KeyZ;
+const ZOOM_OUT: KeyCode = KeyCode::KeyX;
+const RESET_VIEW: KeyCode = KeyCode::KeyR;
+const FREEZE_WORLD: KeyCode = KeyCode::KeyP;
+const HIDE_HUD: KeyCode = KeyCode::KeyH;
+const HIDE_DISPLAYS: KeyCode = KeyCode::KeyG;
+
+#[derive(Default, Debug)]
+struct FreeCameraController {
+    move_forward: bool,
+    move_backward: bool,
+    move_left: bool,
+    move_right: bool,
+    move_up: bool,
+    move_down: bool,
+    fast: bool,
+    roll_left: bool,
+    roll_right: bool,
+    zoom_in: bool,
+    zoom_out: bool,
+}
+
+/// Stops the time of the scene while the world is frozen. The scene must stay enabled to be
+/// rendered, so the nodes that are advanced by the graph itself are stopped here: animations are
+/// disabled, particle systems are paused and lifetimes of the nodes are held.
+#[derive(Default, Debug)]
+struct SceneFreeze {
+    frozen: bool,
+    animations: Vec<Handle<Node>>,
+    particle_systems: Vec<Handle<Node>>,
+}
+
+impl SceneFreeze {
+    fn set_frozen(&mut self, graph: &mut Graph, frozen: bool) {
+        if self.frozen == frozen {
+            return;
+        }
+        self.frozen = frozen;
+
+        if frozen {
+            for (handle, node) in graph.pair_iter_mut() {
+                let is_animation = node.cast::<AnimationPlayer>().is_some()
+                    || node.cast::<AnimationBlendingStateMachine>().is_some();
+                if is_animation && node.is_enabled() {
+                    node.set_enabled(false);
+                    self.animations.push(handle);
+                } else if let Some(particle_system) = node.cast_mut::<ParticleSystem>() {
+                    if particle_system.is_playing() {
+                        particle_system.play(false);
+                        self.particle_systems.push(handle);
+                    }
+                }
+            }
+        } else {
+            // Some of the nodes could be removed while the world was frozen.
+            for handle in self.animations.drain(..) {
+                if let Ok(node) = graph.try_get_mut(handle) {
+                    node.set_enabled(true);
+                }
+            }
+            for handle in self.particle_systems.drain(..) {
+                if let Ok(particle_system) = graph.try_get_mut_of_type::<ParticleSystem>(handle) {
+                    particle_system.play(true);
+                }
+            }
+        }
+    }
+
+    /// The graph counts down the lifetimes of the nodes by itself, so the time is given back.
+    fn hold_lifetimes(&self, graph: &mut Graph, dt: f32) {
+        if !self.frozen {
+            return;
+        }
+
+        for node in graph.linear_iter_mut() {
+            if let Some(lifetime) = node.lifetime() {
+                node.set_lifetime(Some(lifetime + dt));
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FreeCamera {
+    root: Handle<Grid>,
+    hint: Handle<Text>,
+    hint_text: String,
+    camera: Handle<Node>,
+    // Camera of the player, it is disabled while the free camera is active.
+    player_camera: Handle<Node>,
+    position: Vector3<f32>,
+    yaw: f32,
+    pitch: f32,
+    roll: f32,
+    fov: f32,
+    // Field of view of the player's camera, the view is reset to it.
+    default_fov: f32,
+    speed: f32,
+    freeze_world: bool,
+    hide_hud: bool,
+    hide_displays: bool,
+    controller: FreeCameraController,
+    mouse_filter: MouseFilter,
+    scene_freeze: SceneFreeze,
+}
+
+impl Default for FreeCamera {
+    fn default() -> Self {
+        Self {
+            root: Default::default(),
+            hint: Default::default(),
+            hint_text: Default::default(),
+            camera: Default::default(),
+            player_camera: Default::default(),
+            position: Default::default(),
+            yaw: 0.0,
+            pitch: 0.0,
+            roll: 0.0,
+            fov: Self::DEFAULT_FOV,
+            default_fov: Self::DEFAULT_FOV,
+            speed: Self::DEFAULT_SPEED,
+            freeze_world: false,
+            hide_hud: false,
+            hide_displays: false,
+            controller: Default::default(),
+            mouse_filter: Default::default(),
+            scene_freeze: Default::default(),
+        }
+    }
+}
+
+impl FreeCamera {
+    const DEFAULT_FOV: f32 = 75.0f32.to_radians();
+    const DEFAULT_SPEED: f32 = 4.0;
+    const MIN_SPEED: f32 = 0.25;
+    const MAX_SPEED: f32 = 64.0;
+    const FAST_FACTOR: f32 = 4.0;
+    const MIN_FOV: f32 = 10.0f32.to_radians();
+    const MAX_FOV: f32 = 120.0f32.to_radians();
+    const ZOOM_SPEED: f32 = 30.0f32.to_radians();
+    const ROLL_SPEED: f32 = 45.0f32.to_radians();
+
+    pub fn new(font: FontResource, ctx: &mut BuildContext) -> Self {
+        let hint = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(10.0))
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .with_vertical_alignment(VerticalAlignment::Bottom),
+        )
+        .with_font(font)
+        .with_font_size(16.0.into())
+        .build(ctx);
+
+        // The root fills the screen, so the hint could be aligned to the corner.
+        let root = GridBuilder::new(WidgetBuilder::new().with_visibility(false).with_child(hint))
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .build(ctx);
+
+        Self {
+            root,
+            hint,
+            ..Default::default()
+        }
+    }
+
+    pub fn resize(&self, ui: &UserInterface, width: f32, height: f32) {
+        ui.send(self.root, WidgetMessage::Width(width));
+        ui.send(self.root, WidgetMessage::Height(height));
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.camera.is_some()
+    }
+
+    pub fn is_world_frozen(&self) -> bool {
+        self.is_active() && self.freeze_world
+    }
+
+    pub fn hides_hud(&self) -> bool {
+        self.is_active() && self.hide_hud
+    }
+
+    pub fn hides_displays(&self) -> bool {
+        self.is_active() && self.hide_displays
+    }
+
+    /// Takes over the view from the given camera. The free camera is a copy of it, so it has the
+    /// same rendering settings (skybox, exposure, etc.) and starts exactly where the view was.
+    pub fn activate(&mut self, graph: &mut Graph, player_camera: Handle<Node>) -> GameResult {
+        let player_camera_ref = graph.try_get(player_camera)?;
+        let look = player_camera_ref.look_vector();
+        self.position = player_camera_ref.global_position();
+        self.yaw = look.x.atan2(look.z);
+        self.pitch = (-look.y).clamp(-1.0, 1.0).asin();
+        self.roll = 0.0;
+        if let Some(Projection::Perspective(perspective)) = player_camera_ref
+            .cast::<Camera>()
+            .map(|camera| camera.projection())
+        {
+            self.default_fov = perspective.fov;
+        }
+        self.fov = self.default_fov;
+
+        let mut camera = graph.copy_single_node(player_camera);
+        camera.set_enabled(true);
+        self.camera = graph.add_node(camera);
+        graph.try_get_mut(player_camera)?.set_enabled(false);
+        self.player_camera = player_camera;
+        self.controller = Default::default();
+        self.mouse_filter.reset();
+        self.scene_freeze = Default::default();
+
+        self.sync_camera(graph)
+    }
+
+    /// Gives the view back to the player's camera.
+    pub fn deactivate(&mut self, graph: &mut Graph, ui: &UserInterface) {
+        if !self.is_active() {
+            return;
+        }
+
+        self.scene_freeze.set_frozen(graph, false);
+        if graph.try_get(self.camera).is_ok() {
+            graph.remove_node(self.camera);
+        }
+        if let Ok(player_camera) = graph.try_get_mut(self.player_camera) {
+            player_camera.set_enabled(true);
+        }
+        self.camera = Handle::NONE;
+        self.player_camera = Handle::NONE;
+        self.freeze_world = false;
+        self.controller = Default::default();
+        self.mouse_filter.reset();
+        ui.send(self.root, WidgetMessage::Visibility(false));
+    }
+
+    /// Releases every button, it is used when something else takes the input (menus, console).
+    pub fn release_input(&mut self) {
+        self.controller = Default::default();
+        self.mouse_filter.reset();
+    }
+
+    fn reset_view(&mut self) {
+        self.roll = 0.0;
+        self.fov = self.default_fov;
+        self.speed = Self::DEFAULT_SPEED;
+    }
+
+    fn process_action(&mut self, action: Action, pressed: bool) {
+        let controller = &mut self.controller;
+        match action {
+            Action::MoveForward => controller.move_forward = pressed,
+            Action::MoveBackward => controller.move_backward = pressed,
+            Action::MoveLeft => controller.move_left = pressed,
+            Action::MoveRight => controller.move_right = pressed,
+            Action::Run => controller.fast = pressed,
+            _ => (),
+        }
+    }
+
+    fn process_button(&mut self, button: ControlButton, pressed: bool) {
+        let controller = &mut self.controller;
+        if button == ControlButton::Key(UP) {
+            controller.move_up = pressed;
+        } else if button == ControlButton::Key(DOWN) {
+            controller.move_down = pressed;
+        } else if button == ControlButton::Key(ROLL_LEFT) {
+            controller.roll_left = pressed;
+        } else if button == ControlButton::Key(ROLL_RIGHT) {
+            controller.roll_right = pressed;
+        } else if button == ControlButton::Key(ZOOM_IN) {
+            controller.zoom_in = pressed;
+        } else if button == ControlButton::Key(ZOOM_OUT) {
+            controller.zoom_out = pressed;
+        } else if button == ControlButton::WheelUp {
+            self.speed = (self.speed * 1.25).clamp(Self::MIN_SPEED, Self::MAX_SPEED);
+        } else if button == ControlButton::WheelDown {
+            self.speed = (self.speed * 0.8).clamp(Self::MIN_SPEED, Self::MAX_SPEED);
+        } else if pressed {
+            if button == ControlButton::Key(RESET_VIEW) {
+                self.reset_view();
+            } else if button == ControlButton::Key(FREEZE_WORLD) {
+                self.freeze_world = !self.freeze_world;
+            } else if button == ControlButton::Key(HIDE_HUD) {
+                self.hide_hud = !self.hide_hud;
+            } else if button == ControlButton::Key(HIDE_DISPLAYS) {
+                self.hide_displays = !self.hide_displays;
+            }
+        }
+    }
+
+    /// Movement and look come from the actions produced by the input event, the keys of the photo
+    /// mode are fixed.
+    pub fn process_input(&mut self, event: InputEvent, actions: &[ActionEvent]) {
+        if !self.is_active() {
+            return;
+        }
+
+        for action in actions {
+            match *action {
+                ActionEvent::Pressed(action) => self.process_action(action, true),
+                ActionEvent::Released(action) => self.process_action(action, false),
+                // The motion is applied once per frame, the same way as for the player.
+                ActionEvent::Axis { axis, value } => self.mouse_filter.add_motion(axis, value),
+            }
+        }
+
+        if let InputEvent::Button { button, pressed } = event {
+            self.process_button(button, pressed);
+        }
+    }
+
+    fn rotation(&self) -> UnitQuaternion<f32> {
+        UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.yaw)
+            * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), self.pitch)
+            * UnitQuaternion::from_axis_angle(&Vector3::z_axis(), self.roll)
+    }
+
+    fn sync_camera(&self, graph: &mut Graph) -> GameResult {
+        let camera = graph.try_get_mut(self.camera)?;
+        camera
+            .local_transform_mut()
+            .set_position(self.position)
+            .set_rotation(self.rotation());
+        if let Some(camera) = camera.cast_mut::<Camera>() {
+            let mut projection = camera.projection().clone();
+            if let Projection::Perspective(ref mut perspective) = projection {
+                perspective.fov = self.fov;
+            }
+            camera.set_projection(projection);
+        }
+        Ok(())
+    }
+
+    fn sync_hint(&mut self, ui: &UserInterface) {
+        ui.send(
+            self.root,
+            WidgetMessage::Visibility(self.is_active() && !self.hide_hud),
+        );
+
+        let mut text = format!(
+            "{}\n{}\n{}",
+            tr("photo_mode.title"),
+            tr("photo_mode.controls"),
+            tr_args(
+                "photo_mode.state",
+                &[
+                    ("speed", format!("{:.1}", self.speed).as_str()),
+                    ("fov", format!("{:.0}", self.fov.to_degrees()).as_str()),
+                    ("roll", format!("{:.0}", self.roll.to_degrees()).as_str()),
+                ],
+            )
+        );
+        if self.freeze_world {
+            text.push('\n');
+            text.push_str(&tr("photo_mode.frozen"));
+        }
+
+        if text != self.hint_text {
+            ui.send(self.hint, TextMessage::Text(text.clone()));
+            self.hint_text = text;
+        }
+    }
+
+    /// Moves the camera according to the input. The camera is moved by the real frame time, so it
+    /// keeps moving when the world is frozen.
+    pub fn update(
+        &mut self,
+        graph: &mut Graph,
+        dt: f32,
+        controls: &ControlScheme,
+        ui: &UserInterface,
+    ) -> GameResult {
+        if !self.is_active() {
+            return Ok(());
+        }
+
+        let yaw_direction = if controls.mouse_x_inverse { -1.0 } else { 1.0 };
+        let pitch_direction = if controls.mouse_y_inverse { -1.0 } else { 1.0 };
+        let mouse =
+            self.mouse_filter
+                .update(controls.mouse_smoothing, controls.mouse_acceleration, dt);
+        let mouse_sens = controls.mouse_sens * ControlScheme::MOUSE_SCALE;
+        self.yaw -= yaw_direction * mouse.x * mouse_sens;
+        self.pitch = (self.pitch + pitch_direction * mouse.y * mouse_sens)
+            .clamp(-89.0f32.to_radians(), 89.0f32.to_radians());
+
+        let controller = &self.controller;
+
+        if controller.roll_left {
+            self.roll -= Self::ROLL_SPEED * dt;
+        }
+        if controller.roll_right {
+            self.roll += Self::ROLL_SPEED * dt;
+        }
+        if controller.zoom_in {
+            self.fov -= Self::ZOOM_SPEED * dt;
+        }
+        if controller.zoom_out {
+            self.fov += Self::ZOOM_SPEED * dt;
+        }
+        self.fov = self.fov.clamp(Self::MIN_FOV, Self::MAX_FOV);
+
+        let rotation = self.rotation();
+        let forward = rotation * Vector3::z();
+        let left = rotation * Vector3::x();
+        let up = rotation * Vector3::y();
+
+        let mut direction = Vector3::default();
+        if controller.move_forward {
+            direction += forward;
+        }
+        if controller.move_backward {
+            direction -= forward;
+        }
+        if controller.move_left {
+            direction += left;
+        }
+        if controller.move_right {
+            direction -= left;
+        }
+        if controller.move_up {
+            direction += up;
+        }
+        if controller.move_down {
+            direction -= up;
+        }
+
+        if let Some(direction) = direction.try_normalize(f32::EPSILON) {
+            let speed = if controller.fast {
+                self.speed * Self::FAST_FACTOR
+            } else {
+                self.speed
+            };
+            self.position += direction.scale(speed * dt);
+        }
+
+        self.scene_freeze.set_frozen(graph, self.freeze_world);
+        self.scene_freeze.hold_lifetimes(graph, dt);
+
+        self.sync_camera(graph)?;
+        self.sync_hint(ui);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+//! Gamepad input source. Events of the gamepads are read with `gilrs` when the `gamepad` feature is
+//! enabled, synthetic events could be pushed to the source directly (tests use them to drive the
+//! game without a device).
+//!
+//! Every connected gamepad controls the game, the game has only one player.
+
+use crate::{
+    control_scheme::{ControlAxis, ControlButton, GamepadButton},
+    demo::InputEvent,
+    input::InputSource,
+};
+use fyrox::fxhash::{FxHashMap, FxHashSet};
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Formatter},
+};
+
+/// Device-level event of a gamepad.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GamepadEvent {
+    Button {
+        button: GamepadButton,
+        pressed: bool,
+    },
+    /// Position of a stick (`[-1; 1]` range, positive values are to the right and up) or a trigger
+    /// (`[0; 1]` range).
+    Axis {
+        axis: ControlAxis,
+        value: f32,
+    },
+    Disconnected,
+}
+
+#[derive(Default)]
+pub struct Gamepad {
+    #[cfg(feature = "gamepad")]
+    backend: Option<gilrs::Gilrs>,
+    queue: VecDeque<GamepadEvent>,
+    held_buttons: FxHashSet<GamepadButton>,
+    axes: FxHashMap<ControlAxis, f32>,
+}
+
+impl Debug for Gamepad {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Gamepad")
+            .field("queue", &self.queue)
+            .field("held_buttons", &self.held_buttons)
+            .field("axes", &self.axes)
+            .finish()
+    }
+}
+
+impl Gamepad {
+    /// Triggers pressed deeper than this act as buttons, so they could be bound to actions.
+    pub const TRIGGER_THRESHOLD: f32 = 0.5;
+
+    /// Creates the source that reads the events of the connected gamepads.
+    #[cfg(feature = "gamepad")]
+    pub fn with_backend() -> Result<Self, String> {
+        let backend = gilrs::Gilrs::new().map_err(|err| err.to_string())?;
+        Ok(Self {
+            backend: Some(backend),
+            ..Default::default()
+        })
+    }
+
+    /// Queues an event, it is processed on the next poll.
+    pub fn push_event(&mut self, event: GamepadEvent) {
+        self.queue.push_back(event);
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn read_backend(&mut self) {
+        use gilrs::{Axis, Button, EventType};
+
+        let Some(backend) = self.backend.as_mut() else {
+            return;
+        };
+
+        fn button(button: Button) -> Option<GamepadButton> {
+            Some(match button {
+                Button::South => GamepadButton::South,
+                Button::East => GamepadButton::East,
+                Button::North => GamepadButton::North,
+                Button::West => GamepadButton::West,
+                Button::LeftTrigger => GamepadButton::LeftBumper,
+                Button::RightTrigger => GamepadButton::RightBumper,
+                Button::Select => GamepadButton::Select,
+                Button::Start => GamepadButton::Start,
+                Button::LeftThumb => GamepadButton::LeftThumb,
+                Button::RightThumb => GamepadButton::RightThumb,
+                Button::DPadUp => GamepadButton::DPadUp,
+                Button::DPadDown => GamepadButton::DPadDown,
+                Button::DPadLeft => GamepadButton::DPadLeft,
+                Button::DPadRight => GamepadButton::DPadRight,
+                _ => return None,
+            })
+        }
+
+        while let Some(event) = backend.next_event() {
+            let event = match event.event {
+                EventType::ButtonPressed(b, _) => button(b).map(|button| GamepadEvent::Button {
+                    button,
+                    pressed: true,
+                }),
+                EventType::ButtonReleased(b, _) => button(b).map(|button| GamepadEvent::Button {
+                    button,
+                    pressed: false,
+                }),
+                // Analog triggers, they are turned into buttons by the source itself.
+                EventType::ButtonChanged(Button::LeftTrigger2, value, _) => {
+                    Some(GamepadEvent::Axis {
+                        axis: ControlAxis::LeftTrigger,
+                        value,
+                    })
+                }
+                EventType::ButtonChanged(Button::RightTrigger2, value, _) => {
+                    Some(GamepadEvent::Axis {
+                        axis: ControlAxis::RightTrigger,
+                        value,
+                    })
+                }
+                EventType::AxisChanged(axis, value, _) => match axis {
+                    Axis::LeftStickX => Some(ControlAxis::LeftStickX),
+                    Axis::LeftStickY => Some(ControlAxis::LeftStickY),
+                    Axis::RightStickX => Some(ControlAxis::RightStickX),
+                    Axis::RightStickY => Some(ControlAxis::RightStickY),
+                    _ => None,
+                }
+                .map(|axis| GamepadEvent::Axis { axis, value }),
+                EventType::Disconnected => Some(GamepadEvent::Disconnected),
+                _ => None,
+            };
+            self.queue.extend(event);
+        }
+    }
+
+    fn set_button(&mut self, button: GamepadButton, pressed: bool, output: &mut Vec<InputEvent>) {
+        let changed = if pressed {
+            self.held_buttons.insert(button)
+        } else {
+            self.held_buttons.remove(&button)
+        };
+        if changed {
+            output.push(InputEvent::Button {
+                button: ControlButton::Gamepad(button),
+                pressed,
+            });
+        }
+    }
+
+    fn process_event(&mut self, event: GamepadEvent, output: &mut Vec<InputEvent>) {
+        match event {
+            GamepadEvent::Button { button, pressed } => self.set_button(button, pressed, output),
+            GamepadEvent::Axis { axis, value } => {
+                let value = value.clamp(-1.0, 1.0);
+                if self.axes.insert(axis, value) != Some(value) {
+                    output.push(InputEvent::Axis { axis, value });
+                }
+                let trigger_button = match axis {
+                    ControlAxis::LeftTrigger => Some(GamepadButton::LeftTrigger),
+                    ControlAxis::RightTrigger => Some(GamepadButton::RightTrigger),
+                    _ => None,
+                };
+                if let Some(button) = trigger_button {
+                    self.set_button(button, value > Self::TRIGGER_THRESHOLD, output);
+                }
+            }
+            GamepadEvent::Disconnected => {
+                // Nothing must stay pressed or deflected when the gamepad is gone.
+                for button in self.held_buttons.drain() {
+                    output.push(InputEvent::Button {
+                        button: ControlButton::Gamepad(button),
+                        pressed: false,
+                    });
+                }
+                for (axis, value) in self.axes.drain() {
+                    if value != 0.0 {
+                        output.push(InputEvent::Axis { axis, value: 0.0 });
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl InputSource for Gamepad {
+    fn poll(&mut self, output: &mut Vec<InputEvent>) {
+        #[cfg(feature = "gamepad")]
+        self.read_backend();
+
+        while let Some(event) = self.queue.pop_front() {
+            self.process_event(event, output);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! A notification that is shown at the top right corner of the screen when an achievement is
+//! unlocked. Achievements unlocked at the same time are shown one after another.
+
+use crate::{achievements::AchievementDefinition, localization::tr};
+use fyrox::{
+    core::{color::Color, pool::Handle},
+    gui::{
+        border::{Border, BorderBuilder},
+        brush::Brush,
+        font::FontResource,
+        grid::{Column, Grid, GridBuilder, Row},
+        text::{Text, TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, HorizontalAlignment, Thickness, UserInterface, VerticalAlignment,
+    },
+};
+use std::collections::VecDeque;
+
+#[derive(Default, Debug)]
+pub struct AchievementToast {
+    root: Handle<Grid>,
+    panel: Handle<Border>,
+    text: Handle<Text>,
+    queue: VecDeque<&'static AchievementDefinition>,
+    timer: f32,
+}
+
+impl AchievementToast {
+    const DURATION: f32 = 4.0;
+
+    pub fn new(font: FontResource, ctx: &mut BuildContext) -> Self {
+        let text;
+        let panel = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_width(320.0)
+                .with_margin(Thickness::uniform(10.0))
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_vertical_alignment(VerticalAlignment::Top)
+                .with_background(Brush::Solid(Color::from_rgba(0, 0, 0, 200)).into())
+                .with_child({
+                    text =
+                        TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(8.0)))
+                            .with_font(font)
+                            .with_font_size(18.0.into())
+                            .build(ctx);
+                    text
+                }),
+        )
+        .build(ctx);
+
+        // The root fills the screen, so the panel could be aligned to the corner.
+        let root = GridBuilder::new(WidgetBuilder::new().with_child(panel))
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .build(ctx);
+
+        Self {
+            root,
+            panel,
+            text,
+            queue: Default::default(),
+            timer: 0.0,
+        }
+    }
+
+    pub fn resize(&self, ui: &UserInterface, width: f32, height: f32) {
+        ui.send(self.root, WidgetMessage::Width(width));
+        ui.send(self.root, WidgetMessage::Height(height));
+    }
+
+    /// Hides the toast without losing the queue, used to hide the interface in photo mode.
+    pub fn set_visible(&self, visible: bool, ui: &UserInterface) {
+        ui.send(self.root, WidgetMessage::Visibility(visible));
+    }
+
+    pub fn push(&mut self, definition: &'static AchievementDefinition) {
+        self.queue.push_back(definition);
+    }
+
+    pub fn update(&mut self, dt: f32, ui: &UserInterface) {
+        if self.timer > 0.0 {
+            self.timer -= dt;
+            if self.timer > 0.0 {
+                return;
+            }
+            ui.send(self.panel, WidgetMessage::Visibility(false));
+        }
+
+        if let Some(definition) = self.queue.pop_front() {
+            ui.send(
+                self.text,
+                TextMessage::Text(format!(
+                    "{}\n{}",
+                    tr("achievements.unlocked"),
+                    tr(&definition.name_key())
+                )),
+            );
+            ui.send(self.panel, WidgetMessage::Visibility(true));
+            self.timer = Self::DURATION;
+        }
+    }
+}
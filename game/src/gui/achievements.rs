@@ -0,0 +1,139 @@
+use crate::{
+    achievements::{AchievementDefinition, Achievements, DEFINITIONS},
+    gui::navigation::NavigationCommand,
+    localization::{tr, tr_args},
+};
+use fyrox::{
+    core::{pool::Handle, pool::HandlesVecExtension, visitor::prelude::*},
+    gui::{
+        border::BorderBuilder,
+        button::{Button, ButtonBuilder, ButtonMessage},
+        decorator::{Decorator, DecoratorBuilder},
+        font::FontResource,
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::ListViewBuilder,
+        message::UiMessage,
+        text::TextBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{Window, WindowAlignment, WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Thickness, UserInterface, VerticalAlignment,
+    },
+};
+
+/// A page of the main menu that lists every achievement with its state.
+#[derive(Default, Debug, Visit, Clone)]
+pub struct AchievementsPage {
+    pub window: Handle<Window>,
+    close: Handle<Button>,
+}
+
+fn create_achievement_entry(
+    definition: &AchievementDefinition,
+    achievements: &Achievements,
+    font: FontResource,
+    ctx: &mut BuildContext,
+) -> Handle<Decorator> {
+    let state = if achievements.is_unlocked(definition) {
+        tr("achievements.state.unlocked")
+    } else if let Some((current, target)) = achievements.progress(definition) {
+        tr_args(
+            "achievements.state.progress",
+            &[
+                ("current", current.to_string().as_str()),
+                ("target", target.to_string().as_str()),
+            ],
+        )
+    } else {
+        tr("achievements.state.locked")
+    };
+
+    let text = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(4.0)))
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .with_horizontal_text_alignment(HorizontalAlignment::Left)
+        .with_wrap(WrapMode::Word)
+        .with_font(font)
+        .with_font_size(16.0.into())
+        .with_text(format!(
+            "{} - {}\n{}",
+            tr(&definition.name_key()),
+            state,
+            tr(&definition.description_key())
+        ))
+        .build(ctx);
+
+    DecoratorBuilder::new(BorderBuilder::new(WidgetBuilder::new().with_child(text))).build(ctx)
+}
+
+impl AchievementsPage {
+    pub fn new(achievements: &Achievements, font: FontResource, ctx: &mut BuildContext) -> Self {
+        let items = DEFINITIONS
+            .achievements
+            .iter()
+            .map(|definition| create_achievement_entry(definition, achievements, font.clone(), ctx))
+            .collect::<Vec<_>>();
+
+        let list = ListViewBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(0),
+        )
+        .with_items(items.to_base())
+        .build(ctx);
+
+        let close = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_width(120.0)
+                .with_margin(Thickness::uniform(2.0))
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .on_row(1),
+        )
+        .with_text_and_font_size(&tr("achievements.close"), font, 24.0.into())
+        .build(ctx);
+
+        let content = GridBuilder::new(WidgetBuilder::new().with_child(list).with_child(close))
+            .add_row(Row::stretch())
+            .add_row(Row::strict(35.0))
+            .add_column(Column::stretch())
+            .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(500.0))
+            .can_minimize(false)
+            .can_maximize(false)
+            .open(false)
+            .with_title(WindowTitle::text(tr("achievements.title")))
+            .with_content(content)
+            .build(ctx);
+
+        ctx.inner().send(
+            window,
+            WindowMessage::Open {
+                alignment: WindowAlignment::Center,
+                focus_content: true,
+                modal: true,
+            },
+        );
+
+        Self { window, close }
+    }
+
+    /// The page has only the close button, so both accepting and going back close it.
+    pub fn process_navigation(&self, command: NavigationCommand, ui: &UserInterface) {
+        if matches!(command, NavigationCommand::Back | NavigationCommand::Accept) {
+            ui.send(self.window, WindowMessage::Close);
+        }
+    }
+
+    pub fn handle_ui_message(self, message: &UiMessage, ui: &mut UserInterface) -> Option<Self> {
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                ui.send(self.window, WidgetMessage::Remove);
+                return None;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data_from(self.close) {
+            ui.send(self.window, WindowMessage::Close);
+        }
+
+        Some(self)
+    }
+}
@@ -1,9 +1,14 @@
-use crate::{message::Message, MessageSender};
+use crate::{
+    gui, localization::tr, message::Message, saved_game, statistics::RunStatistics, MessageSender,
+};
 use fyrox::{
     core::{pool::Handle, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
     gui::{
         button::{Button, ButtonMessage},
+        font::FontResource,
         message::UiMessage,
+        text::TextMessage,
+        widget::WidgetMessage,
         UserInterface,
     },
     plugin::PluginContext,
@@ -24,15 +29,44 @@ pub struct DeathScreen {
 }
 
 impl DeathScreen {
-    pub fn new(mut ui: UserInterface, ctx: &mut PluginContext) -> Self {
+    pub fn new(
+        mut ui: UserInterface,
+        ctx: &mut PluginContext,
+        statistics: &RunStatistics,
+        font: FontResource,
+    ) -> Self {
         let data = ui
             .user_data
             .try_take::<DeathScreenData>()
             .unwrap_or_default();
 
-        Self {
+        gui::create_statistics_panel(&mut ui.build_ctx(), statistics, font);
+
+        // There is nothing to load until the player saves the game at least once.
+        ui.send(
+            data.load_game,
+            WidgetMessage::Enabled(saved_game::latest_saved_game().is_some()),
+        );
+
+        let screen = Self {
             ui: ctx.user_interfaces.add(ui),
             data,
+        };
+        if let Ok(ui) = ctx.user_interfaces.try_get(screen.ui) {
+            screen.localize(ui);
+        }
+        screen
+    }
+
+    fn localize(&self, ui: &UserInterface) {
+        for (button, key) in [
+            (self.data.load_game, "death_screen.load_last_save"),
+            (self.data.exit_to_menu, "death_screen.exit_to_menu"),
+            (self.data.exit_game, "death_screen.exit_game"),
+        ] {
+            if let Ok(button) = ui.try_get(button) {
+                ui.send(*button.content, TextMessage::Text(tr(key)));
+            }
         }
     }
 
@@ -48,8 +82,7 @@ impl DeathScreen {
         }
 
         if let Some(ButtonMessage::Click) = message.data_from(self.data.load_game) {
-            // TODO: Add quick saves.
-            // sender.send(Message::LoadGame);
+            sender.send(Message::LoadLastSave);
             self.destroy(ctx)
         } else if let Some(ButtonMessage::Click) = message.data_from(self.data.exit_to_menu) {
             sender.send(Message::ToggleMainMenu);
@@ -1,9 +1,11 @@
-use crate::{message::Message, MessageSender};
+use crate::{gui, localization::tr, message::Message, statistics::RunStatistics, MessageSender};
 use fyrox::{
     core::{pool::Handle, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
     gui::{
         button::{Button, ButtonMessage},
+        font::FontResource,
         message::UiMessage,
+        text::TextMessage,
         UserInterface,
     },
     plugin::PluginContext,
@@ -23,15 +25,37 @@ pub struct FinalScreen {
 }
 
 impl FinalScreen {
-    pub fn new(mut ui: UserInterface, ctx: &mut PluginContext) -> Self {
+    pub fn new(
+        mut ui: UserInterface,
+        ctx: &mut PluginContext,
+        statistics: &RunStatistics,
+        font: FontResource,
+    ) -> Self {
         let data = ui
             .user_data
             .try_take::<FinalScreenData>()
             .unwrap_or_default();
 
-        Self {
+        gui::create_statistics_panel(&mut ui.build_ctx(), statistics, font);
+
+        let screen = Self {
             ui: ctx.user_interfaces.add(ui),
             data,
+        };
+        if let Ok(ui) = ctx.user_interfaces.try_get(screen.ui) {
+            screen.localize(ui);
+        }
+        screen
+    }
+
+    fn localize(&self, ui: &UserInterface) {
+        for (button, key) in [
+            (self.data.exit_to_menu, "final_screen.exit_to_menu"),
+            (self.data.exit_game, "final_screen.exit_game"),
+        ] {
+            if let Ok(button) = ui.try_get(button) {
+                ui.send(*button.content, TextMessage::Text(tr(key)));
+            }
         }
     }
 
@@ -1,9 +1,10 @@
 use crate::{
     character::{CharacterMessage, CharacterMessageData},
-    control_scheme::{ControlButton, ControlScheme},
+    control_scheme::Action,
     gui,
     inventory::Inventory,
     level::item::Item,
+    localization::tr,
 };
 use fyrox::graph::constructor::{ConstructorProvider, GraphNodeConstructor};
 use fyrox::gui::message::MessageData;
@@ -25,7 +26,7 @@ use fyrox::{
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
         image::ImageBuilder,
-        message::{ButtonState, OsEvent, UiMessage},
+        message::{OsEvent, UiMessage},
         scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
@@ -181,7 +182,7 @@ impl InventoryItemBuilder {
                                                         .with_vertical_text_alignment(
                                                             VerticalAlignment::Center,
                                                         )
-                                                        .with_text((*item.name).clone())
+                                                        .with_text(item.localized_name())
                                                         .build(ctx),
                                                 )
                                                 .with_child({
@@ -253,7 +254,7 @@ impl InventoryInterface {
                         WidgetBuilder::new()
                             .with_child(
                                 TextBuilder::new(WidgetBuilder::new().on_row(0))
-                                    .with_text("Inventory")
+                                    .with_text(tr("inventory.title"))
                                     .with_horizontal_text_alignment(HorizontalAlignment::Center)
                                     .build(&mut ui.build_ctx()),
                             )
@@ -297,7 +298,7 @@ impl InventoryInterface {
                                                                     TextBuilder::new(
                                                                         WidgetBuilder::new(),
                                                                     )
-                                                                    .with_text("Item")
+                                                                    .with_text(tr("inventory.item"))
                                                                     .with_horizontal_text_alignment(
                                                                         HorizontalAlignment::Center,
                                                                     )
@@ -404,91 +405,71 @@ impl InventoryInterface {
         }
     }
 
-    pub fn process_os_event(
+    pub fn process_os_event(&mut self, os_event: &OsEvent) {
+        self.ui.process_os_event(os_event);
+    }
+
+    /// Navigates the inventory and uses or drops the selected item, see [`crate::input`].
+    pub fn process_action(
         &mut self,
-        os_event: &OsEvent,
-        control_scheme: &ControlScheme,
+        action: Action,
         player_handle: Handle<Node>,
         script_message_sender: &ScriptMessageSender,
     ) {
-        self.ui.process_os_event(os_event);
+        if !self.is_enabled {
+            return;
+        }
 
-        if self.is_enabled {
-            if let OsEvent::KeyboardInput { button, state, .. } = *os_event {
-                if state == ButtonState::Pressed {
-                    // TODO: Add support for other input bindings.
-                    if let ControlButton::Key(key) = control_scheme.cursor_up.button {
-                        if fyrox::utils::translate_key_to_ui(key) == button {
-                            self.try_move_selection(MoveDirection::Up);
-                        }
-                    }
-                    if let ControlButton::Key(key) = control_scheme.cursor_down.button {
-                        if fyrox::utils::translate_key_to_ui(key) == button {
-                            self.try_move_selection(MoveDirection::Down);
-                        }
-                    }
-                    if let ControlButton::Key(key) = control_scheme.cursor_left.button {
-                        if fyrox::utils::translate_key_to_ui(key) == button {
-                            self.try_move_selection(MoveDirection::Left);
-                        }
-                    }
-                    if let ControlButton::Key(key) = control_scheme.cursor_right.button {
-                        if fyrox::utils::translate_key_to_ui(key) == button {
-                            self.try_move_selection(MoveDirection::Right);
-                        }
-                    }
-                    if let ControlButton::Key(key) = control_scheme.action.button {
-                        if fyrox::utils::translate_key_to_ui(key) == button {
-                            let selection = self.selection();
-                            if selection.is_some() {
-                                if let Some(item) = self.ui.node(selection).cast::<InventoryItem>()
-                                {
-                                    let item_resource = &item.item;
-                                    Item::from_resource(item_resource, |item| {
-                                        if let Some(item) = item {
-                                            if item.enabled {
-                                                script_message_sender.send_to_target(
-                                                    player_handle,
-                                                    CharacterMessage {
-                                                        character: player_handle,
-                                                        data: CharacterMessageData::UseItem {
-                                                            item: item_resource.clone(),
-                                                        },
-                                                    },
-                                                );
-                                            }
-                                        }
-                                    });
-                                } else {
-                                    unreachable!()
-                                }
-                            }
-                        }
-                    }
-                    if let ControlButton::Key(key) = control_scheme.drop_item.button {
-                        if fyrox::utils::translate_key_to_ui(key) == button {
-                            let selection = self.selection();
-                            if selection.is_some() {
-                                if let Some(item) = self.ui.node(selection).cast::<InventoryItem>()
-                                {
+        match action {
+            Action::CursorUp => self.try_move_selection(MoveDirection::Up),
+            Action::CursorDown => self.try_move_selection(MoveDirection::Down),
+            Action::CursorLeft => self.try_move_selection(MoveDirection::Left),
+            Action::CursorRight => self.try_move_selection(MoveDirection::Right),
+            Action::Interact => {
+                let selection = self.selection();
+                if selection.is_some() {
+                    if let Some(item) = self.ui.node(selection).cast::<InventoryItem>() {
+                        let item_resource = &item.item;
+                        Item::from_resource(item_resource, |item| {
+                            if let Some(item) = item {
+                                if item.enabled {
                                     script_message_sender.send_to_target(
                                         player_handle,
                                         CharacterMessage {
                                             character: player_handle,
-                                            data: CharacterMessageData::DropItems {
-                                                item: item.item.clone(),
-                                                count: 1,
+                                            data: CharacterMessageData::UseItem {
+                                                item: item_resource.clone(),
                                             },
                                         },
                                     );
-                                } else {
-                                    unreachable!()
                                 }
                             }
-                        }
+                        });
+                    } else {
+                        unreachable!()
+                    }
+                }
+            }
+            Action::DropItem => {
+                let selection = self.selection();
+                if selection.is_some() {
+                    if let Some(item) = self.ui.node(selection).cast::<InventoryItem>() {
+                        script_message_sender.send_to_target(
+                            player_handle,
+                            CharacterMessage {
+                                character: player_handle,
+                                data: CharacterMessageData::DropItems {
+                                    item: item.item.clone(),
+                                    count: 1,
+                                },
+                            },
+                        );
+                    } else {
+                        unreachable!()
                     }
                 }
             }
+            _ => (),
         }
     }
 
@@ -554,7 +535,7 @@ impl InventoryInterface {
                             if let Some(item) = item {
                                 self.ui.send(
                                     self.item_description,
-                                    TextMessage::Text(item.description.deref().clone()),
+                                    TextMessage::Text(item.localized_description()),
                                 );
                             }
                         });
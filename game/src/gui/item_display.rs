@@ -1,4 +1,9 @@
-use crate::{control_scheme::ControlScheme, gui, level::item::Item};
+use crate::{
+    control_scheme::{Action, ControlScheme},
+    gui,
+    level::item::Item,
+    localization::tr_args,
+};
 use fyrox::gui::image::Image;
 use fyrox::gui::text::Text;
 use fyrox::{
@@ -122,7 +127,7 @@ impl ItemDisplay {
                 if let Some(item_script) = item {
                     self.ui.send(
                         self.item_name,
-                        TextMessage::Text(format!("{}-{}", *item_script.name, count)),
+                        TextMessage::Text(format!("{}-{}", item_script.localized_name(), count)),
                     );
                     self.ui.send(
                         self.item_image,
@@ -133,9 +138,12 @@ impl ItemDisplay {
 
             self.ui.send(
                 self.action_text,
-                TextMessage::Text(format!(
-                    "[{}] - Pickup",
-                    control_scheme.action.button.name()
+                TextMessage::Text(tr_args(
+                    "item.pick_up",
+                    &[(
+                        "button",
+                        control_scheme.binding_name(Action::Interact).as_str(),
+                    )],
                 )),
             );
         }
@@ -1,9 +1,6 @@
 #![allow(dead_code)] // TODO
 
-use crate::{
-    control_scheme::{ControlButton, ControlScheme},
-    gui,
-};
+use crate::{control_scheme::Action, gui, localization::tr};
 use fyrox::core::pool::HandlesVecExtension;
 use fyrox::gui::list_view::ListView;
 use fyrox::gui::text::Text;
@@ -15,7 +12,7 @@ use fyrox::{
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
         list_view::{ListViewBuilder, ListViewMessage},
-        message::{ButtonState, MessageDirection, OsEvent},
+        message::{MessageDirection, OsEvent},
         scroll_viewer::ScrollViewerBuilder,
         text::{TextBuilder, TextMessage},
         widget::WidgetBuilder,
@@ -34,6 +31,7 @@ pub enum JournalEntryKind {
     CurrentSituation,
 }
 
+/// Title and text of a journal entry, both are localization keys, see [`crate::localization`].
 #[derive(Deserialize)]
 pub struct JournalEntryDefinition {
     pub title: String,
@@ -112,7 +110,7 @@ impl JournalDisplay {
                             .with_child({
                                 objective =
                                     TextBuilder::new(WidgetBuilder::new().on_row(0).on_column(0))
-                                        .with_text("Investigate the reasons why connection with the station was lost.")
+                                        .with_text(tr("journal.objective"))
                                         .with_wrap(WrapMode::Word)
                                         .build(&mut ui.build_ctx());
                                 objective
@@ -176,7 +174,7 @@ impl JournalDisplay {
                 DecoratorBuilder::new(BorderBuilder::new(
                     WidgetBuilder::new().with_child(
                         TextBuilder::new(WidgetBuilder::new())
-                            .with_text(&definition.title)
+                            .with_text(tr(&definition.title))
                             .build(&mut self.ui.build_ctx()),
                     ),
                 ))
@@ -187,41 +185,22 @@ impl JournalDisplay {
             .send(self.messages, ListViewMessage::Items(items.to_base()));
     }
 
-    pub fn process_os_event(&mut self, os_event: &OsEvent, control_scheme: &ControlScheme) {
+    pub fn process_os_event(&mut self, os_event: &OsEvent) {
         self.ui.process_os_event(os_event);
+    }
 
-        if let OsEvent::KeyboardInput { button, state, .. } = *os_event {
-            if state == ButtonState::Pressed {
-                if let ControlButton::Key(key) = control_scheme.cursor_up.button {
-                    if fyrox::utils::translate_key_to_ui(key) == button {
-                        self.current_message = match self.current_message {
-                            None => Some(0),
-                            Some(n) => Some(n.saturating_sub(1)),
-                        };
-                        self.ui.send(
-                            self.messages,
-                            ListViewMessage::Selection(
-                                self.current_message.map(|n| vec![n]).unwrap_or_default(),
-                            ),
-                        );
-                    }
-                }
-                if let ControlButton::Key(key) = control_scheme.cursor_down.button {
-                    if fyrox::utils::translate_key_to_ui(key) == button {
-                        self.current_message = match self.current_message {
-                            None => Some(0),
-                            Some(n) => Some(n + 1),
-                        };
-                        self.ui.send(
-                            self.messages,
-                            ListViewMessage::Selection(
-                                self.current_message.map(|n| vec![n]).unwrap_or_default(),
-                            ),
-                        );
-                    }
-                }
-            }
-        }
+    /// Scrolls through the messages, see [`crate::input`].
+    pub fn process_action(&mut self, action: Action) {
+        self.current_message = match (action, self.current_message) {
+            (Action::CursorUp | Action::CursorDown, None) => Some(0),
+            (Action::CursorUp, Some(n)) => Some(n.saturating_sub(1)),
+            (Action::CursorDown, Some(n)) => Some(n + 1),
+            _ => return,
+        };
+        self.ui.send(
+            self.messages,
+            ListViewMessage::Selection(self.current_message.map(|n| vec![n]).unwrap_or_default()),
+        );
     }
 
     pub fn update(&mut self, delta: f32, journal: &Journal) {
@@ -239,7 +218,7 @@ impl JournalDisplay {
                     {
                         self.ui.send(
                             self.message_text,
-                            TextMessage::Text(entry.get_definition().text.clone()),
+                            TextMessage::Text(tr(&entry.get_definition().text)),
                         );
                     }
                 }
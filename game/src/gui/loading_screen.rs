@@ -1,3 +1,4 @@
+use crate::localization::tr;
 use fyrox::gui::grid::Grid;
 use fyrox::gui::progress_bar::ProgressBar;
 use fyrox::{
@@ -36,7 +37,7 @@ impl LoadingScreen {
                             TextBuilder::new(WidgetBuilder::new().on_row(0))
                                 .with_horizontal_text_alignment(HorizontalAlignment::Center)
                                 .with_vertical_text_alignment(VerticalAlignment::Center)
-                                .with_text("Loading... Please wait.")
+                                .with_text(tr("loading_screen.text"))
                                 .build(ctx),
                         ),
                 )
@@ -1,9 +1,15 @@
 use crate::{
+    achievements::Achievements,
     config::{Config, SoundConfig},
+    demo::InputEvent,
     gui::{
+        achievements::AchievementsPage,
+        navigation::{Navigation, NavigationCommand},
+        new_game::NewGameDialog,
         options_menu::OptionsMenu,
         save_load::{Mode, SaveLoadDialog},
     },
+    localization::tr,
     message::Message,
     Game, MessageSender,
 };
@@ -12,13 +18,14 @@ use fyrox::{
     event::Event,
     graph::SceneGraph,
     gui::{
-        button::{Button, ButtonMessage},
+        button::{Button, ButtonBuilder, ButtonMessage},
         font::FontResource,
-        grid::Grid,
+        grid::{Grid, GridMessage},
         message::UiMessage,
-        widget::WidgetMessage,
+        text::TextMessage,
+        widget::{WidgetBuilder, WidgetMessage},
         window::WindowMessage,
-        UserInterface,
+        Thickness, UserInterface,
     },
     plugin::{error::GameResult, PluginContext},
     scene::{sound::Sound, Scene},
@@ -43,6 +50,12 @@ pub struct Menu {
     data: MenuData,
     options_menu: Option<OptionsMenu>,
     save_load_dialog: Option<SaveLoadDialog>,
+    new_game_dialog: Option<NewGameDialog>,
+    achievements_page: Option<AchievementsPage>,
+    // The button is not defined in the ui file, see `add_achievements_button`.
+    btn_achievements: Handle<Button>,
+    #[visit(skip)]
+    navigation: Navigation,
     font: FontResource,
 }
 
@@ -74,6 +87,38 @@ impl MenuScene {
     }
 }
 
+/// Adds the button that opens the achievements page as a new row of the container of the buttons.
+/// The main menu ui was made before the achievements, so the button is created here instead.
+fn add_achievements_button(
+    ui: &mut UserInterface,
+    container: Handle<Grid>,
+    font: FontResource,
+) -> Handle<Button> {
+    let Ok(grid) = ui.try_get(container) else {
+        return Handle::NONE;
+    };
+
+    // The new row is the same as the row of the last button.
+    let mut rows = grid.rows.borrow().clone();
+    let row = rows.len();
+    if let Some(last) = rows.last().cloned() {
+        rows.push(last);
+    }
+
+    let button = ButtonBuilder::new(
+        WidgetBuilder::new()
+            .on_row(row)
+            .with_margin(Thickness::top(5.0)),
+    )
+    .with_text_and_font_size(&tr("menu.achievements"), font, 32.0.into())
+    .build(&mut ui.build_ctx());
+
+    ui.send(button, WidgetMessage::LinkWith(container.to_base()));
+    ui.send(container, GridMessage::Rows(rows));
+
+    button
+}
+
 impl Menu {
     pub fn new(
         mut user_interface: UserInterface,
@@ -99,16 +144,55 @@ impl Menu {
                 err!("There's no menu data in the main menu ui! Fallback to default.");
                 MenuData::default()
             });
+        let btn_achievements =
+            add_achievements_button(&mut user_interface, data.container, font.clone());
         let ui = context.user_interfaces.add(user_interface);
 
-        Self {
+        let menu = Self {
             scene: None,
             ui,
             data,
             options_menu: None,
             save_load_dialog: None,
+            new_game_dialog: None,
+            achievements_page: None,
+            btn_achievements,
+            navigation: Default::default(),
             font,
+        };
+        if let Ok(ui) = context.user_interfaces.try_get(ui) {
+            menu.localize(ui);
         }
+        menu
+    }
+
+    /// Replaces the text of the buttons (defined in the ui file) with the text in current language.
+    fn localize(&self, ui: &UserInterface) {
+        for (button, key) in [
+            (self.data.btn_new_game, "menu.new_game"),
+            (self.data.btn_save_game, "menu.save_game"),
+            (self.data.btn_load_game, "menu.load_game"),
+            (self.data.btn_settings, "menu.settings"),
+            (self.btn_achievements, "menu.achievements"),
+            (self.data.btn_quit_game, "menu.quit_game"),
+        ] {
+            if let Ok(button) = ui.try_get(button) {
+                ui.send(*button.content, TextMessage::Text(tr(key)));
+            }
+        }
+    }
+
+    pub fn on_language_changed(&mut self, ctx: &mut PluginContext, config: &Config) -> GameResult {
+        let ui = ctx.user_interfaces.try_get(self.ui)?;
+        self.localize(ui);
+
+        // Options menu is rebuilt, it is much simpler than updating every label in it.
+        if let Some(options_menu) = self.options_menu.take() {
+            ui.send(options_menu.window, WindowMessage::Close);
+            self.options_menu = Some(OptionsMenu::new(self.ui, ctx, config));
+        }
+
+        Ok(())
     }
 
     pub fn set_visible(&mut self, context: &mut PluginContext, visible: bool) -> GameResult {
@@ -151,6 +235,42 @@ impl Menu {
         Ok(())
     }
 
+    /// Navigates the menu or its opened screen with a gamepad.
+    pub fn process_gamepad_input(
+        &mut self,
+        ctx: &mut PluginContext,
+        event: InputEvent,
+        command: Option<NavigationCommand>,
+        config: &mut Config,
+    ) -> GameResult {
+        if let Some(options_menu) = self.options_menu.as_mut() {
+            return options_menu.process_gamepad_input(ctx, event, command, config);
+        }
+
+        let Some(command) = command else {
+            return Ok(());
+        };
+        let ui = ctx.user_interfaces.try_get(self.ui)?;
+        if let Some(save_load_dialog) = self.save_load_dialog.as_mut() {
+            save_load_dialog.process_navigation(command, ui);
+        } else if let Some(new_game_dialog) = self.new_game_dialog.as_mut() {
+            new_game_dialog.process_navigation(command, ui);
+        } else if let Some(achievements_page) = self.achievements_page.as_ref() {
+            achievements_page.process_navigation(command, ui);
+        } else {
+            let items = [
+                self.data.btn_new_game.to_base(),
+                self.data.btn_save_game.to_base(),
+                self.data.btn_load_game.to_base(),
+                self.data.btn_settings.to_base(),
+                self.btn_achievements.to_base(),
+                self.data.btn_quit_game.to_base(),
+            ];
+            self.navigation.process(command, &items, ui);
+        }
+        Ok(())
+    }
+
     pub fn sync_to_model(&mut self, ctx: &mut PluginContext, level_loaded: bool) -> GameResult {
         let ui = ctx.user_interfaces.try_get(self.ui)?;
         ui.send(
@@ -160,6 +280,10 @@ impl Menu {
         Ok(())
     }
 
+    fn on_new_game_clicked(&mut self, ui: &mut UserInterface) {
+        self.new_game_dialog = Some(NewGameDialog::new(self.font.clone(), &mut ui.build_ctx()));
+    }
+
     fn on_save_game_clicked(&mut self, ui: &mut UserInterface) {
         self.save_load_dialog = Some(SaveLoadDialog::new(
             Mode::Save,
@@ -176,6 +300,14 @@ impl Menu {
         ));
     }
 
+    fn on_achievements_clicked(&mut self, ui: &mut UserInterface, achievements: &Achievements) {
+        self.achievements_page = Some(AchievementsPage::new(
+            achievements,
+            self.font.clone(),
+            &mut ui.build_ctx(),
+        ));
+    }
+
     fn on_settings_clicked(&mut self, ctx: &mut PluginContext, config: &mut Config) -> GameResult {
         if let Some(options_menu) = self.options_menu.as_ref() {
             let ui = ctx.user_interfaces.try_get(self.ui)?;
@@ -189,7 +321,10 @@ impl Menu {
     pub fn update(&self, ctx: &mut PluginContext) -> GameResult {
         let ui = ctx.user_interfaces.try_get(self.ui)?;
 
-        let no_opened_screens = self.options_menu.is_none() && self.save_load_dialog.is_none();
+        let no_opened_screens = self.options_menu.is_none()
+            && self.save_load_dialog.is_none()
+            && self.new_game_dialog.is_none()
+            && self.achievements_page.is_none();
         ui.send_sync(
             self.data.container,
             WidgetMessage::Visibility(no_opened_screens),
@@ -204,6 +339,7 @@ impl Menu {
         ui_handle: Handle<UserInterface>,
         message: &UiMessage,
         config: &mut Config,
+        achievements: &Achievements,
         sender: &MessageSender,
     ) -> GameResult {
         if self.ui != ui_handle {
@@ -220,8 +356,16 @@ impl Menu {
             self.save_load_dialog = save_load_dialog.handle_ui_message(message, ui, sender);
         }
 
+        if let Some(new_game_dialog) = self.new_game_dialog.take() {
+            self.new_game_dialog = new_game_dialog.handle_ui_message(message, ui, sender);
+        }
+
+        if let Some(achievements_page) = self.achievements_page.take() {
+            self.achievements_page = achievements_page.handle_ui_message(message, ui);
+        }
+
         if let Some(ButtonMessage::Click) = message.data_from(self.data.btn_new_game) {
-            sender.send(Message::StartNewGame);
+            self.on_new_game_clicked(ui);
         } else if let Some(ButtonMessage::Click) = message.data_from(self.data.btn_save_game) {
             self.on_save_game_clicked(ui);
         } else if let Some(ButtonMessage::Click) = message.data_from(self.data.btn_load_game) {
@@ -230,6 +374,8 @@ impl Menu {
             sender.send(Message::QuitGame);
         } else if let Some(ButtonMessage::Click) = message.data_from(self.data.btn_settings) {
             self.on_settings_clicked(ctx, config)?;
+        } else if let Some(ButtonMessage::Click) = message.data_from(self.btn_achievements) {
+            self.on_achievements_clicked(ui, achievements);
         }
 
         Ok(())
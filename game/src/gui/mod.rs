@@ -1,16 +1,21 @@
 //! Contains all helper functions that creates styled widgets for game user interface.
 //! However most of the styles are used from dark theme of rg3d-ui library so there
 //! is not much.
+use crate::{localization::tr, statistics::RunStatistics};
 use fyrox::{
     core::pool::Handle,
     gui::{
-        check_box::CheckBox, check_box::CheckBoxBuilder, font::FontResource, scroll_bar::ScrollBar,
-        scroll_bar::ScrollBarBuilder, texture::TexturePixelKind, widget::WidgetBuilder,
-        BuildContext, HorizontalAlignment, Orientation, Thickness, VerticalAlignment,
+        border::BorderBuilder, check_box::CheckBox, check_box::CheckBoxBuilder, font::FontResource,
+        formatted_text::WrapMode, scroll_bar::ScrollBar, scroll_bar::ScrollBarBuilder,
+        scroll_viewer::ScrollViewerBuilder, text::TextBuilder, texture::TexturePixelKind,
+        widget::WidgetBuilder, BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode,
+        VerticalAlignment,
     },
     resource::texture::{TextureResource, TextureResourceExtension, TextureWrapMode},
 };
 
+pub mod achievement_toast;
+pub mod achievements;
 pub mod death_screen;
 pub mod final_screen;
 pub mod inventory;
@@ -18,8 +23,12 @@ pub mod item_display;
 pub mod journal;
 pub mod loading_screen;
 pub mod menu;
+pub mod navigation;
+pub mod new_game;
 pub mod options_menu;
+pub mod pause_menu;
 pub mod save_load;
+pub mod status_effects;
 pub mod weapon_display;
 
 pub struct ScrollBarData {
@@ -90,3 +99,36 @@ pub fn create_ui_render_target(width: f32, height: f32) -> TextureResource {
     drop(texture);
     render_target
 }
+
+/// Creates a panel with the statistics of a run, it is placed at the left side of the screen.
+pub fn create_statistics_panel(
+    ctx: &mut BuildContext,
+    statistics: &RunStatistics,
+    font: FontResource,
+) -> Handle<UiNode> {
+    let text = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(8.0)))
+        .with_wrap(WrapMode::Word)
+        .with_font(font)
+        .with_font_size(16.0.into())
+        .with_text(format!(
+            "{}\n\n{}",
+            tr("statistics.title"),
+            statistics.format()
+        ))
+        .build(ctx);
+
+    BorderBuilder::new(
+        WidgetBuilder::new()
+            .with_width(300.0)
+            .with_margin(Thickness::uniform(10.0))
+            .with_horizontal_alignment(HorizontalAlignment::Left)
+            .with_vertical_alignment(VerticalAlignment::Stretch)
+            .with_child(
+                ScrollViewerBuilder::new(WidgetBuilder::new())
+                    .with_content(text)
+                    .build(ctx),
+            ),
+    )
+    .build(ctx)
+    .to_base()
+}
@@ -0,0 +1,226 @@
+//! Navigation of the menus with a gamepad. The menus are made for the mouse, so the gamepad moves
+//! a selection over a list of the widgets of the menu: buttons are clicked, check boxes are
+//! toggled, scroll bars and drop-down lists are changed with left and right, lists are scrolled
+//! with up and down.
+
+use crate::{
+    control_scheme::{ControlAxis, ControlButton, GamepadButton},
+    demo::InputEvent,
+};
+use fyrox::{
+    core::{algebra::Vector2, pool::Handle},
+    gui::{
+        button::{Button, ButtonMessage},
+        check_box::{CheckBox, CheckBoxMessage},
+        dropdown_list::{DropdownList, DropdownListMessage},
+        list_view::{ListView, ListViewMessage},
+        message::UiMessage,
+        scroll_bar::{ScrollBar, ScrollBarMessage},
+        scroll_viewer::{ScrollViewer, ScrollViewerMessage},
+        widget::WidgetMessage,
+        UiNode, UserInterface,
+    },
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NavigationCommand {
+    Up,
+    Down,
+    Left,
+    Right,
+    Accept,
+    Back,
+    NextTab,
+    PrevTab,
+}
+
+/// Turns the input of a gamepad to navigation commands. Navigation does not depend on the control
+/// scheme, so the menus could be used even with a broken scheme.
+#[derive(Default, Debug)]
+pub struct NavigationInput {
+    stick: Vector2<f32>,
+    // Direction the stick is held in, the stick must return to the center to repeat a command.
+    stick_command: Option<NavigationCommand>,
+}
+
+impl NavigationInput {
+    const STICK_THRESHOLD: f32 = 0.5;
+
+    pub fn process(&mut self, event: InputEvent) -> Option<NavigationCommand> {
+        match event {
+            InputEvent::Button {
+                button: ControlButton::Gamepad(button),
+                pressed: true,
+            } => match button {
+                GamepadButton::DPadUp => Some(NavigationCommand::Up),
+                GamepadButton::DPadDown => Some(NavigationCommand::Down),
+                GamepadButton::DPadLeft => Some(NavigationCommand::Left),
+                GamepadButton::DPadRight => Some(NavigationCommand::Right),
+                GamepadButton::South => Some(NavigationCommand::Accept),
+                GamepadButton::East => Some(NavigationCommand::Back),
+                GamepadButton::RightBumper => Some(NavigationCommand::NextTab),
+                GamepadButton::LeftBumper => Some(NavigationCommand::PrevTab),
+                _ => None,
+            },
+            InputEvent::Axis { axis, value } => {
+                match axis {
+                    ControlAxis::LeftStickX => self.stick.x = value,
+                    ControlAxis::LeftStickY => self.stick.y = value,
+                    _ => return None,
+                }
+                let command = if self.stick.x.abs() > self.stick.y.abs() {
+                    if self.stick.x > Self::STICK_THRESHOLD {
+                        Some(NavigationCommand::Right)
+                    } else if self.stick.x < -Self::STICK_THRESHOLD {
+                        Some(NavigationCommand::Left)
+                    } else {
+                        None
+                    }
+                } else if self.stick.y > Self::STICK_THRESHOLD {
+                    Some(NavigationCommand::Up)
+                } else if self.stick.y < -Self::STICK_THRESHOLD {
+                    Some(NavigationCommand::Down)
+                } else {
+                    None
+                };
+                let previous = std::mem::replace(&mut self.stick_command, command);
+                command.filter(|command| previous != Some(*command))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Selection of a menu. Hidden and disabled widgets are skipped, so the same list of widgets could
+/// be used for every state of the menu.
+#[derive(Default, Debug, Clone)]
+pub struct Navigation {
+    selected: Option<Handle<UiNode>>,
+}
+
+impl Navigation {
+    /// Applies the command to the given widgets, `Back` and the tab commands are left to the menu.
+    pub fn process(
+        &mut self,
+        command: NavigationCommand,
+        items: &[Handle<UiNode>],
+        ui: &UserInterface,
+    ) {
+        let available = items
+            .iter()
+            .copied()
+            .filter(|item| {
+                ui.try_get(*item)
+                    .is_ok_and(|widget| widget.is_globally_visible() && widget.enabled())
+            })
+            .collect::<Vec<_>>();
+        if available.is_empty() {
+            self.selected = None;
+            return;
+        }
+
+        let current = self
+            .selected
+            .and_then(|selected| available.iter().position(|item| *item == selected));
+        let Some(current) = current else {
+            // The first command only shows where the selection is.
+            self.select(available[0], ui);
+            return;
+        };
+        let item = available[current];
+
+        match command {
+            NavigationCommand::Up | NavigationCommand::Down => {
+                let direction = if command == NavigationCommand::Down {
+                    1
+                } else {
+                    -1
+                };
+                if !step_list(item, direction, ui) {
+                    let next = (current as isize + direction).rem_euclid(available.len() as isize);
+                    self.select(available[next as usize], ui);
+                }
+            }
+            NavigationCommand::Left => adjust(item, -1, ui),
+            NavigationCommand::Right => adjust(item, 1, ui),
+            NavigationCommand::Accept => accept(item, ui),
+            NavigationCommand::Back | NavigationCommand::NextTab | NavigationCommand::PrevTab => (),
+        }
+    }
+
+    /// Forgets the selection, the next command selects the first widget again.
+    pub fn reset(&mut self) {
+        self.selected = None;
+    }
+
+    fn select(&mut self, item: Handle<UiNode>, ui: &UserInterface) {
+        self.selected = Some(item);
+        ui.send(item, WidgetMessage::Focus);
+
+        // Long menus are placed in scroll viewers, the selection must stay visible.
+        let mut parent = ui
+            .try_get(item)
+            .map(|widget| widget.parent())
+            .unwrap_or_default();
+        while let Ok(widget) = ui.try_get(parent) {
+            if widget.cast::<ScrollViewer>().is_some() {
+                ui.send(parent, ScrollViewerMessage::BringIntoView(item));
+                break;
+            }
+            parent = widget.parent();
+        }
+    }
+}
+
+fn step_index(index: Option<usize>, direction: isize, count: usize) -> Option<usize> {
+    match index {
+        Some(index) => index
+            .checked_add_signed(direction)
+            .filter(|index| *index < count),
+        None if direction > 0 && count > 0 => Some(0),
+        None => None,
+    }
+}
+
+/// Moves the selection of a list, returns `false` if the item is not a list or the selection is
+/// at the end of the list.
+fn step_list(item: Handle<UiNode>, direction: isize, ui: &UserInterface) -> bool {
+    let Ok(list) = ui.try_get_of_type::<ListView>(item) else {
+        return false;
+    };
+    let Some(index) = step_index(list.selection.first().copied(), direction, list.items.len())
+    else {
+        return false;
+    };
+    ui.send(item, ListViewMessage::Selection(vec![index]));
+    ui.send(item, ListViewMessage::BringItemIntoView(list.items[index]));
+    true
+}
+
+fn adjust(item: Handle<UiNode>, direction: isize, ui: &UserInterface) {
+    if let Ok(scroll_bar) = ui.try_get_of_type::<ScrollBar>(item) {
+        let value = (*scroll_bar.value + direction as f32 * *scroll_bar.step)
+            .clamp(*scroll_bar.min, *scroll_bar.max);
+        ui.send(item, ScrollBarMessage::Value(value));
+    } else if let Ok(dropdown_list) = ui.try_get_of_type::<DropdownList>(item) {
+        if let Some(index) = step_index(
+            *dropdown_list.selection,
+            direction,
+            dropdown_list.items.len(),
+        ) {
+            ui.send(item, DropdownListMessage::Selection(Some(index)));
+        }
+    }
+}
+
+fn accept(item: Handle<UiNode>, ui: &UserInterface) {
+    if ui.try_get_of_type::<Button>(item).is_ok() {
+        // Menus react to the clicks reported by the buttons.
+        ui.send_message(UiMessage::from_widget(item, ButtonMessage::Click));
+    } else if let Ok(check_box) = ui.try_get_of_type::<CheckBox>(item) {
+        ui.send(
+            item,
+            CheckBoxMessage::Check(Some(*check_box.checked != Some(true))),
+        );
+    }
+}
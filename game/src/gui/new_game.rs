@@ -0,0 +1,196 @@
+use crate::{
+    difficulty::Difficulty,
+    gui::navigation::{Navigation, NavigationCommand},
+    localization::tr,
+    message::Message,
+    MessageSender,
+};
+use fyrox::{
+    core::{pool::Handle, pool::HandlesVecExtension, visitor::prelude::*},
+    gui::{
+        border::BorderBuilder,
+        button::{Button, ButtonBuilder, ButtonMessage},
+        decorator::{Decorator, DecoratorBuilder},
+        font::FontResource,
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListView, ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{Window, WindowAlignment, WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UserInterface,
+        VerticalAlignment,
+    },
+};
+
+/// A dialog that asks for the difficulty of a new game.
+#[derive(Default, Debug, Visit, Clone)]
+pub struct NewGameDialog {
+    pub window: Handle<Window>,
+    difficulties: Handle<ListView>,
+    start: Handle<Button>,
+    cancel: Handle<Button>,
+    difficulty: Difficulty,
+    #[visit(skip)]
+    navigation: Navigation,
+}
+
+fn create_difficulty_entry(
+    difficulty: Difficulty,
+    font: FontResource,
+    ctx: &mut BuildContext,
+) -> Handle<Decorator> {
+    let text = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(4.0)))
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .with_horizontal_text_alignment(HorizontalAlignment::Left)
+        .with_wrap(WrapMode::Word)
+        .with_font(font)
+        .with_font_size(16.0.into())
+        .with_text(format!(
+            "{}\n{}",
+            tr(difficulty.name_key()),
+            tr(difficulty.description_key())
+        ))
+        .build(ctx);
+
+    DecoratorBuilder::new(BorderBuilder::new(WidgetBuilder::new().with_child(text))).build(ctx)
+}
+
+fn make_button(text: &str, font: &FontResource, ctx: &mut BuildContext) -> Handle<Button> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(120.0)
+            .with_margin(Thickness::uniform(2.0)),
+    )
+    .with_text_and_font_size(text, font.clone(), 24.0.into())
+    .build(ctx)
+}
+
+impl NewGameDialog {
+    pub fn new(font: FontResource, ctx: &mut BuildContext) -> Self {
+        let difficulty = Difficulty::default();
+
+        let items = Difficulty::ALL
+            .iter()
+            .map(|difficulty| create_difficulty_entry(*difficulty, font.clone(), ctx))
+            .collect::<Vec<_>>();
+
+        let difficulties = ListViewBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(0),
+        )
+        .with_items(items.to_base())
+        .build(ctx);
+
+        let start = make_button(&tr("new_game.start"), &font, ctx);
+        let cancel = make_button(&tr("new_game.cancel"), &font, ctx);
+
+        let buttons = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .on_row(1)
+                .with_child(start)
+                .with_child(cancel),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(difficulties)
+                .with_child(buttons),
+        )
+        .add_row(Row::stretch())
+        .add_row(Row::strict(35.0))
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(450.0).with_height(420.0))
+            .can_minimize(false)
+            .can_maximize(false)
+            .open(false)
+            .with_title(WindowTitle::text(tr("new_game.title")))
+            .with_content(content)
+            .build(ctx);
+
+        let ui = ctx.inner();
+        ui.send(
+            difficulties,
+            ListViewMessage::Selection(
+                Difficulty::ALL
+                    .iter()
+                    .position(|d| *d == difficulty)
+                    .into_iter()
+                    .collect(),
+            ),
+        );
+        ui.send(
+            window,
+            WindowMessage::Open {
+                alignment: WindowAlignment::Center,
+                focus_content: true,
+                modal: true,
+            },
+        );
+
+        Self {
+            window,
+            difficulties,
+            start,
+            cancel,
+            difficulty,
+            navigation: Default::default(),
+        }
+    }
+
+    pub fn process_navigation(&mut self, command: NavigationCommand, ui: &UserInterface) {
+        if command == NavigationCommand::Back {
+            ui.send(self.window, WindowMessage::Close);
+        } else {
+            let items = [
+                self.difficulties.to_base(),
+                self.start.to_base(),
+                self.cancel.to_base(),
+            ];
+            self.navigation.process(command, &items, ui);
+        }
+    }
+
+    pub fn handle_ui_message(
+        mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+    ) -> Option<Self> {
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                ui.send(self.window, WidgetMessage::Remove);
+                return None;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.start {
+                sender.send(Message::StartNewGame(self.difficulty));
+                ui.send(self.window, WindowMessage::Close);
+            } else if message.destination() == self.cancel {
+                ui.send(self.window, WindowMessage::Close);
+            }
+        } else if let Some(ListViewMessage::Selection(selection)) = message.data() {
+            if message.destination() == self.difficulties
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(difficulty) = selection
+                    .first()
+                    .and_then(|index| Difficulty::ALL.get(*index))
+                {
+                    self.difficulty = *difficulty;
+                }
+                ui.send(self.start, WidgetMessage::Enabled(!selection.is_empty()));
+            }
+        }
+
+        Some(self)
+    }
+}
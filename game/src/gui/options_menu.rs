@@ -1,7 +1,17 @@
 use crate::{
     config::Config,
-    control_scheme::ControlButton,
-    gui::{create_check_box, create_scroll_bar, ScrollBarData},
+    control_scheme::{
+        Action, ActivationMode, Binding, ConflictResolution, ControlButton, ControlScheme,
+        Modifiers, ResponseCurve,
+    },
+    debug_layers::DebugLayer,
+    demo::InputEvent,
+    gui::{
+        create_check_box, create_scroll_bar,
+        navigation::{Navigation, NavigationCommand},
+        ScrollBarData,
+    },
+    localization::{self, tr, tr_args},
     message::Message,
     MessageSender,
 };
@@ -15,7 +25,7 @@ use fyrox::{
         visitor::prelude::*,
     },
     engine::{GraphicsContext, InitializedGraphicsContext},
-    event::{Event, MouseButton, MouseScrollDelta, WindowEvent},
+    event::{ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent},
     gui::{
         border::BorderBuilder,
         button::{Button, ButtonBuilder, ButtonMessage},
@@ -23,17 +33,19 @@ use fyrox::{
         decorator::{Decorator, DecoratorBuilder},
         dropdown_list::{DropdownList, DropdownListBuilder, DropdownListMessage},
         font::{Font, FontResource},
+        formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         scroll_bar::{ScrollBar, ScrollBarMessage},
         scroll_viewer::ScrollViewerBuilder,
-        tab_control::{TabControlBuilder, TabDefinition},
+        stack_panel::{StackPanel, StackPanelBuilder},
+        tab_control::{TabControl, TabControlBuilder, TabControlMessage, TabDefinition},
         text::{Text, TextBuilder, TextMessage},
-        widget::WidgetBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
         window::{Window, WindowAlignment, WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, VerticalAlignment,
     },
-    keyboard::PhysicalKey,
+    keyboard::{KeyCode, PhysicalKey},
     monitor::VideoModeHandle,
     plugin::PluginContext,
     renderer::ShadowMapPrecision,
@@ -56,16 +68,56 @@ pub struct OptionsMenu {
     use_light_scatter: Handle<CheckBox>,
     fxaa: Handle<CheckBox>,
     ssao: Handle<CheckBox>,
+    // Buttons of the editable bindings, `ControlScheme::EDITABLE_BINDINGS` per action in the same
+    // order as in `Action::ALL`.
     control_scheme_buttons: Vec<Handle<Button>>,
     active_control_button: Option<usize>,
+    // Modifiers held while a new binding is awaited. A modifier released without pressing any
+    // other button becomes the binding itself.
+    #[visit(skip)]
+    held_modifiers: Modifiers,
+    #[visit(skip)]
+    pending_modifier: Option<ControlButton>,
     mouse_sens: Handle<ScrollBar>,
+    mouse_aim_sens: Handle<ScrollBar>,
+    mouse_x_inverse: Handle<CheckBox>,
     mouse_y_inverse: Handle<CheckBox>,
+    mouse_smoothing: Handle<ScrollBar>,
+    mouse_acceleration: Handle<ScrollBar>,
+    gamepad_dead_zone: Handle<ScrollBar>,
+    gamepad_response_curve: Handle<DropdownList>,
+    gamepad_look_sensitivity: Handle<ScrollBar>,
+    gamepad_aim_look_sensitivity: Handle<ScrollBar>,
+    aim_assist: Handle<CheckBox>,
+    aim_assist_strength: Handle<ScrollBar>,
+    // Selectors of the activation modes in the same order as in `Action::HAS_ACTIVATION_MODE`.
+    activation_modes: Vec<Handle<DropdownList>>,
     reset_control_scheme: Handle<Button>,
+    // Buttons that reset the bindings of the actions, in the same order as in `Action::ALL`.
+    reset_action_buttons: Vec<Handle<Button>>,
+    conflict: Handle<StackPanel>,
+    conflict_text: Handle<Text>,
+    conflict_swap: Handle<Button>,
+    conflict_clear: Handle<Button>,
+    conflict_cancel: Handle<Button>,
+    // Index of the control scheme button and the binding that conflicts with other actions, it is
+    // applied once the player chooses how to resolve the conflict.
+    #[visit(skip)]
+    pending_rebind: Option<(usize, Binding)>,
     use_hrtf: Handle<CheckBox>,
     reset_audio_settings: Handle<Button>,
     point_shadows_quality: Handle<DropdownList>,
     spot_shadows_quality: Handle<DropdownList>,
     show_debug_info: Handle<CheckBox>,
+    // Check boxes of the debug layers in the same order as in `DebugLayer::ALL`.
+    debug_layers: Vec<Handle<CheckBox>>,
+    language: Handle<DropdownList>,
+    // Codes of the languages in the same order as in the language selector.
+    languages: Vec<String>,
+    tab_control: Handle<TabControl>,
+    active_tab: usize,
+    #[visit(skip)]
+    navigation: Navigation,
     font: FontResource,
 }
 
@@ -88,6 +140,26 @@ fn make_text_mark(
     .build(ctx)
 }
 
+/// Action and the index of its binding edited by the control scheme button with the given index.
+fn binding_slot(button_index: usize) -> (Action, usize) {
+    (
+        Action::ALL[button_index / ControlScheme::EDITABLE_BINDINGS],
+        button_index % ControlScheme::EDITABLE_BINDINGS,
+    )
+}
+
+fn binding_text(control_scheme: &ControlScheme, action: Action, slot: usize) -> String {
+    control_scheme
+        .bindings(action)
+        .get(slot)
+        .map(|binding| binding.name())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Rows of the controls tab before the rows of the actions.
+const CONTROLS_HEADER_ROWS: usize = 12 + Action::HAS_ACTIVATION_MODE.len();
+const TAB_COUNT: usize = 5;
+
 fn make_tab_header(text: &str, font: FontResource, ctx: &mut BuildContext) -> Handle<Text> {
     TextBuilder::new(
         WidgetBuilder::new()
@@ -160,24 +232,29 @@ fn make_shadows_quality_drop_down(
             .with_margin(Thickness::uniform(1.0)),
     )
     .with_items({
-        ["Low", "Medium", "High", "Ultra"]
-            .iter()
-            .map(|o| {
-                DecoratorBuilder::new(BorderBuilder::new(
-                    WidgetBuilder::new().with_child(
-                        TextBuilder::new(WidgetBuilder::new())
-                            .with_vertical_text_alignment(VerticalAlignment::Center)
-                            .with_horizontal_text_alignment(HorizontalAlignment::Center)
-                            .with_font(font.clone())
-                            .with_font_size(16.0.into())
-                            .with_text(*o)
-                            .build(ctx),
-                    ),
-                ))
-                .build(ctx)
-            })
-            .collect::<Vec<_>>()
-            .to_base()
+        [
+            "options.quality_low",
+            "options.quality_medium",
+            "options.quality_high",
+            "options.quality_ultra",
+        ]
+        .iter()
+        .map(|o| {
+            DecoratorBuilder::new(BorderBuilder::new(
+                WidgetBuilder::new().with_child(
+                    TextBuilder::new(WidgetBuilder::new())
+                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                        .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                        .with_font(font.clone())
+                        .with_font_size(16.0.into())
+                        .with_text(tr(o))
+                        .build(ctx),
+                ),
+            ))
+            .build(ctx)
+        })
+        .collect::<Vec<_>>()
+        .to_base()
     })
     .with_selected(current)
     .build(ctx)
@@ -222,7 +299,19 @@ impl OptionsMenu {
         let point_shadow_distance;
         let spot_shadow_distance;
         let mouse_sens;
+        let mouse_aim_sens;
+        let mouse_x_inverse;
         let mouse_y_inverse;
+        let mouse_smoothing;
+        let mouse_acceleration;
+        let gamepad_dead_zone;
+        let gamepad_response_curve;
+        let gamepad_look_sensitivity;
+        let gamepad_aim_look_sensitivity;
+        let mut activation_modes = Vec::new();
+        let mut reset_action_buttons = Vec::new();
+        let aim_assist;
+        let aim_assist_strength;
         let reset_control_scheme;
         let mut control_scheme_buttons = Vec::new();
         let use_hrtf;
@@ -245,17 +334,74 @@ impl OptionsMenu {
                 vec![]
             };
 
-        let mut modes = vec![make_video_mode_item_raw("Windowed", font.clone(), ctx)];
+        let mut modes = vec![make_video_mode_item_raw(
+            &tr("options.windowed"),
+            font.clone(),
+            ctx,
+        )];
         modes.extend(
             video_modes
                 .iter()
                 .map(|video_mode| make_video_mode_item(video_mode, font.clone(), ctx)),
         );
 
+        let languages = localization::languages();
+        let current_language = localization::language();
+        let language = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .on_row(0)
+                .with_margin(margin),
+        )
+        .with_items(
+            languages
+                .iter()
+                .map(|(_, name)| make_video_mode_item_raw(name, font.clone(), ctx))
+                .collect::<Vec<_>>()
+                .to_base(),
+        )
+        .with_close_on_selection(true)
+        .with_selected(
+            languages
+                .iter()
+                .position(|(code, _)| *code == current_language)
+                .unwrap_or_default(),
+        )
+        .build(ctx);
+
+        let general_content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(5.0))
+                .with_child(make_text_mark(
+                    &tr("options.language"),
+                    font.clone(),
+                    0,
+                    ctx,
+                ))
+                .with_child(language),
+        )
+        .add_row(common_row)
+        .add_column(Column::strict(270.0))
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        let general_tab = TabDefinition {
+            uuid: uuid!("b17b91ef-b997-42ee-8365-68e10687855b"),
+            header: make_tab_header(&tr("options.general"), font.clone(), ctx).to_base(),
+            can_be_closed: false,
+            user_data: None,
+            content: general_content.to_base(),
+        };
+
         let graphics_content = GridBuilder::new(
             WidgetBuilder::new()
                 .with_margin(Thickness::uniform(5.0))
-                .with_child(make_text_mark("Resolution", font.clone(), 0, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.resolution"),
+                    font.clone(),
+                    0,
+                    ctx,
+                ))
                 .with_child({
                     video_mode = DropdownListBuilder::new(
                         WidgetBuilder::new()
@@ -270,14 +416,24 @@ impl OptionsMenu {
                     video_mode
                 })
                 // Spot Shadows Enabled
-                .with_child(make_text_mark("Spot Shadows", font.clone(), 1, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.spot_shadows"),
+                    font.clone(),
+                    1,
+                    ctx,
+                ))
                 .with_child({
                     spot_shadows =
                         create_check_box(ctx, 1, 1, config.graphics.spot_shadows_enabled);
                     spot_shadows
                 })
                 // Soft Spot Shadows
-                .with_child(make_text_mark("Soft Spot Shadows", font.clone(), 2, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.soft_spot_shadows"),
+                    font.clone(),
+                    2,
+                    ctx,
+                ))
                 .with_child({
                     soft_spot_shadows =
                         create_check_box(ctx, 2, 1, config.graphics.spot_soft_shadows);
@@ -285,7 +441,7 @@ impl OptionsMenu {
                 })
                 // Spot Shadows Distance
                 .with_child(make_text_mark(
-                    "Spot Shadows Distance",
+                    &tr("options.spot_shadows_distance"),
                     font.clone(),
                     3,
                     ctx,
@@ -309,14 +465,24 @@ impl OptionsMenu {
                     spot_shadow_distance
                 })
                 // Point Shadows Enabled
-                .with_child(make_text_mark("Point Shadows", font.clone(), 4, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.point_shadows"),
+                    font.clone(),
+                    4,
+                    ctx,
+                ))
                 .with_child({
                     point_shadows =
                         create_check_box(ctx, 4, 1, config.graphics.point_shadows_enabled);
                     point_shadows
                 })
                 // Soft Point Shadows
-                .with_child(make_text_mark("Soft Point Shadows", font.clone(), 5, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.soft_point_shadows"),
+                    font.clone(),
+                    5,
+                    ctx,
+                ))
                 .with_child({
                     soft_point_shadows =
                         create_check_box(ctx, 5, 1, config.graphics.point_soft_shadows);
@@ -324,7 +490,7 @@ impl OptionsMenu {
                 })
                 // Point Shadows Distance
                 .with_child(make_text_mark(
-                    "Point Shadows Distance",
+                    &tr("options.point_shadows_distance"),
                     font.clone(),
                     6,
                     ctx,
@@ -347,24 +513,29 @@ impl OptionsMenu {
                     );
                     point_shadow_distance
                 })
-                .with_child(make_text_mark("Use Light Scatter", font.clone(), 7, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.light_scatter"),
+                    font.clone(),
+                    7,
+                    ctx,
+                ))
                 .with_child({
                     use_light_scatter =
                         create_check_box(ctx, 7, 1, config.graphics.light_scatter_enabled);
                     use_light_scatter
                 })
-                .with_child(make_text_mark("FXAA", font.clone(), 8, ctx))
+                .with_child(make_text_mark(&tr("options.fxaa"), font.clone(), 8, ctx))
                 .with_child({
                     fxaa = create_check_box(ctx, 8, 1, config.graphics.fxaa);
                     fxaa
                 })
-                .with_child(make_text_mark("SSAO", font.clone(), 9, ctx))
+                .with_child(make_text_mark(&tr("options.ssao"), font.clone(), 9, ctx))
                 .with_child({
                     ssao = create_check_box(ctx, 9, 1, config.graphics.use_ssao);
                     ssao
                 })
                 .with_child(make_text_mark(
-                    "Point Shadows Quality",
+                    &tr("options.point_shadows_quality"),
                     font.clone(),
                     10,
                     ctx,
@@ -379,7 +550,7 @@ impl OptionsMenu {
                     point_shadows_quality
                 })
                 .with_child(make_text_mark(
-                    "Spot Shadows Quality",
+                    &tr("options.spot_shadows_quality"),
                     font.clone(),
                     11,
                     ctx,
@@ -393,7 +564,12 @@ impl OptionsMenu {
                     );
                     spot_shadows_quality
                 })
-                .with_child(make_text_mark("Show Debug Info", font.clone(), 12, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.show_debug_info"),
+                    font.clone(),
+                    12,
+                    ctx,
+                ))
                 .with_child({
                     show_debug_info = create_check_box(ctx, 12, 1, config.show_debug_info);
                     show_debug_info
@@ -418,7 +594,7 @@ impl OptionsMenu {
 
         let graphics_tab = TabDefinition {
             uuid: uuid!("16099fbe-a17a-44fc-91e8-90982d34e4af"),
-            header: make_tab_header("Graphics", font.clone(), ctx).to_base(),
+            header: make_tab_header(&tr("options.graphics"), font.clone(), ctx).to_base(),
             can_be_closed: false,
             user_data: None,
             content: {
@@ -431,7 +607,12 @@ impl OptionsMenu {
 
         let sound_content = GridBuilder::new(
             WidgetBuilder::new()
-                .with_child(make_text_mark("Sound Volume", font.clone(), 0, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.sound_volume"),
+                    font.clone(),
+                    0,
+                    ctx,
+                ))
                 .with_child({
                     sound_volume = create_scroll_bar(
                         ctx,
@@ -450,7 +631,12 @@ impl OptionsMenu {
                     );
                     sound_volume
                 })
-                .with_child(make_text_mark("Music Volume", font.clone(), 1, ctx))
+                .with_child(make_text_mark(
+                    &tr("options.music_volume"),
+                    font.clone(),
+                    1,
+                    ctx,
+                ))
                 .with_child({
                     music_volume = create_scroll_bar(
                         ctx,
@@ -469,7 +655,7 @@ impl OptionsMenu {
                     );
                     music_volume
                 })
-                .with_child(make_text_mark("Use HRTF", font.clone(), 2, ctx))
+                .with_child(make_text_mark(&tr("options.hrtf"), font.clone(), 2, ctx))
                 .with_child({
                     use_hrtf = create_check_box(ctx, 2, 1, config.sound.use_hrtf);
                     use_hrtf
@@ -477,7 +663,7 @@ impl OptionsMenu {
                 .with_child({
                     reset_audio_settings =
                         ButtonBuilder::new(WidgetBuilder::new().on_row(4).with_margin(margin))
-                            .with_text("Reset")
+                            .with_text(&tr("options.reset"))
                             .build(ctx);
                     reset_audio_settings
                 }),
@@ -493,7 +679,7 @@ impl OptionsMenu {
 
         let sound_tab = TabDefinition {
             uuid: uuid!("3e086252-057c-42cb-b3ef-e25e7093ce4b"),
-            header: make_tab_header("Sound", font.clone(), ctx).to_base(),
+            header: make_tab_header(&tr("options.sound"), font.clone(), ctx).to_base(),
             can_be_closed: false,
             user_data: None,
             content: {
@@ -507,37 +693,106 @@ impl OptionsMenu {
         let controls_content = {
             let mut children = Vec::<Handle<UiNode>>::new();
 
-            for (row, button) in config.controls.buttons().iter().enumerate() {
+            // Activation modes go right before the bindings.
+            let first_row = CONTROLS_HEADER_ROWS - Action::HAS_ACTIVATION_MODE.len();
+            for (row, action) in Action::HAS_ACTIVATION_MODE.into_iter().enumerate() {
+                let row = row + first_row;
+
+                children.push(
+                    make_text_mark(
+                        &format!(
+                            "{} ({})",
+                            tr(action.description_key()),
+                            tr("options.activation_mode")
+                        ),
+                        font.clone(),
+                        row,
+                        ctx,
+                    )
+                    .to_base(),
+                );
+
+                let mode = config.controls.activation_mode(action);
+                let dropdown_list = DropdownListBuilder::new(
+                    WidgetBuilder::new()
+                        .on_row(row)
+                        .on_column(1)
+                        .with_margin(margin),
+                )
+                .with_items(
+                    ActivationMode::ALL
+                        .iter()
+                        .map(|mode| {
+                            make_video_mode_item_raw(
+                                &tr(mode.localization_key()),
+                                font.clone(),
+                                ctx,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .to_base(),
+                )
+                .with_close_on_selection(true)
+                .with_selected(
+                    ActivationMode::ALL
+                        .iter()
+                        .position(|m| *m == mode)
+                        .unwrap_or_default(),
+                )
+                .build(ctx);
+                children.push(dropdown_list.to_base());
+                activation_modes.push(dropdown_list);
+            }
+
+            for (row, action) in Action::ALL.into_iter().enumerate() {
                 // Offset by total amount of rows that goes before
-                let row = row + 2;
+                let row = row + CONTROLS_HEADER_ROWS;
 
                 children.push(
-                    make_text_mark(button.description.as_str(), font.clone(), row, ctx).to_base(),
+                    make_text_mark(&tr(action.description_key()), font.clone(), row, ctx).to_base(),
                 );
 
-                let button = ButtonBuilder::new(
+                for slot in 0..ControlScheme::EDITABLE_BINDINGS {
+                    let button = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(margin)
+                            .on_row(row)
+                            .on_column(1 + slot),
+                    )
+                    .with_content(
+                        TextBuilder::new(WidgetBuilder::new())
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                            .with_font(font.clone())
+                            .with_font_size(16.0.into())
+                            .with_text(binding_text(&config.controls, action, slot))
+                            .build(ctx),
+                    )
+                    .build(ctx);
+                    children.push(button.to_base());
+                    control_scheme_buttons.push(button);
+                }
+
+                let reset_action = ButtonBuilder::new(
                     WidgetBuilder::new()
                         .with_margin(margin)
                         .on_row(row)
-                        .on_column(1),
-                )
-                .with_content(
-                    TextBuilder::new(WidgetBuilder::new())
-                        .with_vertical_text_alignment(VerticalAlignment::Center)
-                        .with_horizontal_text_alignment(HorizontalAlignment::Center)
-                        .with_font(font.clone())
-                        .with_font_size(16.0.into())
-                        .with_text(button.button.name())
-                        .build(ctx),
+                        .on_column(1 + ControlScheme::EDITABLE_BINDINGS),
                 )
+                .with_text_and_font_size(&tr("options.reset"), font.clone(), 16.0.into())
                 .build(ctx);
-                children.push(button.to_base());
-                control_scheme_buttons.push(button);
+                children.push(reset_action.to_base());
+                reset_action_buttons.push(reset_action);
             }
 
             GridBuilder::new(
                 WidgetBuilder::new()
-                    .with_child(make_text_mark("Mouse Sensitivity", font.clone(), 0, ctx))
+                    .with_child(make_text_mark(
+                        &tr("options.mouse_sensitivity"),
+                        font.clone(),
+                        0,
+                        ctx,
+                    ))
                     .with_child({
                         mouse_sens = create_scroll_bar(
                             ctx,
@@ -556,19 +811,250 @@ impl OptionsMenu {
                         );
                         mouse_sens
                     })
-                    .with_child(make_text_mark("Inverse Mouse Y", font.clone(), 1, ctx))
+                    .with_child(make_text_mark(
+                        &tr("options.mouse_aim_sensitivity"),
+                        font.clone(),
+                        1,
+                        ctx,
+                    ))
+                    .with_child({
+                        mouse_aim_sens = create_scroll_bar(
+                            ctx,
+                            ScrollBarData {
+                                min: 0.05,
+                                max: 2.0,
+                                value: config.controls.mouse_aim_sens,
+                                step: 0.05,
+                                row: 1,
+                                column: 1,
+                                margin,
+                                show_value: true,
+                                orientation: Orientation::Horizontal,
+                                font: font.clone(),
+                            },
+                        );
+                        mouse_aim_sens
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.inverse_mouse_x"),
+                        font.clone(),
+                        2,
+                        ctx,
+                    ))
+                    .with_child({
+                        mouse_x_inverse =
+                            create_check_box(ctx, 2, 1, config.controls.mouse_x_inverse);
+                        mouse_x_inverse
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.inverse_mouse_y"),
+                        font.clone(),
+                        3,
+                        ctx,
+                    ))
                     .with_child({
                         mouse_y_inverse =
-                            create_check_box(ctx, 1, 1, config.controls.mouse_y_inverse);
+                            create_check_box(ctx, 3, 1, config.controls.mouse_y_inverse);
                         mouse_y_inverse
                     })
+                    .with_child(make_text_mark(
+                        &tr("options.mouse_smoothing"),
+                        font.clone(),
+                        4,
+                        ctx,
+                    ))
+                    .with_child({
+                        mouse_smoothing = create_scroll_bar(
+                            ctx,
+                            ScrollBarData {
+                                min: 0.0,
+                                max: 0.2,
+                                value: config.controls.mouse_smoothing,
+                                step: 0.01,
+                                row: 4,
+                                column: 1,
+                                margin,
+                                show_value: true,
+                                orientation: Orientation::Horizontal,
+                                font: font.clone(),
+                            },
+                        );
+                        mouse_smoothing
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.mouse_acceleration"),
+                        font.clone(),
+                        5,
+                        ctx,
+                    ))
+                    .with_child({
+                        mouse_acceleration = create_scroll_bar(
+                            ctx,
+                            ScrollBarData {
+                                min: 0.0,
+                                max: 2.0,
+                                value: config.controls.mouse_acceleration,
+                                step: 0.1,
+                                row: 5,
+                                column: 1,
+                                margin,
+                                show_value: true,
+                                orientation: Orientation::Horizontal,
+                                font: font.clone(),
+                            },
+                        );
+                        mouse_acceleration
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.gamepad_dead_zone"),
+                        font.clone(),
+                        6,
+                        ctx,
+                    ))
+                    .with_child({
+                        gamepad_dead_zone = create_scroll_bar(
+                            ctx,
+                            ScrollBarData {
+                                min: 0.0,
+                                max: 0.5,
+                                value: config.controls.gamepad.dead_zone,
+                                step: 0.01,
+                                row: 6,
+                                column: 1,
+                                margin,
+                                show_value: true,
+                                orientation: Orientation::Horizontal,
+                                font: font.clone(),
+                            },
+                        );
+                        gamepad_dead_zone
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.gamepad_response_curve"),
+                        font.clone(),
+                        7,
+                        ctx,
+                    ))
+                    .with_child({
+                        gamepad_response_curve = DropdownListBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(7)
+                                .on_column(1)
+                                .with_margin(margin),
+                        )
+                        .with_items(
+                            ResponseCurve::ALL
+                                .iter()
+                                .map(|curve| {
+                                    make_video_mode_item_raw(
+                                        &tr(curve.localization_key()),
+                                        font.clone(),
+                                        ctx,
+                                    )
+                                })
+                                .collect::<Vec<_>>()
+                                .to_base(),
+                        )
+                        .with_close_on_selection(true)
+                        .with_selected(
+                            ResponseCurve::ALL
+                                .iter()
+                                .position(|curve| *curve == config.controls.gamepad.response_curve)
+                                .unwrap_or_default(),
+                        )
+                        .build(ctx);
+                        gamepad_response_curve
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.gamepad_look_sensitivity"),
+                        font.clone(),
+                        8,
+                        ctx,
+                    ))
+                    .with_child({
+                        gamepad_look_sensitivity = create_scroll_bar(
+                            ctx,
+                            ScrollBarData {
+                                min: 0.1,
+                                max: 3.0,
+                                value: config.controls.gamepad.look_sensitivity,
+                                step: 0.1,
+                                row: 8,
+                                column: 1,
+                                margin,
+                                show_value: true,
+                                orientation: Orientation::Horizontal,
+                                font: font.clone(),
+                            },
+                        );
+                        gamepad_look_sensitivity
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.gamepad_aim_look_sensitivity"),
+                        font.clone(),
+                        9,
+                        ctx,
+                    ))
+                    .with_child({
+                        gamepad_aim_look_sensitivity = create_scroll_bar(
+                            ctx,
+                            ScrollBarData {
+                                min: 0.1,
+                                max: 3.0,
+                                value: config.controls.gamepad.aim_look_sensitivity,
+                                step: 0.1,
+                                row: 9,
+                                column: 1,
+                                margin,
+                                show_value: true,
+                                orientation: Orientation::Horizontal,
+                                font: font.clone(),
+                            },
+                        );
+                        gamepad_aim_look_sensitivity
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.aim_assist"),
+                        font.clone(),
+                        10,
+                        ctx,
+                    ))
+                    .with_child({
+                        aim_assist =
+                            create_check_box(ctx, 10, 1, config.controls.gamepad.aim_assist);
+                        aim_assist
+                    })
+                    .with_child(make_text_mark(
+                        &tr("options.aim_assist_strength"),
+                        font.clone(),
+                        11,
+                        ctx,
+                    ))
+                    .with_child({
+                        aim_assist_strength = create_scroll_bar(
+                            ctx,
+                            ScrollBarData {
+                                min: 0.0,
+                                max: 1.0,
+                                value: config.controls.gamepad.aim_assist_strength,
+                                step: 0.05,
+                                row: 11,
+                                column: 1,
+                                margin,
+                                show_value: true,
+                                orientation: Orientation::Horizontal,
+                                font: font.clone(),
+                            },
+                        );
+                        aim_assist_strength
+                    })
                     .with_child({
                         reset_control_scheme = ButtonBuilder::new(
                             WidgetBuilder::new()
-                                .on_row(2 + config.controls.buttons().len())
+                                .on_row(CONTROLS_HEADER_ROWS + Action::ALL.len())
                                 .with_margin(margin),
                         )
-                        .with_text("Reset")
+                        .with_text(&tr("options.reset"))
                         .build(ctx);
                         reset_control_scheme
                     })
@@ -576,41 +1062,114 @@ impl OptionsMenu {
             )
             .add_column(Column::strict(250.0))
             .add_column(Column::stretch())
-            .add_row(common_row)
-            .add_row(common_row)
-            .add_rows(
-                (0..config.controls.buttons().len())
-                    .map(|_| common_row)
-                    .collect(),
-            )
+            .add_column(Column::stretch())
+            .add_column(Column::strict(70.0))
+            .add_rows((0..CONTROLS_HEADER_ROWS).map(|_| common_row).collect())
+            .add_rows(Action::ALL.iter().map(|_| common_row).collect())
             .add_row(common_row)
             .build(ctx)
         };
 
+        // Shown above the bindings when a new binding is already used by other actions.
+        let conflict_text =
+            TextBuilder::new(WidgetBuilder::new().with_width(250.0).with_margin(margin))
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_wrap(WrapMode::Word)
+                .with_font(font.clone())
+                .with_font_size(16.0.into())
+                .build(ctx);
+        let make_conflict_button = |text: &str, ctx: &mut BuildContext| {
+            ButtonBuilder::new(WidgetBuilder::new().with_width(75.0).with_margin(margin))
+                .with_text_and_font_size(text, font.clone(), 16.0.into())
+                .build(ctx)
+        };
+        let conflict_swap = make_conflict_button(&tr("options.swap"), ctx);
+        let conflict_clear = make_conflict_button(&tr("options.clear"), ctx);
+        let conflict_cancel = make_conflict_button(&tr("options.cancel"), ctx);
+        let conflict = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_visibility(false)
+                .with_child(conflict_text)
+                .with_child(conflict_swap)
+                .with_child(conflict_clear)
+                .with_child(conflict_cancel),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
         let controls_tab = TabDefinition {
             uuid: uuid!("7c751103-cf66-4c78-8b05-3042c7a20c51"),
-            header: make_tab_header("Controls", font.clone(), ctx).to_base(),
+            header: make_tab_header(&tr("options.controls"), font.clone(), ctx).to_base(),
             can_be_closed: false,
             user_data: None,
             content: {
-                ScrollViewerBuilder::new(WidgetBuilder::new())
+                let scroll_viewer = ScrollViewerBuilder::new(WidgetBuilder::new().on_row(1))
                     .with_content(controls_content)
+                    .build(ctx);
+
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(conflict)
+                        .with_child(scroll_viewer),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
+                .build(ctx)
+                .to_base()
+            },
+        };
+
+        let mut debug_layers = Vec::new();
+        let debug_content = {
+            let mut children = Vec::<Handle<UiNode>>::new();
+
+            for (row, layer) in DebugLayer::ALL.into_iter().enumerate() {
+                children.push(
+                    make_text_mark(&tr(&layer.localization_key()), font.clone(), row, ctx)
+                        .to_base(),
+                );
+
+                let check_box =
+                    create_check_box(ctx, row, 1, config.debug_layers.is_enabled(layer));
+                children.push(check_box.to_base());
+                debug_layers.push(check_box);
+            }
+
+            GridBuilder::new(WidgetBuilder::new().with_children(children))
+                .add_column(Column::strict(250.0))
+                .add_column(Column::stretch())
+                .add_rows(DebugLayer::ALL.iter().map(|_| common_row).collect())
+                .build(ctx)
+        };
+
+        let debug_tab = TabDefinition {
+            uuid: uuid!("b0a4d5c2-6e8f-4a3b-9c71-2f5e8d9a1c34"),
+            header: make_tab_header(&tr("options.debug"), font.clone(), ctx).to_base(),
+            can_be_closed: false,
+            user_data: None,
+            content: {
+                ScrollViewerBuilder::new(WidgetBuilder::new())
+                    .with_content(debug_content)
                     .build(ctx)
                     .to_base()
             },
         };
 
         let tab_control = TabControlBuilder::new(WidgetBuilder::new())
+            .with_tab(general_tab)
             .with_tab(graphics_tab)
             .with_tab(sound_tab)
             .with_tab(controls_tab)
+            .with_tab(debug_tab)
             .build(ctx);
 
-        let window = WindowBuilder::new(WidgetBuilder::new().with_height(650.0).with_width(500.0))
+        let window = WindowBuilder::new(WidgetBuilder::new().with_height(650.0).with_width(600.0))
             .can_maximize(false)
             .can_minimize(false)
             .with_remove_on_close(true)
-            .with_title(WindowTitle::text("Options"))
+            .with_title(WindowTitle::text(tr("options.title")))
             .open(false)
             .with_content(tab_control)
             .build(ctx);
@@ -638,9 +1197,29 @@ impl OptionsMenu {
             spot_shadow_distance,
             control_scheme_buttons,
             active_control_button: None,
+            held_modifiers: Default::default(),
+            pending_modifier: None,
             mouse_sens,
+            mouse_aim_sens,
+            mouse_x_inverse,
             mouse_y_inverse,
+            mouse_smoothing,
+            mouse_acceleration,
+            gamepad_dead_zone,
+            gamepad_response_curve,
+            gamepad_look_sensitivity,
+            gamepad_aim_look_sensitivity,
+            aim_assist,
+            aim_assist_strength,
+            activation_modes,
             reset_control_scheme,
+            reset_action_buttons,
+            conflict,
+            conflict_text,
+            conflict_swap,
+            conflict_clear,
+            conflict_cancel,
+            pending_rebind: None,
             use_hrtf,
             reset_audio_settings,
             point_shadows_quality,
@@ -649,6 +1228,12 @@ impl OptionsMenu {
             ssao,
             spot_shadows_quality,
             show_debug_info,
+            debug_layers,
+            language,
+            languages: languages.into_iter().map(|(code, _)| code).collect(),
+            tab_control,
+            active_tab: 0,
+            navigation: Default::default(),
             font,
         }
     }
@@ -679,28 +1264,69 @@ impl OptionsMenu {
             sync_scroll_bar(self.spot_shadow_distance, settings.spot_shadows_distance);
         }
 
+        sync_check_box(self.mouse_x_inverse, config.controls.mouse_x_inverse);
         sync_check_box(self.mouse_y_inverse, config.controls.mouse_y_inverse);
+        sync_check_box(self.aim_assist, config.controls.gamepad.aim_assist);
         sync_check_box(self.use_hrtf, config.sound.use_hrtf);
         sync_check_box(self.show_debug_info, config.show_debug_info);
+        for (check_box, layer) in self.debug_layers.iter().zip(DebugLayer::ALL) {
+            sync_check_box(*check_box, config.debug_layers.is_enabled(layer));
+        }
 
         sync_scroll_bar(self.mouse_sens, config.controls.mouse_sens);
-        sync_scroll_bar(self.sound_volume, config.sound.master_volume);
-        sync_scroll_bar(self.music_volume, config.sound.music_volume);
-
-        for (btn, def) in self
-            .control_scheme_buttons
+        sync_scroll_bar(self.mouse_aim_sens, config.controls.mouse_aim_sens);
+        sync_scroll_bar(self.mouse_smoothing, config.controls.mouse_smoothing);
+        sync_scroll_bar(self.mouse_acceleration, config.controls.mouse_acceleration);
+        sync_scroll_bar(self.gamepad_dead_zone, config.controls.gamepad.dead_zone);
+        sync_scroll_bar(
+            self.gamepad_look_sensitivity,
+            config.controls.gamepad.look_sensitivity,
+        );
+        sync_scroll_bar(
+            self.gamepad_aim_look_sensitivity,
+            config.controls.gamepad.aim_look_sensitivity,
+        );
+        sync_scroll_bar(
+            self.aim_assist_strength,
+            config.controls.gamepad.aim_assist_strength,
+        );
+        for (dropdown_list, action) in self
+            .activation_modes
             .iter()
-            .zip(config.controls.buttons().iter())
+            .zip(Action::HAS_ACTIVATION_MODE)
         {
+            let mode = config.controls.activation_mode(action);
             ui.send(
-                *ui[*btn].content,
-                TextMessage::Text(def.button.name().to_owned()),
+                *dropdown_list,
+                DropdownListMessage::Selection(ActivationMode::ALL.iter().position(|m| *m == mode)),
             );
         }
+        ui.send(
+            self.gamepad_response_curve,
+            DropdownListMessage::Selection(
+                ResponseCurve::ALL
+                    .iter()
+                    .position(|curve| *curve == config.controls.gamepad.response_curve),
+            ),
+        );
+        sync_scroll_bar(self.sound_volume, config.sound.master_volume);
+        sync_scroll_bar(self.music_volume, config.sound.music_volume);
+
+        self.sync_control_scheme_buttons(ui, config);
 
         Ok(())
     }
 
+    fn sync_control_scheme_buttons(&self, ui: &UserInterface, config: &Config) {
+        for (index, button) in self.control_scheme_buttons.iter().enumerate() {
+            let (action, slot) = binding_slot(index);
+            ui.send(
+                *ui[*button].content,
+                TextMessage::Text(binding_text(&config.controls, action, slot)),
+            );
+        }
+    }
+
     fn video_mode_list(graphics_context: &InitializedGraphicsContext) -> Vec<VideoModeHandle> {
         if let Some(monitor) = graphics_context.window.current_monitor() {
             monitor
@@ -721,55 +1347,251 @@ impl OptionsMenu {
         config: &mut Config,
     ) -> GameResult {
         if let Event::WindowEvent { event, .. } = event {
-            let mut control_button = None;
+            let mut binding = None;
+            let mut clear = false;
 
             match event {
                 WindowEvent::MouseWheel {
                     delta: MouseScrollDelta::LineDelta(_, y),
                     ..
                 } if *y != 0.0 => {
-                    control_button = if *y >= 0.0 {
-                        Some(ControlButton::WheelUp)
+                    let button = if *y >= 0.0 {
+                        ControlButton::WheelUp
                     } else {
-                        Some(ControlButton::WheelDown)
+                        ControlButton::WheelDown
                     };
+                    binding = Some(Binding::new(button).with_modifiers(self.held_modifiers));
                 }
                 WindowEvent::KeyboardInput { event: input, .. } => {
                     if let PhysicalKey::Code(key) = input.physical_key {
-                        control_button = Some(ControlButton::Key(key));
+                        let button = ControlButton::Key(key);
+                        let modifier = button.modifier();
+                        let pressed = input.state == ElementState::Pressed;
+                        if !modifier.is_empty() {
+                            if pressed {
+                                self.held_modifiers = self.held_modifiers.union(modifier);
+                                self.pending_modifier = Some(button);
+                            } else {
+                                self.held_modifiers = self.held_modifiers.difference(modifier);
+                                if self.pending_modifier.take() == Some(button) {
+                                    binding = Some(Binding::new(button));
+                                }
+                            }
+                        } else if pressed && !input.repeat {
+                            self.pending_modifier = None;
+                            if key == KeyCode::Delete {
+                                clear = true;
+                            } else {
+                                binding =
+                                    Some(Binding::new(button).with_modifiers(self.held_modifiers));
+                            }
+                        }
                     }
                 }
-                WindowEvent::MouseInput { button, .. } => {
+                WindowEvent::MouseInput {
+                    button,
+                    state: ElementState::Pressed,
+                    ..
+                } => {
+                    // Same numbering as in `ControlButton::name`.
                     let index = match button {
-                        MouseButton::Left => 1,
-                        MouseButton::Right => 2,
-                        MouseButton::Middle => 3,
-                        MouseButton::Back => 4,
-                        MouseButton::Forward => 5,
+                        MouseButton::Left => 0,
+                        MouseButton::Right => 1,
+                        MouseButton::Middle => 2,
+                        MouseButton::Back => 3,
+                        MouseButton::Forward => 4,
                         MouseButton::Other(i) => *i,
                     };
 
-                    control_button = Some(ControlButton::Mouse(index));
+                    self.pending_modifier = None;
+                    binding = Some(
+                        Binding::new(ControlButton::Mouse(index))
+                            .with_modifiers(self.held_modifiers),
+                    );
                 }
                 _ => {}
             }
 
-            if let Some(control_button) = control_button {
-                if let Some(active_control_button) = self.active_control_button {
-                    let ui = ctx.user_interfaces.try_get(self.ui)?;
+            if binding.is_some() || clear {
+                let ui = ctx.user_interfaces.try_get(self.ui)?;
+                self.bind(ui, config, binding);
+            }
+        }
 
-                    ui.send(
-                        *ui[self.control_scheme_buttons[active_control_button]].content,
-                        TextMessage::Text(control_button.name().to_owned()),
-                    );
+        Ok(())
+    }
 
-                    config.controls.buttons_mut()[active_control_button].button = control_button;
+    /// Applies the input received by the control scheme button that waits for it. Reserved
+    /// buttons cancel the rebinding, bindings used by other actions must be confirmed.
+    fn bind(&mut self, ui: &UserInterface, config: &mut Config, binding: Option<Binding>) {
+        let Some(active_control_button) = self.active_control_button.take() else {
+            return;
+        };
+        let (action, slot) = binding_slot(active_control_button);
 
-                    self.active_control_button = None;
+        match binding {
+            Some(binding) if binding.button.is_reserved() => (),
+            Some(binding) => {
+                let conflicts = config.controls.conflicts(action, binding);
+                if conflicts.is_empty() {
+                    config.controls.set_binding(action, slot, Some(binding));
+                } else {
+                    self.ask_conflict_resolution(ui, active_control_button, binding, &conflicts);
                 }
             }
+            None => config.controls.set_binding(action, slot, None),
         }
 
+        // Removal of a binding shifts the next ones, so every button is refreshed.
+        self.sync_control_scheme_buttons(ui, config);
+    }
+
+    fn ask_conflict_resolution(
+        &mut self,
+        ui: &UserInterface,
+        button_index: usize,
+        binding: Binding,
+        conflicts: &[(Action, usize)],
+    ) {
+        let mut actions = conflicts
+            .iter()
+            .map(|(action, _)| tr(action.description_key()))
+            .collect::<Vec<_>>();
+        actions.dedup();
+        let text = tr_args(
+            "options.binding_conflict",
+            &[
+                ("binding", binding.name().as_str()),
+                ("actions", actions.join(", ").as_str()),
+            ],
+        );
+        self.pending_rebind = Some((button_index, binding));
+
+        ui.send(self.conflict_text, TextMessage::Text(text));
+        ui.send(self.conflict, WidgetMessage::Visibility(true));
+    }
+
+    fn close_conflict_resolution(&mut self, ui: &UserInterface) -> Option<(usize, Binding)> {
+        ui.send(self.conflict, WidgetMessage::Visibility(false));
+        self.pending_rebind.take()
+    }
+
+    /// Widgets of the active tab in the order they are navigated with a gamepad.
+    fn navigation_items(&self) -> Vec<Handle<UiNode>> {
+        match self.active_tab {
+            0 => vec![self.language.to_base()],
+            1 => vec![
+                self.video_mode.to_base(),
+                self.spot_shadows.to_base(),
+                self.soft_spot_shadows.to_base(),
+                self.spot_shadow_distance.to_base(),
+                self.point_shadows.to_base(),
+                self.soft_point_shadows.to_base(),
+                self.point_shadow_distance.to_base(),
+                self.use_light_scatter.to_base(),
+                self.fxaa.to_base(),
+                self.ssao.to_base(),
+                self.point_shadows_quality.to_base(),
+                self.spot_shadows_quality.to_base(),
+                self.show_debug_info.to_base(),
+            ],
+            2 => vec![
+                self.sound_volume.to_base(),
+                self.music_volume.to_base(),
+                self.use_hrtf.to_base(),
+                self.reset_audio_settings.to_base(),
+            ],
+            // Nothing but the resolution of a conflict could be chosen until it is resolved.
+            3 if self.pending_rebind.is_some() => vec![
+                self.conflict_swap.to_base(),
+                self.conflict_clear.to_base(),
+                self.conflict_cancel.to_base(),
+            ],
+            3 => [
+                self.mouse_sens.to_base(),
+                self.mouse_aim_sens.to_base(),
+                self.mouse_x_inverse.to_base(),
+                self.mouse_y_inverse.to_base(),
+                self.mouse_smoothing.to_base(),
+                self.mouse_acceleration.to_base(),
+                self.gamepad_dead_zone.to_base(),
+                self.gamepad_response_curve.to_base(),
+                self.gamepad_look_sensitivity.to_base(),
+                self.gamepad_aim_look_sensitivity.to_base(),
+                self.aim_assist.to_base(),
+                self.aim_assist_strength.to_base(),
+            ]
+            .into_iter()
+            .chain(self.activation_modes.iter().map(|mode| mode.to_base()))
+            .chain(
+                self.control_scheme_buttons
+                    .iter()
+                    .map(|button| button.to_base()),
+            )
+            .chain(
+                self.reset_action_buttons
+                    .iter()
+                    .map(|button| button.to_base()),
+            )
+            .chain([self.reset_control_scheme.to_base()])
+            .collect(),
+            _ => self
+                .debug_layers
+                .iter()
+                .map(|check_box| check_box.to_base())
+                .collect(),
+        }
+    }
+
+    /// Handles the input of a gamepad. A button pressed while a binding button waits for the
+    /// input becomes the new binding, otherwise the menu is navigated with the command. `Back`
+    /// cancels the resolution of a binding conflict or closes the menu.
+    pub fn process_gamepad_input(
+        &mut self,
+        ctx: &mut PluginContext,
+        event: InputEvent,
+        command: Option<NavigationCommand>,
+        config: &mut Config,
+    ) -> GameResult {
+        let ui = ctx.user_interfaces.try_get(self.ui)?;
+
+        if self.active_control_button.is_some() {
+            if let InputEvent::Button {
+                button: button @ ControlButton::Gamepad(_),
+                pressed: true,
+            } = event
+            {
+                self.bind(ui, config, Some(Binding::new(button)));
+            }
+            return Ok(());
+        }
+
+        let Some(command) = command else {
+            return Ok(());
+        };
+        match command {
+            NavigationCommand::Back => {
+                if self.close_conflict_resolution(ui).is_none() {
+                    ui.send(self.window, WindowMessage::Close);
+                }
+            }
+            NavigationCommand::NextTab | NavigationCommand::PrevTab => {
+                self.active_tab = if command == NavigationCommand::NextTab {
+                    (self.active_tab + 1) % TAB_COUNT
+                } else {
+                    (self.active_tab + TAB_COUNT - 1) % TAB_COUNT
+                };
+                ui.send(
+                    self.tab_control,
+                    TabControlMessage::ActiveTab(Some(self.active_tab)),
+                );
+                self.navigation.reset();
+            }
+            _ => {
+                let items = self.navigation_items();
+                self.navigation.process(command, &items, ui);
+            }
+        }
         Ok(())
     }
 
@@ -800,6 +1622,20 @@ impl OptionsMenu {
                     graphics_settings.spot_shadows_distance = *new_value;
                 } else if message.destination() == self.mouse_sens {
                     config.controls.mouse_sens = *new_value;
+                } else if message.destination() == self.mouse_aim_sens {
+                    config.controls.mouse_aim_sens = *new_value;
+                } else if message.destination() == self.mouse_smoothing {
+                    config.controls.mouse_smoothing = *new_value;
+                } else if message.destination() == self.mouse_acceleration {
+                    config.controls.mouse_acceleration = *new_value;
+                } else if message.destination() == self.gamepad_dead_zone {
+                    config.controls.gamepad.dead_zone = *new_value;
+                } else if message.destination() == self.gamepad_look_sensitivity {
+                    config.controls.gamepad.look_sensitivity = *new_value;
+                } else if message.destination() == self.gamepad_aim_look_sensitivity {
+                    config.controls.gamepad.aim_look_sensitivity = *new_value;
+                } else if message.destination() == self.aim_assist_strength {
+                    config.controls.gamepad.aim_assist_strength = *new_value;
                 } else if message.destination() == self.music_volume {
                     sender.send(Message::SetMusicVolume(*new_value));
                 }
@@ -817,6 +1653,26 @@ impl OptionsMenu {
                         window.set_fullscreen(None);
                     }
                 }
+            } else if message.destination() == self.gamepad_response_curve {
+                if let Some(curve) = ResponseCurve::ALL.get(*index) {
+                    config.controls.gamepad.response_curve = *curve;
+                }
+            } else if let Some(position) = self
+                .activation_modes
+                .iter()
+                .position(|dropdown_list| message.destination() == *dropdown_list)
+            {
+                if let Some(mode) = ActivationMode::ALL.get(*index) {
+                    config
+                        .controls
+                        .set_activation_mode(Action::HAS_ACTIVATION_MODE[position], *mode);
+                }
+            } else if message.destination() == self.language {
+                if let Some(language) = self.languages.get(*index) {
+                    if *language != localization::language() {
+                        sender.send(Message::SetLanguage(language.clone()));
+                    }
+                }
             } else if message.destination() == self.spot_shadows_quality {
                 graphics_settings.spot_shadow_map_size = index_to_shadow_map_size(*index);
                 if *index > 0 {
@@ -842,8 +1698,12 @@ impl OptionsMenu {
                 graphics_settings.spot_soft_shadows = value;
             } else if message.destination() == self.soft_point_shadows {
                 graphics_settings.point_soft_shadows = value;
+            } else if message.destination() == self.mouse_x_inverse {
+                config.controls.mouse_x_inverse = value;
             } else if message.destination() == self.mouse_y_inverse {
                 config.controls.mouse_y_inverse = value;
+            } else if message.destination() == self.aim_assist {
+                config.controls.gamepad.aim_assist = value;
             } else if message.destination() == self.use_light_scatter {
                 graphics_settings.light_scatter_enabled = value;
             } else if message.destination() == self.fxaa {
@@ -854,25 +1714,68 @@ impl OptionsMenu {
                 sender.send(Message::SetUseHrtf(value));
             } else if message.destination() == self.show_debug_info {
                 config.show_debug_info = value;
+            } else if let Some(index) = self
+                .debug_layers
+                .iter()
+                .position(|check_box| message.destination() == *check_box)
+            {
+                config
+                    .debug_layers
+                    .set_enabled(DebugLayer::ALL[index], value);
             }
         } else if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.reset_control_scheme {
+                let ui = ctx.user_interfaces.try_get(self.ui)?;
+                self.close_conflict_resolution(ui);
                 config.controls.reset();
                 self.sync_to_model(ctx, config)?;
             } else if message.destination() == self.reset_audio_settings {
                 self.sync_to_model(ctx, config)?;
+            } else if message.destination() == self.conflict_swap
+                || message.destination() == self.conflict_clear
+            {
+                let resolution = if message.destination() == self.conflict_swap {
+                    ConflictResolution::Swap
+                } else {
+                    ConflictResolution::Clear
+                };
+                let ui = ctx.user_interfaces.try_get(self.ui)?;
+                if let Some((button_index, binding)) = self.close_conflict_resolution(ui) {
+                    let (action, slot) = binding_slot(button_index);
+                    config.controls.rebind(action, slot, binding, resolution);
+                    self.sync_control_scheme_buttons(ui, config);
+                }
+            } else if message.destination() == self.conflict_cancel {
+                let ui = ctx.user_interfaces.try_get(self.ui)?;
+                self.close_conflict_resolution(ui);
+            } else if let Some(index) = self
+                .reset_action_buttons
+                .iter()
+                .position(|button| message.destination() == *button)
+            {
+                config.controls.reset_action(Action::ALL[index]);
+                self.sync_to_model(ctx, config)?;
             }
 
             for (i, button) in self.control_scheme_buttons.iter().enumerate() {
                 if message.destination() == *button {
                     let ui = ctx.user_interfaces.try_get(self.ui)?;
+                    // Another button could still be waiting for the input or a conflict could
+                    // still be waiting for the resolution.
+                    ui.send(self.conflict, WidgetMessage::Visibility(false));
+                    self.pending_rebind = None;
+                    self.sync_control_scheme_buttons(ui, config);
                     ui.send(
                         *ui[*button].content,
-                        TextMessage::Text("[WAITING INPUT]".to_owned()),
+                        TextMessage::Text(tr("options.waiting_input")),
                     );
                     self.active_control_button = Some(i);
                 }
             }
+        } else if let Some(TabControlMessage::ActiveTab(Some(index))) =
+            message.data_from(self.tab_control)
+        {
+            self.active_tab = *index;
         } else if let Some(WindowMessage::Close) = message.data_for(self.window) {
             return Ok(None);
         }
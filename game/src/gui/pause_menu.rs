@@ -0,0 +1,280 @@
+//! Pause menu. It is shown over the level when the player presses Escape or when the window loses
+//! focus, the level is frozen while the menu is visible. Unlike the main menu, it has its own user
+//! interface and does not replace the level.
+
+use crate::{
+    config::Config,
+    demo::InputEvent,
+    gui::{
+        navigation::{Navigation, NavigationCommand},
+        options_menu::OptionsMenu,
+        save_load::{Mode, SaveLoadDialog},
+    },
+    localization::tr,
+    message::Message,
+    MessageSender,
+};
+use fyrox::{
+    core::{algebra::Vector2, color::Color, pool::Handle, visitor::prelude::*},
+    event::Event,
+    graph::SceneGraph,
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        button::{Button, ButtonBuilder, ButtonMessage},
+        font::FontResource,
+        grid::{Column, Grid, GridBuilder, Row},
+        message::UiMessage,
+        stack_panel::{StackPanel, StackPanelBuilder},
+        text::TextMessage,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::WindowMessage,
+        BuildContext, HorizontalAlignment, Thickness, UserInterface, VerticalAlignment,
+    },
+    plugin::{error::GameResult, PluginContext},
+};
+
+#[derive(Visit, Default, Debug)]
+pub struct PauseMenu {
+    ui: Handle<UserInterface>,
+    root: Handle<Grid>,
+    buttons: Handle<StackPanel>,
+    btn_resume: Handle<Button>,
+    btn_save_game: Handle<Button>,
+    btn_load_game: Handle<Button>,
+    btn_settings: Handle<Button>,
+    btn_quit_to_menu: Handle<Button>,
+    options_menu: Option<OptionsMenu>,
+    save_load_dialog: Option<SaveLoadDialog>,
+    #[visit(skip)]
+    navigation: Navigation,
+    font: FontResource,
+}
+
+fn make_button(font: &FontResource, ctx: &mut BuildContext) -> Handle<Button> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(300.0)
+            .with_height(64.0)
+            .with_margin(Thickness::top(5.0)),
+    )
+    .with_text_and_font_size("", font.clone(), 32.0.into())
+    .build(ctx)
+}
+
+impl PauseMenu {
+    pub fn new(ctx: &mut PluginContext, font: FontResource) -> Self {
+        let mut ui = UserInterface::new(Vector2::repeat(100.0));
+        let build_ctx = &mut ui.build_ctx();
+
+        let btn_resume = make_button(&font, build_ctx);
+        let btn_save_game = make_button(&font, build_ctx);
+        let btn_load_game = make_button(&font, build_ctx);
+        let btn_settings = make_button(&font, build_ctx);
+        let btn_quit_to_menu = make_button(&font, build_ctx);
+
+        let buttons = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_horizontal_alignment(HorizontalAlignment::Center)
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .with_child(btn_resume)
+                .with_child(btn_save_game)
+                .with_child(btn_load_game)
+                .with_child(btn_settings)
+                .with_child(btn_quit_to_menu),
+        )
+        .build(build_ctx);
+
+        // The root fills the screen, so the buttons could be centered on it.
+        let root = GridBuilder::new(
+            WidgetBuilder::new().with_visibility(false).with_child(
+                BorderBuilder::new(
+                    WidgetBuilder::new()
+                        .with_background(Brush::Solid(Color::from_rgba(0, 0, 0, 160)).into())
+                        .with_child(buttons),
+                )
+                .build(build_ctx),
+            ),
+        )
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .build(build_ctx);
+
+        let menu = Self {
+            ui: ctx.user_interfaces.add(ui),
+            root,
+            buttons,
+            btn_resume,
+            btn_save_game,
+            btn_load_game,
+            btn_settings,
+            btn_quit_to_menu,
+            options_menu: None,
+            save_load_dialog: None,
+            navigation: Default::default(),
+            font,
+        };
+        if let Ok(ui) = ctx.user_interfaces.try_get(menu.ui) {
+            menu.localize(ui);
+        }
+        menu
+    }
+
+    fn localize(&self, ui: &UserInterface) {
+        for (button, key) in [
+            (self.btn_resume, "pause_menu.resume"),
+            (self.btn_save_game, "menu.save_game"),
+            (self.btn_load_game, "menu.load_game"),
+            (self.btn_settings, "menu.settings"),
+            (self.btn_quit_to_menu, "pause_menu.quit_to_menu"),
+        ] {
+            if let Ok(button) = ui.try_get(button) {
+                ui.send(*button.content, TextMessage::Text(tr(key)));
+            }
+        }
+    }
+
+    pub fn on_language_changed(&mut self, ctx: &mut PluginContext, config: &Config) -> GameResult {
+        let ui = ctx.user_interfaces.try_get(self.ui)?;
+        self.localize(ui);
+
+        if let Some(options_menu) = self.options_menu.take() {
+            ui.send(options_menu.window, WindowMessage::Close);
+            self.options_menu = Some(OptionsMenu::new(self.ui, ctx, config));
+        }
+
+        Ok(())
+    }
+
+    pub fn resize(&self, ctx: &PluginContext, width: f32, height: f32) {
+        if let Ok(ui) = ctx.user_interfaces.try_get(self.ui) {
+            ui.send(self.root, WidgetMessage::Width(width));
+            ui.send(self.root, WidgetMessage::Height(height));
+        }
+    }
+
+    pub fn set_visible(&mut self, ctx: &mut PluginContext, visible: bool) -> GameResult {
+        let ui = ctx.user_interfaces.try_get(self.ui)?;
+
+        ui.send(self.root, WidgetMessage::Visibility(visible));
+        if !visible {
+            if let Some(options_menu) = self.options_menu.as_ref() {
+                ui.send(options_menu.window, WindowMessage::Close);
+            }
+            if let Some(save_load_dialog) = self.save_load_dialog.as_ref() {
+                ui.send(save_load_dialog.window, WindowMessage::Close);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_visible(&self, ctx: &PluginContext) -> bool {
+        ctx.user_interfaces
+            .try_get(self.ui)
+            .ok()
+            .and_then(|ui| ui.try_get(self.root).ok())
+            .is_some_and(|root| root.visibility())
+    }
+
+    pub fn process_input_event(
+        &mut self,
+        ctx: &mut PluginContext,
+        event: &Event<()>,
+        config: &mut Config,
+    ) -> GameResult {
+        if let Some(options_menu) = self.options_menu.as_mut() {
+            options_menu.process_input_event(ctx, event, config)?;
+        }
+        Ok(())
+    }
+
+    /// Navigates the menu or its opened screen with a gamepad, `Back` resumes the game.
+    pub fn process_gamepad_input(
+        &mut self,
+        ctx: &mut PluginContext,
+        event: InputEvent,
+        command: Option<NavigationCommand>,
+        config: &mut Config,
+    ) -> GameResult {
+        if let Some(options_menu) = self.options_menu.as_mut() {
+            return options_menu.process_gamepad_input(ctx, event, command, config);
+        }
+
+        let Some(command) = command else {
+            return Ok(());
+        };
+        let ui = ctx.user_interfaces.try_get(self.ui)?;
+        if let Some(save_load_dialog) = self.save_load_dialog.as_mut() {
+            save_load_dialog.process_navigation(command, ui);
+        } else if command == NavigationCommand::Back {
+            ui.send(self.root, WidgetMessage::Visibility(false));
+        } else {
+            let items = [
+                self.btn_resume.to_base(),
+                self.btn_save_game.to_base(),
+                self.btn_load_game.to_base(),
+                self.btn_settings.to_base(),
+                self.btn_quit_to_menu.to_base(),
+            ];
+            self.navigation.process(command, &items, ui);
+        }
+        Ok(())
+    }
+
+    pub fn update(&self, ctx: &mut PluginContext) -> GameResult {
+        let ui = ctx.user_interfaces.try_get(self.ui)?;
+
+        let no_opened_screens = self.options_menu.is_none() && self.save_load_dialog.is_none();
+        ui.send_sync(self.buttons, WidgetMessage::Visibility(no_opened_screens));
+
+        Ok(())
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        ctx: &mut PluginContext,
+        ui_handle: Handle<UserInterface>,
+        message: &UiMessage,
+        config: &mut Config,
+        sender: &MessageSender,
+    ) -> GameResult {
+        if self.ui != ui_handle {
+            return Ok(());
+        }
+
+        if let Some(options_menu) = self.options_menu.take() {
+            self.options_menu = options_menu.handle_ui_event(ctx, message, config, sender)?;
+        }
+
+        let ui = ctx.user_interfaces.try_get_mut(self.ui)?;
+
+        if let Some(save_load_dialog) = self.save_load_dialog.take() {
+            self.save_load_dialog = save_load_dialog.handle_ui_message(message, ui, sender);
+        }
+
+        if let Some(ButtonMessage::Click) = message.data_from(self.btn_resume) {
+            ui.send(self.root, WidgetMessage::Visibility(false));
+        } else if let Some(ButtonMessage::Click) = message.data_from(self.btn_save_game) {
+            self.save_load_dialog = Some(SaveLoadDialog::new(
+                Mode::Save,
+                self.font.clone(),
+                &mut ui.build_ctx(),
+            ));
+        } else if let Some(ButtonMessage::Click) = message.data_from(self.btn_load_game) {
+            self.save_load_dialog = Some(SaveLoadDialog::new(
+                Mode::Load,
+                self.font.clone(),
+                &mut ui.build_ctx(),
+            ));
+        } else if let Some(ButtonMessage::Click) = message.data_from(self.btn_settings) {
+            if self.options_menu.is_none() {
+                self.options_menu = Some(OptionsMenu::new(self.ui, ctx, config));
+            }
+        } else if let Some(ButtonMessage::Click) = message.data_from(self.btn_quit_to_menu) {
+            sender.send(Message::QuitToMainMenu);
+        }
+
+        Ok(())
+    }
+}
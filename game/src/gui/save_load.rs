@@ -1,15 +1,24 @@
-use crate::{message::Message, MessageSender};
+use crate::{
+    gui::navigation::{Navigation, NavigationCommand},
+    localization::{tr, tr_args},
+    message::Message,
+    saved_game::{self, SavedGame},
+    utils, MessageSender,
+};
 use chrono::{DateTime, Utc};
 use fyrox::core::pool::HandlesVecExtension;
 use fyrox::gui::button::Button;
 use fyrox::gui::decorator::Decorator;
 use fyrox::gui::list_view::ListView;
+use fyrox::gui::stack_panel::StackPanel;
+use fyrox::gui::text::Text;
 use fyrox::gui::text_box::TextBox;
 use fyrox::gui::window::{Window, WindowAlignment};
 use fyrox::{
-    core::{log::Log, pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    core::{color::Color, log::Log, pool::Handle, reflect::prelude::*, visitor::prelude::*},
     gui::{
         border::BorderBuilder,
+        brush::Brush,
         button::{ButtonBuilder, ButtonMessage},
         decorator::DecoratorBuilder,
         font::FontResource,
@@ -26,13 +35,10 @@ use fyrox::{
     },
 };
 use std::{
-    ffi::OsStr,
     path::{Path, PathBuf},
     time::SystemTime,
 };
 
-const SAVED_GAME_EXT: &str = "rgs";
-
 #[derive(Visit, Reflect, Clone, Default, Debug)]
 pub enum Mode {
     #[default]
@@ -40,17 +46,65 @@ pub enum Mode {
     Load,
 }
 
+#[derive(Visit, Reflect, Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Most recent saves first.
+    #[default]
+    Date,
+    Name,
+}
+
+impl SortOrder {
+    fn name(self) -> String {
+        tr(match self {
+            SortOrder::Date => "save_load.sort_by_date",
+            SortOrder::Name => "save_load.sort_by_name",
+        })
+    }
+
+    fn next(self) -> Self {
+        match self {
+            SortOrder::Date => SortOrder::Name,
+            SortOrder::Name => SortOrder::Date,
+        }
+    }
+}
+
+/// An action that waits for the user to confirm it.
+#[derive(Visit, Reflect, Clone, Default, Debug)]
+enum PendingAction {
+    #[default]
+    None,
+    Delete(PathBuf),
+    Overwrite(PathBuf),
+}
+
 #[derive(Default, Debug, Visit, Clone, Reflect)]
 pub struct SaveLoadDialog {
     pub window: Handle<Window>,
     confirm: Handle<Button>,
     cancel: Handle<Button>,
+    rename: Handle<Button>,
+    delete: Handle<Button>,
+    sort: Handle<Button>,
     name: Handle<TextBox>,
+    validation: Handle<Text>,
     saved_games: Handle<ListView>,
+    buttons: Handle<StackPanel>,
+    confirmation: Handle<StackPanel>,
+    confirmation_text: Handle<Text>,
+    confirmation_yes: Handle<Button>,
+    confirmation_no: Handle<Button>,
     saved_games_list: Vec<PathBuf>,
     mode: Mode,
+    sort_order: SortOrder,
     file_stem: String,
     selected_entry: Option<usize>,
+    pending_action: PendingAction,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    navigation: Navigation,
+    font: FontResource,
 }
 
 fn create_saved_game_entry(
@@ -58,16 +112,37 @@ fn create_saved_game_entry(
     font: FontResource,
     ctx: &mut BuildContext,
 ) -> Handle<Decorator> {
-    let text = format!(
-        "{} - {}",
-        path.file_stem().unwrap_or_default().to_string_lossy(),
-        DateTime::<Utc>::from(
-            path.metadata()
-                .and_then(|metadata| metadata.modified())
-                .unwrap_or_else(|_| SystemTime::now())
-        )
-        .format("%d/%m/%Y %H:%M")
-    );
+    let name = path.file_stem().unwrap_or_default().to_string_lossy();
+    // Only the header is read here, the payload could be quite large.
+    let text = match SavedGame::load_header(path) {
+        Ok(header) => {
+            let level = header
+                .level
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| tr("save_load.unknown_level"));
+            format!(
+                "{name} - {}\n{level}: {} | {} | {}",
+                DateTime::<Utc>::from_timestamp(header.timestamp as i64, 0)
+                    .unwrap_or_else(|| DateTime::<Utc>::from(SystemTime::now()))
+                    .format("%d/%m/%Y %H:%M"),
+                header.location,
+                utils::format_time(header.playtime),
+                if header.format_version == SavedGame::FORMAT_VERSION {
+                    format!("v{}", header.game_version)
+                } else {
+                    tr_args(
+                        "save_load.old_format",
+                        &[("version", header.game_version.as_str())],
+                    )
+                }
+            )
+        }
+        Err(err) => {
+            Log::err(err);
+            tr_args("save_load.corrupted", &[("name", &*name)])
+        }
+    };
 
     let text_handle = TextBuilder::new(WidgetBuilder::new())
         .with_vertical_text_alignment(VerticalAlignment::Center)
@@ -83,35 +158,65 @@ fn create_saved_game_entry(
     .build(ctx)
 }
 
-fn is_file_stem_valid(file_stem: &str) -> bool {
-    file_stem.chars().all(|c| c.is_alphanumeric() || c == '_')
+fn sorted_saved_games(sort_order: SortOrder) -> Vec<PathBuf> {
+    let mut saved_games = saved_game::saved_games();
+    match sort_order {
+        SortOrder::Date => {
+            saved_games.sort_by_cached_key(|path| {
+                std::cmp::Reverse(
+                    SavedGame::load_header(path)
+                        .map(|header| header.timestamp)
+                        .unwrap_or_default(),
+                )
+            });
+        }
+        SortOrder::Name => {
+            saved_games.sort_by_cached_key(|path| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().to_lowercase())
+                    .unwrap_or_default()
+            });
+        }
+    }
+    saved_games
+}
+
+fn make_button(
+    text: &str,
+    width: f32,
+    enabled: bool,
+    font: &FontResource,
+    ctx: &mut BuildContext,
+) -> Handle<Button> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(width)
+            .with_enabled(enabled)
+            .with_margin(Thickness::uniform(2.0)),
+    )
+    .with_text_and_font_size(text, font.clone(), 20.0.into())
+    .build(ctx)
 }
 
 impl SaveLoadDialog {
-    const SAVED_GAMES_FOLDER: &'static str = "./saved_games";
-
     pub fn new(mode: Mode, font: FontResource, ctx: &mut BuildContext) -> Self {
-        let file_stem = "unnamed_save";
-
-        let mut items = Vec::new();
-        let mut saved_games_list = Vec::new();
-        if let Ok(dir_iterator) = std::fs::read_dir(Self::SAVED_GAMES_FOLDER) {
-            for entry in dir_iterator.flatten() {
-                let path = entry.path();
-                if let Some(extension) = path.extension() {
-                    if extension == OsStr::new(SAVED_GAME_EXT) {
-                        items.push(create_saved_game_entry(&path, font.clone(), ctx));
-                        saved_games_list.push(path);
-                    }
-                }
-            }
-        }
+        let file_stem = match mode {
+            Mode::Save => "unnamed_save",
+            Mode::Load => "",
+        };
+
+        let sort_order = SortOrder::default();
+        let saved_games_list = sorted_saved_games(sort_order);
+        let items = saved_games_list
+            .iter()
+            .map(|path| create_saved_game_entry(path, font.clone(), ctx))
+            .collect::<Vec<_>>();
 
+        // In the load mode the name is used only to rename saved games.
         let name = TextBoxBuilder::new(
             WidgetBuilder::new()
                 .on_row(0)
-                .with_margin(Thickness::uniform(1.0))
-                .with_visibility(matches!(mode, Mode::Save)),
+                .with_margin(Thickness::uniform(1.0)),
         )
         .with_text(file_stem)
         .with_font(font.clone())
@@ -119,26 +224,49 @@ impl SaveLoadDialog {
         .with_text_commit_mode(TextCommitMode::Immediate)
         .build(ctx);
 
+        let validation = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_margin(Thickness::uniform(1.0))
+                .with_foreground(Brush::Solid(Color::opaque(255, 80, 80)).into()),
+        )
+        .with_font(font.clone())
+        .with_font_size(14.0.into())
+        .build(ctx);
+
+        let sort = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .on_row(2)
+                .with_width(120.0)
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_text_and_font_size(&sort_order.name(), font.clone(), 16.0.into())
+        .build(ctx);
+
         let saved_games = ListViewBuilder::new(
             WidgetBuilder::new()
                 .with_margin(Thickness::uniform(1.0))
-                .on_row(1),
+                .on_row(3),
         )
         .with_items(items.to_base())
         .build(ctx);
 
         let (title_text, confirm_text) = match mode {
-            Mode::Save => ("Save Game", "Save"),
-            Mode::Load => ("Load Game", "Load"),
+            Mode::Save => ("save_load.save_title", "save_load.save"),
+            Mode::Load => ("save_load.load_title", "save_load.load"),
         };
 
+        let rename = make_button(&tr("save_load.rename"), 100.0, false, &font, ctx);
+        let delete = make_button(&tr("save_load.delete"), 100.0, false, &font, ctx);
+
         let confirm = ButtonBuilder::new(
             WidgetBuilder::new()
                 .with_width(120.0)
                 .with_margin(Thickness::uniform(2.0))
                 .with_enabled(matches!(mode, Mode::Save)),
         )
-        .with_text_and_font_size(confirm_text, font.clone(), 24.0.into())
+        .with_text_and_font_size(&tr(confirm_text), font.clone(), 24.0.into())
         .build(ctx);
 
         let cancel = ButtonBuilder::new(
@@ -146,36 +274,67 @@ impl SaveLoadDialog {
                 .with_width(120.0)
                 .with_margin(Thickness::uniform(2.0)),
         )
-        .with_text_and_font_size("Cancel", font.clone(), 24.0.into())
+        .with_text_and_font_size(&tr("save_load.cancel"), font.clone(), 24.0.into())
+        .build(ctx);
+
+        let buttons = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .on_row(4)
+                .with_child(rename)
+                .with_child(delete)
+                .with_child(confirm)
+                .with_child(cancel),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let confirmation_text = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_width(260.0)
+                .with_margin(Thickness::uniform(2.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .with_font(font.clone())
+        .with_font_size(16.0.into())
+        .build(ctx);
+        let confirmation_yes = make_button(&tr("save_load.yes"), 80.0, true, &font, ctx);
+        let confirmation_no = make_button(&tr("save_load.no"), 80.0, true, &font, ctx);
+
+        let confirmation = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .on_row(4)
+                .with_visibility(false)
+                .with_child(confirmation_text)
+                .with_child(confirmation_yes)
+                .with_child(confirmation_no),
+        )
+        .with_orientation(Orientation::Horizontal)
         .build(ctx);
 
         let content = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child(name)
+                .with_child(validation)
+                .with_child(sort)
                 .with_child(saved_games)
-                .with_child(
-                    StackPanelBuilder::new(
-                        WidgetBuilder::new()
-                            .with_horizontal_alignment(HorizontalAlignment::Right)
-                            .on_row(2)
-                            .with_child(confirm)
-                            .with_child(cancel),
-                    )
-                    .with_orientation(Orientation::Horizontal)
-                    .build(ctx),
-                ),
+                .with_child(buttons)
+                .with_child(confirmation),
         )
         .add_row(Row::auto())
+        .add_row(Row::auto())
+        .add_row(Row::auto())
         .add_row(Row::stretch())
         .add_row(Row::strict(35.0))
         .add_column(Column::stretch())
         .build(ctx);
 
-        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(500.0))
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(550.0))
             .can_minimize(false)
             .can_maximize(false)
             .open(false)
-            .with_title(WindowTitle::text(title_text))
+            .with_title(WindowTitle::text(tr(title_text)))
             .with_content(content)
             .build(ctx);
 
@@ -192,15 +351,125 @@ impl SaveLoadDialog {
             window,
             confirm,
             cancel,
+            rename,
+            delete,
+            sort,
             name,
+            validation,
             saved_games,
+            buttons,
+            confirmation,
+            confirmation_text,
+            confirmation_yes,
+            confirmation_no,
             saved_games_list,
             mode,
+            sort_order,
             file_stem: file_stem.to_string(),
             selected_entry: None,
+            pending_action: PendingAction::None,
+            font,
         }
     }
 
+    fn selected_path(&self) -> Option<&PathBuf> {
+        self.selected_entry
+            .and_then(|index| self.saved_games_list.get(index))
+    }
+
+    fn set_validation_message(&self, ui: &UserInterface, message: impl Into<String>) {
+        ui.send(self.validation, TextMessage::Text(message.into()));
+    }
+
+    /// Enables or disables the buttons according to the current selection and the name.
+    fn sync_buttons(&self, ui: &UserInterface) {
+        let is_name_valid = saved_game::validate_name(&self.file_stem).is_ok();
+        let has_selection = self.selected_path().is_some();
+
+        let can_confirm = match self.mode {
+            Mode::Save => is_name_valid,
+            Mode::Load => has_selection,
+        };
+        ui.send(self.confirm, WidgetMessage::Enabled(can_confirm));
+        ui.send(
+            self.rename,
+            WidgetMessage::Enabled(has_selection && is_name_valid),
+        );
+        ui.send(self.delete, WidgetMessage::Enabled(has_selection));
+    }
+
+    /// Re-reads the saved games folder and rebuilds the list.
+    fn refresh(&mut self, ui: &mut UserInterface) {
+        self.saved_games_list = sorted_saved_games(self.sort_order);
+        self.selected_entry = None;
+
+        let items = self
+            .saved_games_list
+            .iter()
+            .map(|path| create_saved_game_entry(path, self.font.clone(), &mut ui.build_ctx()))
+            .collect::<Vec<_>>();
+        ui.send(self.saved_games, ListViewMessage::Items(items.to_base()));
+
+        self.sync_buttons(ui);
+    }
+
+    fn ask_confirmation(&mut self, action: PendingAction, ui: &UserInterface) {
+        let file_stem = |path: &Path| {
+            path.file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        };
+        let text = match &action {
+            PendingAction::None => return,
+            PendingAction::Delete(path) => tr_args(
+                "save_load.confirm_delete",
+                &[("name", file_stem(path).as_str())],
+            ),
+            PendingAction::Overwrite(path) => tr_args(
+                "save_load.confirm_overwrite",
+                &[("name", file_stem(path).as_str())],
+            ),
+        };
+        self.pending_action = action;
+
+        ui.send(self.confirmation_text, TextMessage::Text(text));
+        ui.send(self.confirmation, WidgetMessage::Visibility(true));
+        ui.send(self.buttons, WidgetMessage::Visibility(false));
+    }
+
+    fn close_confirmation(&mut self, ui: &UserInterface) -> PendingAction {
+        ui.send(self.confirmation, WidgetMessage::Visibility(false));
+        ui.send(self.buttons, WidgetMessage::Visibility(true));
+        std::mem::take(&mut self.pending_action)
+    }
+
+    /// Navigates the dialog with a gamepad, `Back` cancels the confirmation or closes the dialog.
+    /// The name could be typed only with a keyboard, the names of the selected saved games are
+    /// used otherwise.
+    pub fn process_navigation(&mut self, command: NavigationCommand, ui: &UserInterface) {
+        if command == NavigationCommand::Back {
+            if matches!(self.pending_action, PendingAction::None) {
+                ui.send(self.window, WindowMessage::Close);
+            } else {
+                self.close_confirmation(ui);
+            }
+            return;
+        }
+
+        let items = [
+            self.saved_games.to_base(),
+            self.sort.to_base(),
+            self.rename.to_base(),
+            self.delete.to_base(),
+            self.confirm.to_base(),
+            self.cancel.to_base(),
+            self.confirmation_yes.to_base(),
+            self.confirmation_no.to_base(),
+        ];
+        self.navigation.process(command, &items, ui);
+    }
+
     pub fn handle_ui_message(
         mut self,
         message: &UiMessage,
@@ -216,31 +485,66 @@ impl SaveLoadDialog {
             let mut close = false;
             if message.destination() == self.confirm {
                 match self.mode {
-                    Mode::Save => {
-                        let folder = Path::new(Self::SAVED_GAMES_FOLDER);
-                        let path = folder.join(self.file_stem.clone() + "." + SAVED_GAME_EXT);
-
-                        if !folder.exists() {
-                            Log::verify(std::fs::create_dir_all(folder));
+                    Mode::Save => match saved_game::validate_name(&self.file_stem) {
+                        Ok(_) => {
+                            let path = saved_game::saved_game_path(&self.file_stem);
+                            if path.exists() {
+                                self.ask_confirmation(PendingAction::Overwrite(path), ui);
+                            } else {
+                                sender.send(Message::SaveGame(path));
+                                close = true;
+                            }
                         }
-
-                        sender.send(Message::SaveGame(path))
-                    }
+                        Err(err) => self.set_validation_message(ui, err),
+                    },
                     Mode::Load => {
-                        if let Some(path) = self
-                            .selected_entry
-                            .and_then(|index| self.saved_games_list.get(index).cloned())
-                        {
+                        if let Some(path) = self.selected_path().cloned() {
                             if path.exists() {
                                 sender.send(Message::LoadGame(path))
                             }
                         }
+                        close = true;
                     }
                 }
-                close = true;
             } else if message.destination() == self.cancel {
-                ui.send(self.window, WindowMessage::Close);
                 close = true;
+            } else if message.destination() == self.rename {
+                if let Some(path) = self.selected_path().cloned() {
+                    match saved_game::rename(&path, &self.file_stem) {
+                        Ok(_) => {
+                            self.set_validation_message(ui, "");
+                            self.refresh(ui);
+                        }
+                        Err(err) => self.set_validation_message(ui, err),
+                    }
+                }
+            } else if message.destination() == self.delete {
+                if let Some(path) = self.selected_path().cloned() {
+                    self.ask_confirmation(PendingAction::Delete(path), ui);
+                }
+            } else if message.destination() == self.sort {
+                self.sort_order = self.sort_order.next();
+                ui.send(
+                    *ui[self.sort].content,
+                    TextMessage::Text(self.sort_order.name()),
+                );
+                self.refresh(ui);
+            } else if message.destination() == self.confirmation_yes {
+                match self.close_confirmation(ui) {
+                    PendingAction::None => (),
+                    PendingAction::Delete(path) => {
+                        if let Err(err) = saved_game::delete(&path) {
+                            self.set_validation_message(ui, err);
+                        }
+                        self.refresh(ui);
+                    }
+                    PendingAction::Overwrite(path) => {
+                        sender.send(Message::SaveGame(path));
+                        close = true;
+                    }
+                }
+            } else if message.destination() == self.confirmation_no {
+                self.close_confirmation(ui);
             }
 
             if close {
@@ -252,12 +556,11 @@ impl SaveLoadDialog {
             {
                 self.file_stem.clone_from(text);
 
-                if matches!(self.mode, Mode::Save) {
-                    ui.send(
-                        self.confirm,
-                        WidgetMessage::Enabled(is_file_stem_valid(&self.file_stem)),
-                    );
-                }
+                let validation = saved_game::validate_name(&self.file_stem)
+                    .err()
+                    .unwrap_or_default();
+                self.set_validation_message(ui, validation);
+                self.sync_buttons(ui);
             }
         } else if let Some(ListViewMessage::Selection(index)) = message.data() {
             if message.destination() == self.saved_games
@@ -266,21 +569,15 @@ impl SaveLoadDialog {
                 self.selected_entry = index.first().cloned();
 
                 if let Some(file_stem) = self
-                    .selected_entry
-                    .and_then(|index| self.saved_games_list.get(index))
+                    .selected_path()
                     .and_then(|path| path.file_stem())
+                    .map(|file_stem| file_stem.to_string_lossy().to_string())
                 {
-                    self.file_stem = file_stem.to_string_lossy().to_string();
-
-                    ui.send(self.name, TextMessage::Text(self.file_stem.clone()));
+                    ui.send(self.name, TextMessage::Text(file_stem.clone()));
+                    self.file_stem = file_stem;
                 }
 
-                ui.send(
-                    self.confirm,
-                    WidgetMessage::Enabled(
-                        self.selected_entry.is_some() && is_file_stem_valid(&self.file_stem),
-                    ),
-                );
+                self.sync_buttons(ui);
             }
         }
 
@@ -0,0 +1,95 @@
+//! A list of the status effects of the player, it is shown at the bottom left corner of the screen
+//! while the player has any effects.
+
+use crate::{localization::tr, status_effect::StatusEffects};
+use fyrox::{
+    core::{color::Color, pool::Handle},
+    gui::{
+        border::{Border, BorderBuilder},
+        brush::Brush,
+        font::FontResource,
+        grid::{Column, Grid, GridBuilder, Row},
+        text::{Text, TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, HorizontalAlignment, Thickness, UserInterface, VerticalAlignment,
+    },
+};
+
+#[derive(Default, Debug)]
+pub struct StatusEffectDisplay {
+    root: Handle<Grid>,
+    panel: Handle<Border>,
+    text: Handle<Text>,
+    // Text is sent to the interface only when it is changed.
+    shown_text: String,
+}
+
+impl StatusEffectDisplay {
+    pub fn new(font: FontResource, ctx: &mut BuildContext) -> Self {
+        let text;
+        let panel = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_margin(Thickness::uniform(10.0))
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .with_vertical_alignment(VerticalAlignment::Bottom)
+                .with_background(Brush::Solid(Color::from_rgba(0, 0, 0, 160)).into())
+                .with_child({
+                    text = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(6.0))
+                            .with_foreground(Brush::Solid(Color::opaque(230, 90, 60)).into()),
+                    )
+                    .with_font(font)
+                    .with_font_size(18.0.into())
+                    .build(ctx);
+                    text
+                }),
+        )
+        .build(ctx);
+
+        // The root fills the screen, so the panel could be aligned to the corner.
+        let root = GridBuilder::new(WidgetBuilder::new().with_child(panel))
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .build(ctx);
+
+        Self {
+            root,
+            panel,
+            text,
+            shown_text: Default::default(),
+        }
+    }
+
+    pub fn resize(&self, ui: &UserInterface, width: f32, height: f32) {
+        ui.send(self.root, WidgetMessage::Width(width));
+        ui.send(self.root, WidgetMessage::Height(height));
+    }
+
+    /// Shows the effects, `None` hides the display (there is no player or the interface is
+    /// hidden).
+    pub fn sync_to_model(&mut self, status_effects: Option<&StatusEffects>, ui: &UserInterface) {
+        let text = status_effects.map(describe).unwrap_or_default();
+        if text == self.shown_text {
+            return;
+        }
+        ui.send(self.panel, WidgetMessage::Visibility(!text.is_empty()));
+        ui.send(self.text, TextMessage::Text(text.clone()));
+        self.shown_text = text;
+    }
+}
+
+fn describe(status_effects: &StatusEffects) -> String {
+    status_effects
+        .iter()
+        .map(|effect| {
+            let mut line = tr(&effect.kind.name_key());
+            if effect.stacks > 1 {
+                line += &format!(" x{}", effect.stacks);
+            }
+            line + &format!(" {:.0}", effect.remaining.ceil())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
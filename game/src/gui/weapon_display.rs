@@ -1,5 +1,5 @@
-use crate::{gui, player::Player, weapon::Weapon};
-use fyrox::gui::text::Text;
+use crate::{difficulty::Difficulty, gui, localization::tr, player::Player, weapon::Weapon};
+use fyrox::gui::{progress_bar::ProgressBar, text::Text};
 use fyrox::{
     asset::manager::ResourceManager,
     core::{algebra::Vector2, color::Color, pool::Handle, visitor::prelude::*},
@@ -8,9 +8,10 @@ use fyrox::{
         font::FontResource,
         grid::{Column, GridBuilder, Row},
         image::ImageBuilder,
+        progress_bar::{ProgressBarBuilder, ProgressBarMessage},
         text::{TextBuilder, TextMessage},
         widget::WidgetBuilder,
-        UserInterface, VerticalAlignment,
+        Thickness, UserInterface, VerticalAlignment,
     },
     resource::texture::{Texture, TextureResource},
     scene::graph::Graph,
@@ -23,6 +24,8 @@ pub struct WeaponDisplay {
     pub render_target: TextureResource,
     ammo: Handle<Text>,
     grenades: Handle<Text>,
+    stamina_label: Handle<Text>,
+    stamina: Handle<ProgressBar>,
 }
 
 impl WeaponDisplay {
@@ -36,6 +39,8 @@ impl WeaponDisplay {
 
         let ammo;
         let grenades;
+        let stamina_label;
+        let stamina;
         GridBuilder::new(
             WidgetBuilder::new()
                 .with_width(Self::WIDTH)
@@ -87,16 +92,44 @@ impl WeaponDisplay {
                             .on_row(1)
                             .on_column(1),
                     )
-                    .with_font(font)
+                    .with_font(font.clone())
                     .with_font_size(31.0.into())
                     .build(&mut ui.build_ctx());
                     grenades
+                })
+                .with_child({
+                    stamina_label = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .with_foreground(Brush::Solid(Color::opaque(0, 162, 232)).into())
+                            .on_row(2)
+                            .on_column(0),
+                    )
+                    .with_text(tr("hud.stamina"))
+                    .with_font(font)
+                    .with_font_size(16.0.into())
+                    .build(&mut ui.build_ctx());
+                    stamina_label
+                })
+                .with_child({
+                    stamina = ProgressBarBuilder::new(
+                        WidgetBuilder::new()
+                            .with_height(12.0)
+                            .with_margin(Thickness::left(4.0))
+                            .with_vertical_alignment(VerticalAlignment::Center)
+                            .on_row(2)
+                            .on_column(1),
+                    )
+                    .with_progress(1.0)
+                    .build(&mut ui.build_ctx());
+                    stamina
                 }),
         )
         .add_column(Column::auto())
         .add_column(Column::stretch())
         .add_row(Row::auto())
         .add_row(Row::auto())
+        .add_row(Row::auto())
         .add_row(Row::stretch())
         .build(&mut ui.build_ctx());
 
@@ -105,16 +138,23 @@ impl WeaponDisplay {
             render_target,
             ammo,
             grenades,
+            stamina_label,
+            stamina,
         }
     }
 
-    pub fn sync_to_model(&self, player: &Player, graph: &Graph) {
+    pub fn on_language_changed(&self) {
+        self.ui
+            .send(self.stamina_label, TextMessage::Text(tr("hud.stamina")));
+    }
+
+    pub fn sync_to_model(&self, player: &Player, graph: &Graph, difficulty: Difficulty) {
         let ammo = if let Ok(weapon) =
             graph.try_get_script_component_of::<Weapon>(player.current_weapon())
         {
             if let Some(ammo_item) = weapon.ammo_item.as_ref() {
                 let total_ammo = player.inventory().item_count(ammo_item);
-                total_ammo / *weapon.ammo_consumption_per_shot
+                weapon.player_shots_left(total_ammo, difficulty)
             } else {
                 u32::MAX
             }
@@ -131,6 +171,11 @@ impl WeaponDisplay {
             }),
         );
 
+        self.ui.send(
+            self.stamina,
+            ProgressBarMessage::Progress(player.stamina.fraction()),
+        );
+
         if let Some(grenade_item) = player.grenade_item.as_ref() {
             let grenades = player.inventory().item_count(grenade_item);
             self.ui
@@ -0,0 +1,353 @@
+//! Action-based input layer. Input sources translate the input of their devices to
+//! [`InputEvent`]s that do not depend on the device, [`ActionState`] maps the events to the
+//! actions of the [`ControlScheme`]. Gameplay code works only with the actions, so every source
+//! (keyboard and mouse, gamepads) controls the game the same way.
+//!
+//! Input events are what demos record, the actions are produced from them on playback, so the
+//! recorded input is interpreted exactly like the live one.
+
+use crate::{
+    control_scheme::{Action, Axis, ControlAxis, ControlButton, ControlScheme, Modifiers},
+    demo::InputEvent,
+};
+use fyrox::{
+    core::algebra::Vector2,
+    event::{DeviceEvent, ElementState, Event, MouseScrollDelta, WindowEvent},
+    fxhash::{FxHashMap, FxHashSet},
+    keyboard::PhysicalKey,
+};
+use std::fmt::Debug;
+
+/// Source of input events. OS events are given to every source, sources that are not driven by
+/// the OS events (gamepads, for example) are polled once per frame instead.
+pub trait InputSource: Debug {
+    fn translate_os_event(&mut self, _event: &Event<()>, _output: &mut Vec<InputEvent>) {}
+
+    fn poll(&mut self, _output: &mut Vec<InputEvent>) {}
+}
+
+/// Keyboard and mouse, their input comes with the OS events.
+#[derive(Default, Debug)]
+pub struct KeyboardMouse;
+
+impl InputSource for KeyboardMouse {
+    fn translate_os_event(&mut self, event: &Event<()>, output: &mut Vec<InputEvent>) {
+        let input_event = match event {
+            Event::WindowEvent {
+                event: WindowEvent::KeyboardInput { event: input, .. },
+                ..
+            } => {
+                if let PhysicalKey::Code(key) = input.physical_key {
+                    InputEvent::Button {
+                        button: ControlButton::Key(key),
+                        pressed: input.state == ElementState::Pressed,
+                    }
+                } else {
+                    return;
+                }
+            }
+            Event::DeviceEvent { event, .. } => match event {
+                &DeviceEvent::MouseWheel { delta } => {
+                    let y = match delta {
+                        MouseScrollDelta::LineDelta(_, y) => y,
+                        MouseScrollDelta::PixelDelta(delta) => delta.y as f32,
+                    };
+                    InputEvent::Button {
+                        button: if y < 0.0 {
+                            ControlButton::WheelDown
+                        } else {
+                            ControlButton::WheelUp
+                        },
+                        pressed: true,
+                    }
+                }
+                &DeviceEvent::Button { button, state } => InputEvent::Button {
+                    button: ControlButton::Mouse(button as u16),
+                    pressed: state == ElementState::Pressed,
+                },
+                DeviceEvent::MouseMotion { delta } => InputEvent::MouseMotion {
+                    dx: delta.0 as f32,
+                    dy: delta.1 as f32,
+                },
+                _ => return,
+            },
+            _ => return,
+        };
+        output.push(input_event);
+    }
+}
+
+/// Every input source of the game.
+#[derive(Debug)]
+pub struct Input {
+    sources: Vec<Box<dyn InputSource>>,
+    polled: Vec<InputEvent>,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self {
+            sources: vec![Box::new(KeyboardMouse)],
+            polled: Default::default(),
+        }
+    }
+}
+
+impl Input {
+    pub fn add_source(&mut self, source: Box<dyn InputSource>) {
+        self.sources.push(source);
+    }
+
+    pub fn translate_os_event(&mut self, event: &Event<()>, output: &mut Vec<InputEvent>) {
+        for source in self.sources.iter_mut() {
+            source.translate_os_event(event, output);
+        }
+    }
+
+    /// Polls the sources, must be called once per frame.
+    pub fn poll(&mut self) {
+        self.polled.clear();
+        for source in self.sources.iter_mut() {
+            source.poll(&mut self.polled);
+        }
+    }
+
+    /// Events the sources produced during the last poll.
+    pub fn polled_events(&self) -> &[InputEvent] {
+        &self.polled
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ActionEvent {
+    Pressed(Action),
+    Released(Action),
+    /// Relative motion along an axis (mouse movement), absolute axes are read with
+    /// [`ActionState::axis`].
+    Axis {
+        axis: Axis,
+        value: f32,
+    },
+}
+
+/// Current state of the actions, it turns input events into action events.
+#[derive(Default, Debug, Clone)]
+pub struct ActionState {
+    held_buttons: FxHashSet<ControlButton>,
+    // Actions triggered by each held button, they are released together with the button.
+    button_actions: FxHashMap<ControlButton, Vec<Action>>,
+    // Amount of held bindings of each active action.
+    active_actions: FxHashMap<Action, usize>,
+    axes: FxHashMap<ControlAxis, f32>,
+}
+
+impl ActionState {
+    pub fn process(
+        &mut self,
+        event: InputEvent,
+        scheme: &ControlScheme,
+        output: &mut Vec<ActionEvent>,
+    ) {
+        match event {
+            InputEvent::Button {
+                button,
+                pressed: true,
+            } => {
+                let actions = self.matching_actions(button, scheme);
+
+                if button.is_momentary() {
+                    for action in actions {
+                        output.push(ActionEvent::Pressed(action));
+                        output.push(ActionEvent::Released(action));
+                    }
+                    return;
+                }
+
+                // Key repeats.
+                if !self.held_buttons.insert(button) {
+                    return;
+                }
+
+                for &action in actions.iter() {
+                    let count = self.active_actions.entry(action).or_default();
+                    *count += 1;
+                    if *count == 1 {
+                        output.push(ActionEvent::Pressed(action));
+                    }
+                }
+                self.button_actions.insert(button, actions);
+            }
+            InputEvent::Button {
+                button,
+                pressed: false,
+            } => {
+                self.held_buttons.remove(&button);
+                for action in self.button_actions.remove(&button).into_iter().flatten() {
+                    if let Some(count) = self.active_actions.get_mut(&action) {
+                        *count -= 1;
+                        if *count == 0 {
+                            self.active_actions.remove(&action);
+                            output.push(ActionEvent::Released(action));
+                        }
+                    }
+                }
+            }
+            InputEvent::MouseMotion { dx, dy } => {
+                Self::process_motion(ControlAxis::MouseX, dx, scheme, output);
+                Self::process_motion(ControlAxis::MouseY, dy, scheme, output);
+            }
+            InputEvent::Axis { axis, value } => {
+                if axis.is_relative() {
+                    Self::process_motion(axis, value, scheme, output);
+                } else {
+                    self.axes.insert(axis, value);
+                }
+            }
+        }
+    }
+
+    fn process_motion(
+        axis: ControlAxis,
+        value: f32,
+        scheme: &ControlScheme,
+        output: &mut Vec<ActionEvent>,
+    ) {
+        if value == 0.0 {
+            return;
+        }
+        for binding in scheme.axes.iter().filter(|binding| binding.axis == axis) {
+            output.push(ActionEvent::Axis {
+                axis: binding.target,
+                value: value * binding.scale,
+            });
+        }
+    }
+
+    fn held_modifiers(&self) -> Modifiers {
+        self.held_buttons
+            .iter()
+            .fold(Modifiers::default(), |modifiers, button| {
+                modifiers.union(button.modifier())
+            })
+    }
+
+    // Only the most specific bindings trigger, so Ctrl+S does not trigger the action bound to S.
+    fn matching_actions(&self, button: ControlButton, scheme: &ControlScheme) -> Vec<Action> {
+        let modifiers = self.held_modifiers().union(button.modifier());
+        let mut actions = Vec::new();
+        let mut best_modifier_count = 0;
+        for (&action, bindings) in scheme.bindings.iter() {
+            for binding in bindings {
+                if binding.button != button || !modifiers.contains(binding.modifiers) {
+                    continue;
+                }
+                let modifier_count = binding.modifiers.count();
+                if modifier_count > best_modifier_count {
+                    best_modifier_count = modifier_count;
+                    actions.clear();
+                }
+                if modifier_count == best_modifier_count && !actions.contains(&action) {
+                    actions.push(action);
+                }
+            }
+        }
+        actions
+    }
+
+    pub fn is_active(&self, action: Action) -> bool {
+        self.active_actions.contains_key(&action)
+    }
+
+    /// Value of an analog axis in `[-1; 1]` range, it is produced by the absolute axes of the
+    /// devices (gamepad sticks, for example).
+    /// Values of the sticks are shaped with the dead zone and the response curve of the gamepad
+    /// settings of the scheme.
+    pub fn axis(&self, axis: Axis, scheme: &ControlScheme) -> f32 {
+        scheme
+            .axes
+            .iter()
+            .filter(|binding| binding.target == axis)
+            .map(|binding| self.control_axis(binding.axis, scheme) * binding.scale)
+            .sum::<f32>()
+            .clamp(-1.0, 1.0)
+    }
+
+    fn raw_axis(&self, axis: ControlAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or_default()
+    }
+
+    /// Shaped value of an axis of a device.
+    pub fn control_axis(&self, axis: ControlAxis, scheme: &ControlScheme) -> f32 {
+        match axis.stick() {
+            Some((x, y)) => {
+                let stick = scheme
+                    .gamepad
+                    .shape_stick(Vector2::new(self.raw_axis(x), self.raw_axis(y)));
+                if axis == x {
+                    stick.x
+                } else {
+                    stick.y
+                }
+            }
+            None => scheme.gamepad.shape_trigger(self.raw_axis(axis)),
+        }
+    }
+
+    /// Releases every active action and resets the axes.
+    pub fn release_all(&mut self, output: &mut Vec<ActionEvent>) {
+        output.extend(
+            self.active_actions
+                .drain()
+                .map(|(action, _)| ActionEvent::Released(action)),
+        );
+        self.held_buttons.clear();
+        self.button_actions.clear();
+        self.axes.clear();
+    }
+}
+
+/// Smoothing and acceleration of the mouse look. The motion of the mouse is accumulated between
+/// frames and released once per frame, so the look does not depend on the frame rate.
+#[derive(Default, Debug, Clone)]
+pub struct MouseFilter {
+    pending: Vector2<f32>,
+}
+
+impl MouseFilter {
+    /// Remaining motion below this amount of counts is released at once.
+    const MIN_PENDING: f32 = 0.01;
+
+    pub fn add_motion(&mut self, axis: Axis, value: f32) {
+        match axis {
+            Axis::LookX => self.pending.x += value,
+            Axis::LookY => self.pending.y += value,
+            Axis::MoveX | Axis::MoveY => (),
+        }
+    }
+
+    /// Returns the motion of the mouse (in counts) for the frame. `smoothing` is the time the
+    /// motion is spread over, `acceleration` is the extra gain per thousand counts per second.
+    pub fn update(&mut self, smoothing: f32, acceleration: f32, dt: f32) -> Vector2<f32> {
+        let fraction = if smoothing > 0.0 && dt > 0.0 {
+            1.0 - (-dt / smoothing).exp()
+        } else {
+            1.0
+        };
+        let mut motion = self.pending.scale(fraction);
+        self.pending -= motion;
+        if self.pending.norm() < Self::MIN_PENDING {
+            motion += self.pending;
+            self.pending = Vector2::default();
+        }
+
+        if acceleration > 0.0 && dt > 0.0 {
+            let speed = motion.norm() / dt;
+            motion.scale_mut(1.0 + acceleration * speed / 1000.0);
+        }
+        motion
+    }
+
+    pub fn reset(&mut self) {
+        self.pending = Vector2::default();
+    }
+}
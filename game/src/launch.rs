@@ -0,0 +1,21 @@
+//! Options that define how the game starts. They are usually filled from the command line of the
+//! executor and are mostly useful for development, for example to jump straight into a specific
+//! level or a saved game.
+
+use crate::difficulty::Difficulty;
+use std::path::PathBuf;
+
+#[derive(Default, Debug, Clone)]
+pub struct LaunchOptions {
+    /// Saved game that will be loaded right after the start.
+    pub saved_game: Option<PathBuf>,
+    /// Path of the settings file, overrides the default one.
+    pub config: Option<PathBuf>,
+    /// Seed of the game random number generator.
+    pub seed: Option<u64>,
+    /// Starts a new game right away instead of showing the main menu, if there is no level (it is
+    /// passed to the plugin by the executor) or saved game to start from.
+    pub skip_menu: bool,
+    /// Difficulty of the game that is started from the command line.
+    pub difficulty: Option<Difficulty>,
+}
@@ -1,4 +1,4 @@
-use crate::{bot::Bot, door::Door, Game};
+use crate::{bot::Bot, config::AutosaveTrigger, door::Door, message::Message, profiler, Game};
 use fyrox::graph::SceneGraph;
 use fyrox::plugin::error::{GameError, GameResult};
 use fyrox::{
@@ -97,6 +97,7 @@ impl ScriptTrait for EnemyTrap {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("EnemyTrap");
         match self.state {
             State::Inactive => {
                 if let Some(level) = ctx.plugins.get::<Game>().level.as_ref() {
@@ -123,6 +124,10 @@ impl ScriptTrait for EnemyTrap {
                     self.lock_doors(ctx.scene, false);
                     self.enable_nodes(&mut ctx.scene.graph, &self.nodes_to_enable_on_deactivation)?;
                     self.state = State::Finished;
+                    ctx.plugins
+                        .get::<Game>()
+                        .message_sender
+                        .send(Message::Autosave(AutosaveTrigger::EnemyTrapFinished));
                 }
             }
             State::Finished => {
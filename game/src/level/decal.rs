@@ -1,3 +1,4 @@
+use crate::{profiler, Game};
 use fyrox::scene::collider::Collider;
 use fyrox::{
     asset::manager::ResourceManager,
@@ -36,6 +37,12 @@ impl Default for Decal {
 
 impl ScriptTrait for Decal {
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Decal");
+        // Lifetime must not run out while the game is paused.
+        if ctx.plugins.get::<Game>().is_paused() {
+            return Ok(());
+        }
+
         self.lifetime -= ctx.dt;
 
         let abs_lifetime = self.lifetime.abs();
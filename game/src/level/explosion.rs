@@ -1,5 +1,6 @@
 use crate::{
     character::{DamageDealer, DamagePosition},
+    damage::DamageType,
     level::hit_box::{HitBoxDamage, HitBoxMessage},
     Game,
 };
@@ -25,6 +26,13 @@ pub struct Explosion {
     strength: InheritableVariable<f32>,
     scale: InheritableVariable<Vector3<f32>>,
     damage: InheritableVariable<Option<f32>>,
+    damage_type: InheritableVariable<DamageType>,
+    /// The entity that caused the explosion, the damage is attributed to it.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub dealer: DamageDealer,
+    #[reflect(hidden)]
+    exploded: bool,
 }
 
 impl Default for Explosion {
@@ -33,12 +41,21 @@ impl Default for Explosion {
             strength: 100.0f32.into(),
             scale: Vector3::new(2.0, 2.0, 2.0).into(),
             damage: Default::default(),
+            damage_type: DamageType::Explosive.into(),
+            dealer: Default::default(),
+            exploded: false,
         }
     }
 }
 
 impl ScriptTrait for Explosion {
-    fn on_start(&mut self, ctx: &mut ScriptContext) -> GameResult {
+    fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        // An explosion spawned right before the world was frozen goes off when it is resumed.
+        if self.exploded || ctx.plugins.get::<Game>().is_paused() {
+            return Ok(());
+        }
+        self.exploded = true;
+
         let node = &ctx.scene.graph[ctx.handle];
         let aabb = AxisAlignedBoundingBox::unit()
             .transform(&(node.global_transform() * Matrix4::new_nonuniform_scaling(&*self.scale)));
@@ -72,12 +89,14 @@ impl ScriptTrait for Explosion {
                         HitBoxMessage::Damage(HitBoxDamage {
                             hit_box,
                             damage,
-                            dealer: DamageDealer::default(),
+                            damage_type: *self.damage_type,
+                            dealer: self.dealer,
                             position: Some(DamagePosition {
                                 point: position,
                                 direction,
                             }),
                             is_melee: false,
+                            is_status_effect: false,
                         }),
                     );
                 }
@@ -1,4 +1,10 @@
-use crate::level::hit_box::HitBoxMessage;
+use crate::{
+    character::{Character, CharacterMessage, CharacterMessageData, DamageDealer},
+    level::{explosion::Explosion, hit_box::HitBoxMessage},
+    profiler,
+    status_effect::StatusEffectKind,
+    Game,
+};
 use fyrox::{
     core::{
         pool::Handle, reflect::prelude::*, type_traits::prelude::*, variable::InheritableVariable,
@@ -22,6 +28,9 @@ pub struct ExplosiveBarrel {
     exploded_state: InheritableVariable<ImmutableString>,
     state_machine: InheritableVariable<Handle<AnimationBlendingStateMachine>>,
     explosion_prefab: InheritableVariable<Option<ModelResource>>,
+    /// Characters closer than this to a burning barrel catch fire.
+    #[reflect(min_value = 0.0, max_value = 10.0)]
+    fire_radius: InheritableVariable<f32>,
 }
 
 impl Default for ExplosiveBarrel {
@@ -33,6 +42,7 @@ impl Default for ExplosiveBarrel {
             exploded_state: ImmutableString::new("Exploded").into(),
             state_machine: Default::default(),
             explosion_prefab: Default::default(),
+            fire_radius: 1.5.into(),
         }
     }
 }
@@ -45,6 +55,12 @@ impl ScriptTrait for ExplosiveBarrel {
     }
 
     fn on_update(&mut self, context: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("ExplosiveBarrel");
+        // The fire must not spread while the game is paused.
+        if context.plugins.get::<Game>().is_paused() {
+            return Ok(());
+        }
+
         let graph = &mut context.scene.graph;
         let position = graph[context.handle].global_position();
         let absm = graph.try_get_mut(*self.state_machine)?;
@@ -52,17 +68,33 @@ impl ScriptTrait for ExplosiveBarrel {
         let machine = absm.machine_mut();
         machine.set_rule("IsDamaged", *self.health <= 0.0);
 
+        let mut is_burning = false;
         if let Some(layer) = machine.layers_mut().first_mut() {
+            is_burning = layer
+                .states()
+                .try_borrow(layer.active_state())
+                .is_ok_and(|state| state.name.as_str() == self.burning_state.as_str());
+
             while let Some(event) = layer.pop_event() {
                 if let Event::StateEnter(state) = event {
                     let state_ref = layer.states().try_borrow(state)?;
                     if state_ref.name.as_str() == self.exploded_state.as_str() {
                         if let Some(explosion_prefab) = self.explosion_prefab.as_ref() {
-                            explosion_prefab.instantiate_at(
+                            let explosion = explosion_prefab.instantiate_at(
                                 context.scene,
                                 position,
                                 Default::default(),
                             );
+                            // Kills made by the explosion are attributed to the barrel.
+                            if let Ok(explosion) = context
+                                .scene
+                                .graph
+                                .try_get_script_of_mut::<Explosion>(explosion)
+                            {
+                                explosion.dealer = DamageDealer {
+                                    entity: context.handle,
+                                };
+                            }
                             break;
                         }
                     }
@@ -70,6 +102,33 @@ impl ScriptTrait for ExplosiveBarrel {
             }
         }
 
+        if is_burning {
+            let graph = &context.scene.graph;
+            let Some(level) = context.plugins.get::<Game>().level.as_ref() else {
+                return Ok(());
+            };
+            for &actor in level.actors.iter() {
+                let Ok(character) = graph.try_get_script_component_of::<Character>(actor) else {
+                    continue;
+                };
+                // Burning characters catch fire again only when the current fire is over.
+                if !character.status_effects.has(StatusEffectKind::Burning)
+                    && character.position(graph).metric_distance(&position) <= *self.fire_radius
+                {
+                    context.message_sender.send_to_target(
+                        actor,
+                        CharacterMessage {
+                            character: actor,
+                            data: CharacterMessageData::ApplyStatusEffect {
+                                kind: StatusEffectKind::Burning,
+                                dealer: context.handle,
+                            },
+                        },
+                    );
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -1,5 +1,10 @@
 use crate::{
-    character::{DamageDealer, DamagePosition},
+    character::{Character, CharacterMessage, CharacterMessageData, DamageDealer, DamagePosition},
+    damage::{DamageType, Resistances},
+    profiler,
+    statistics::StatisticsEvent,
+    status_effect::StatusEffectKind,
+    telemetry::TelemetryEvent,
     Game,
 };
 use fyrox::plugin::error::GameResult;
@@ -17,7 +22,7 @@ use fyrox::{
     resource::model::{ModelResource, ModelResourceExtension},
     scene::{
         collider::{Collider, ColliderShape},
-        graph::physics::RayCastOptions,
+        graph::{physics::RayCastOptions, Graph},
         node::Node,
     },
     script::{
@@ -27,13 +32,26 @@ use fyrox::{
 };
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
+/// Checks whether the given hit box belongs to the player.
+fn is_player_hit_box(hit_box: Handle<Collider>, game: &Game, graph: &Graph) -> bool {
+    game.level.as_ref().is_some_and(|level| {
+        graph
+            .try_get_script_component_of::<Character>(level.player)
+            .is_ok_and(|player| player.has_hit_box(hit_box))
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct HitBoxDamage {
     pub hit_box: Handle<Collider>,
     pub damage: f32,
+    pub damage_type: DamageType,
     pub dealer: DamageDealer,
     pub position: Option<DamagePosition>,
     pub is_melee: bool,
+    /// A tick of a status effect, it is taken by a single hit box and is not passed to its
+    /// children.
+    pub is_status_effect: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -105,6 +123,12 @@ pub struct HitBox {
     pub environment_damage_timeout: f32,
     pub children_hit_boxes: InheritableVariable<Vec<Handle<Node>>>,
     pub critical_for_survival: InheritableVariable<bool>,
+    /// Applied on top of the resistances of the character that owns the hit box.
+    pub resistances: Resistances,
+    /// Health of the hit box when it was first started, it is saved so the damaged hit boxes of
+    /// a loaded game keep their original reference.
+    #[reflect(hidden)]
+    initial_health: Option<f32>,
 }
 
 impl Default for HitBox {
@@ -118,20 +142,28 @@ impl Default for HitBox {
             pierce_prefab: Default::default(),
             damage_prefab: Default::default(),
             destruction_prefab: Default::default(),
-            health: 100.0.into(),
+            health: Self::DEFAULT_HEALTH.into(),
             limb_type: Default::default(),
             environment_damage_timeout: 0.0,
             children_hit_boxes: Default::default(),
             critical_for_survival: Default::default(),
+            resistances: Default::default(),
+            initial_health: None,
         }
     }
 }
 
 impl HitBox {
+    pub const DEFAULT_HEALTH: f32 = 100.0;
+
     pub fn is_sliced_off(&self) -> bool {
         self.limb_type.can_be_sliced_off() && *self.health <= 0.0
     }
 
+    pub fn initial_health(&self) -> f32 {
+        self.initial_health.unwrap_or(*self.health)
+    }
+
     fn handle_environment_interaction(&mut self, ctx: &mut ScriptContext) -> GameResult {
         if self.environment_damage_timeout > 0.0 {
             self.environment_damage_timeout -= ctx.dt;
@@ -161,6 +193,7 @@ impl HitBox {
                             HitBoxMessage::Damage(HitBoxDamage {
                                 hit_box: ctx.handle.transmute(),
                                 damage: hit_strength,
+                                damage_type: DamageType::Environmental,
                                 dealer: DamageDealer::default(),
                                 position: Some(DamagePosition {
                                     point: graph
@@ -171,6 +204,7 @@ impl HitBox {
                                     direction: manifold.normal,
                                 }),
                                 is_melee: true,
+                                is_status_effect: false,
                             }),
                         );
 
@@ -198,21 +232,125 @@ impl HitBox {
                     HitBoxMessage::Damage(HitBoxDamage {
                         hit_box: ctx.handle.transmute(),
                         damage: 10000.0,
+                        damage_type: DamageType::Environmental,
                         dealer: DamageDealer::default(),
                         position: None,
                         is_melee: false,
+                        is_status_effect: false,
                     }),
                 );
             }
         }
     }
 
+    /// Returns the damage factor of the hit box scaled according to current difficulty.
+    fn scaled_damage_factor(&self, is_player_hit_box: bool, game: &Game) -> f32 {
+        let tuning = game.difficulty().tuning();
+        let difficulty_factor = if is_player_hit_box {
+            tuning.player_damage_factor
+        } else {
+            tuning.enemy_damage_factor
+        };
+        *self.damage_factor * difficulty_factor
+    }
+
+    fn record_statistics(
+        &self,
+        damage: &HitBoxDamage,
+        amount: f32,
+        is_player_hit_box: bool,
+        sliced_off: bool,
+        game: &Game,
+        graph: &Graph,
+    ) {
+        if is_player_hit_box {
+            game.record_statistics(StatisticsEvent::DamageTaken(amount));
+            return;
+        }
+
+        let is_dealt_by_player = game.level.as_ref().is_some_and(|level| {
+            damage
+                .dealer
+                .as_character(graph)
+                .is_some_and(|(handle, _)| handle == level.player)
+        });
+        // Ticks of the status effects are not hits, a limb they take off is not counted either.
+        if !is_dealt_by_player || damage.is_status_effect {
+            return;
+        }
+
+        if *self.limb_type == LimbType::Head && !damage.is_melee {
+            game.record_statistics(StatisticsEvent::Headshot);
+        }
+        if sliced_off {
+            game.record_statistics(StatisticsEvent::LimbSevered);
+        }
+    }
+
     fn on_damage(&mut self, damage: &HitBoxDamage, ctx: &mut ScriptMessageContext) {
+        let game = ctx.plugins.get::<Game>();
+        let graph = &ctx.scene.graph;
+
+        // Damage is attributed to the character that owns the hit box, if any.
+        let owner = game
+            .level
+            .iter()
+            .flat_map(|level| level.actors.iter())
+            .find_map(|actor| {
+                graph
+                    .try_get_script_component_of::<Character>(*actor)
+                    .ok()
+                    .filter(|character| character.has_hit_box(damage.hit_box))
+                    .map(|character| (*actor, character))
+            });
+
+        let is_player_hit_box = is_player_hit_box(damage.hit_box, game, graph);
+        let resisted = owner.map_or(damage.damage, |(_, character)| {
+            character
+                .resistances
+                .apply(damage.damage, damage.damage_type)
+        });
+        let amount = self.resistances.apply(resisted, damage.damage_type)
+            * self.scaled_damage_factor(is_player_hit_box, game);
         let prev_is_sliced_off = self.is_sliced_off();
-        *self.health -= damage.damage;
+        *self.health -= amount;
+        let sliced_off = !prev_is_sliced_off && self.is_sliced_off();
+
+        self.record_statistics(damage, amount, is_player_hit_box, sliced_off, game, graph);
+
+        game.record_telemetry(|| {
+            let target = owner.map_or(ctx.handle, |(handle, _)| handle);
+            TelemetryEvent::damage(
+                graph,
+                target,
+                &damage.dealer,
+                *self.limb_type,
+                amount,
+                damage.is_melee,
+            )
+        });
+
+        // Ticks of the status effects never renew the effects or cause new ones.
+        if let Some((owner, _)) = owner.filter(|_| !damage.is_status_effect) {
+            let caused = StatusEffectKind::caused_by(damage.damage_type)
+                .filter(|_| damage.position.is_some() && amount > 0.0);
+            let bleeding = sliced_off.then_some(StatusEffectKind::Bleeding);
+            for kind in caused.into_iter().chain(bleeding) {
+                ctx.message_sender.send_to_target(
+                    owner,
+                    CharacterMessage {
+                        character: owner,
+                        data: CharacterMessageData::ApplyStatusEffect {
+                            kind,
+                            dealer: damage.dealer.entity,
+                        },
+                    },
+                );
+            }
+        }
 
         if let Some(position) = damage.position {
-            if !prev_is_sliced_off && self.is_sliced_off() {
+            if sliced_off {
                 if let Some(prefab) = self.destruction_prefab.as_ref() {
                     prefab.instantiate_at(
                         ctx.scene,
@@ -248,6 +386,7 @@ impl HitBox {
             if let Some(pierce_prefab) = self.pierce_prefab.as_ref() {
                 let mut query_buffer = Vec::default();
 
+                profiler::count("Raycasts");
                 ctx.scene.graph.physics.cast_ray(
                     RayCastOptions {
                         ray_origin: Point3::from(position.point),
@@ -283,16 +422,22 @@ impl HitBox {
             }
         }
 
-        // Propagate the damage down.
+        if damage.is_status_effect {
+            return;
+        }
+
+        // Propagate the damage down, every hit box applies its own damage factor.
         for child in self.children_hit_boxes.iter() {
             ctx.message_sender.send_to_target(
                 *child,
                 HitBoxMessage::Damage(HitBoxDamage {
                     hit_box: child.to_variant(),
                     damage: damage.damage,
+                    damage_type: damage.damage_type,
                     dealer: damage.dealer,
                     position: damage.position,
                     is_melee: damage.is_melee,
+                    is_status_effect: false,
                 }),
             );
         }
@@ -305,6 +450,8 @@ impl HitBox {
 
 impl ScriptTrait for HitBox {
     fn on_start(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        self.initial_health.get_or_insert(*self.health);
+
         ctx.plugins
             .get_mut::<Game>()
             .level
@@ -332,6 +479,11 @@ impl ScriptTrait for HitBox {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("HitBox");
+        if ctx.plugins.get::<Game>().is_paused() {
+            return Ok(());
+        }
+
         self.handle_death_zones(ctx);
         self.handle_environment_interaction(ctx)?;
         if self.is_sliced_off() {
@@ -354,7 +506,15 @@ impl ScriptTrait for HitBox {
         };
 
         match hit_box_message {
-            HitBoxMessage::Damage(damage) => self.on_damage(damage, ctx),
+            HitBoxMessage::Damage(damage) => {
+                if !ctx
+                    .plugins
+                    .get::<Game>()
+                    .is_invulnerable_hit_box(&ctx.scene.graph, ctx.handle.transmute())
+                {
+                    self.on_damage(damage, ctx)
+                }
+            }
             HitBoxMessage::Heal(heal) => self.on_heal(heal),
         }
 
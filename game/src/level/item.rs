@@ -1,4 +1,4 @@
-use crate::{block_on, Game};
+use crate::{block_on, localization::tr, profiler, Game};
 use fyrox::scene::sprite::Sprite;
 use fyrox::{
     core::{
@@ -32,9 +32,12 @@ use strum_macros::{AsRefStr, EnumString, VariantNames};
 pub enum ItemAction {
     #[default]
     None,
-    Heal {
-        amount: f32,
-    },
+    /// Restores health and puts out burning.
+    Heal { amount: f32 },
+    /// Stops bleeding.
+    Bandage,
+    /// Cures poisoning.
+    Antidote,
 }
 
 stub_uuid_provider!(ItemAction);
@@ -44,7 +47,9 @@ stub_uuid_provider!(ItemAction);
 #[visit(optional)]
 pub struct Item {
     pub stack_size: InheritableVariable<u32>,
+    /// Localization key of the description, see [`crate::localization`].
     pub description: InheritableVariable<String>,
+    /// Localization key of the name, see [`crate::localization`].
     pub name: InheritableVariable<String>,
     pub consumable: InheritableVariable<bool>,
     pub preview: InheritableVariable<Option<TextureResource>>,
@@ -119,9 +124,10 @@ impl ScriptTrait for Item {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Item");
         let spark = &mut ctx.scene.graph[self.spark];
         spark.set_enabled(self.enabled);
-        if self.enabled {
+        if self.enabled && !ctx.plugins.get::<Game>().is_paused() {
             let new_size = spark.size() + 0.02 * self.spark_size_change_dir * ctx.dt;
             spark.set_size(new_size);
             let new_rotation = spark.rotation() + 20.0f32.to_radians() * ctx.dt;
@@ -137,6 +143,14 @@ impl ScriptTrait for Item {
 }
 
 impl Item {
+    pub fn localized_name(&self) -> String {
+        tr(&self.name)
+    }
+
+    pub fn localized_description(&self) -> String {
+        tr(&self.description)
+    }
+
     pub fn from_resource<F, R>(model_resource: &ModelResource, func: F) -> R
     where
         F: FnOnce(Option<&Item>) -> R,
@@ -156,6 +170,7 @@ impl Item {
         let position = if adjust_height {
             let mut intersections = Vec::new();
             let ray = Ray::from_two_points(position, position - Vector3::new(0.0, 1000.0, 0.0));
+            profiler::count("Raycasts");
             scene.graph.physics.cast_ray(
                 RayCastOptions {
                     ray_origin: Point3::from(ray.origin),
@@ -1,20 +1,34 @@
 use crate::{
-    bot::Bot, config::SoundConfig, door::DoorContainer, level::item::ItemContainer,
-    sound::SoundManager, utils::use_hrtf, MessageSender,
+    bot::Bot,
+    config::SoundConfig,
+    debug_layers::{DebugLayer, DebugLayers},
+    difficulty::Difficulty,
+    door::{Door, DoorContainer},
+    elevator::Elevator,
+    level::{hit_box::HitBox, item::ItemContainer, trigger::Trigger, turret::Turret},
+    sound::SoundManager,
+    statistics::RunStatistics,
+    utils::use_hrtf,
+    MessageSender,
 };
-use fyrox::scene::collider::Collider;
+use fyrox::scene::collider::{Collider, ColliderShape};
 use fyrox::{
     asset::manager::ResourceManager,
-    core::{futures::executor::block_on, pool::Handle, visitor::prelude::*},
+    core::{
+        color::Color, futures::executor::block_on, math::aabb::AxisAlignedBoundingBox,
+        pool::Handle, visitor::prelude::*,
+    },
     fxhash::FxHashSet,
     graph::SceneGraph,
     plugin::{error::GameResult, PluginContext},
     scene::{
+        debug::Line,
         navmesh::NavigationalMesh,
         node::{Node, NodeTrait},
         Scene,
     },
 };
+use std::path::PathBuf;
 
 pub mod arrival;
 pub mod death_zone;
@@ -40,6 +54,18 @@ pub struct Level {
     pub elevators: Vec<Handle<Node>>,
     pub navmesh: Handle<Node>,
     pub pois: FxHashSet<Handle<Node>>,
+    /// Path of the scene the level was created from.
+    #[visit(optional)]
+    pub path: PathBuf,
+    /// Elapsed game time (in seconds) since the level was started, pauses are not counted.
+    #[visit(optional)]
+    pub time: f32,
+    /// Difficulty of the game the level belongs to, saved games made before difficulties were
+    /// added are loaded with the default one.
+    #[visit(optional)]
+    pub difficulty: Difficulty,
+    #[visit(optional)]
+    pub statistics: RunStatistics,
 
     #[visit(skip)]
     pub sound_manager: SoundManager,
@@ -96,6 +122,10 @@ impl Level {
             doors_container: Default::default(),
             elevators: Default::default(),
             pois: Default::default(),
+            path: Default::default(),
+            time: 0.0,
+            difficulty: Default::default(),
+            statistics: Default::default(),
         }
     }
 
@@ -117,23 +147,168 @@ impl Level {
         self.sender = Some(sender);
     }
 
-    pub fn debug_draw(&self, context: &mut PluginContext) -> GameResult {
+    pub fn debug_draw(&self, layers: &DebugLayers, context: &mut PluginContext) -> GameResult {
         let scene = &mut context.scenes[self.scene];
 
         let drawing_context = &mut scene.drawing_context;
 
         drawing_context.clear_lines();
 
-        scene.graph.physics.draw(drawing_context);
+        if layers.is_empty() {
+            return Ok(());
+        }
 
-        scene
-            .graph
-            .try_get_of_type::<NavigationalMesh>(self.navmesh)?
-            .debug_draw(drawing_context);
+        let graph = &scene.graph;
+
+        if layers.is_enabled(DebugLayer::Physics) {
+            graph.physics.draw(drawing_context);
+        }
+
+        if layers.is_enabled(DebugLayer::Navmesh) {
+            if let Ok(navmesh) = graph.try_get_of_type::<NavigationalMesh>(self.navmesh) {
+                navmesh.debug_draw(drawing_context);
+            }
+        }
 
         for actor in self.actors.iter() {
-            if let Some(bot) = scene.graph.try_get(*actor)?.try_get_script::<Bot>() {
-                bot.debug_draw(drawing_context);
+            if let Some(bot) = graph
+                .try_get(*actor)
+                .ok()
+                .and_then(|node| node.try_get_script::<Bot>())
+            {
+                bot.debug_draw(layers, drawing_context);
+            }
+        }
+
+        // Turrets and triggers are not registered in the level, so they have to be found.
+        if layers.is_enabled(DebugLayer::TurretFrustums) || layers.is_enabled(DebugLayer::Volumes) {
+            for (_, node) in graph.pair_iter() {
+                if layers.is_enabled(DebugLayer::TurretFrustums) {
+                    if let Some(turret) = node.try_get_script::<Turret>() {
+                        turret.debug_draw(drawing_context);
+                    }
+                }
+                if layers.is_enabled(DebugLayer::Volumes) && node.has_script::<Trigger>() {
+                    drawing_context.draw_aabb(
+                        &AxisAlignedBoundingBox::unit().transform(&node.global_transform()),
+                        Color::from_rgba(0, 150, 255, 255),
+                    );
+                }
+            }
+        }
+
+        if layers.is_enabled(DebugLayer::Volumes) {
+            for zone in self.death_zones.iter() {
+                if let Ok(zone) = graph.try_get(*zone) {
+                    drawing_context.draw_aabb(&zone.world_bounding_box(), Color::RED);
+                }
+            }
+        }
+
+        if layers.is_enabled(DebugLayer::HitBoxes) {
+            for hit_box in self.hit_boxes.iter() {
+                let Ok(collider) = graph.try_get(*hit_box) else {
+                    continue;
+                };
+                let Some(script) = collider.try_get_script::<HitBox>() else {
+                    continue;
+                };
+                // Green for healthy hit boxes, red for destroyed ones.
+                let initial_health = script.initial_health();
+                let k = if initial_health > 0.0 {
+                    (*script.health / initial_health).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let color = Color::from_rgba((255.0 * (1.0 - k)) as u8, (255.0 * k) as u8, 0, 255);
+                let transform = collider.global_transform();
+                match collider.shape() {
+                    ColliderShape::Ball(ball) => drawing_context.draw_sphere(
+                        collider.global_position(),
+                        8,
+                        8,
+                        ball.radius,
+                        color,
+                    ),
+                    ColliderShape::Capsule(capsule) => drawing_context.draw_segment_capsule(
+                        capsule.begin,
+                        capsule.end,
+                        capsule.radius,
+                        8,
+                        8,
+                        transform,
+                        color,
+                    ),
+                    ColliderShape::Cuboid(cuboid) => drawing_context.draw_oob(
+                        &AxisAlignedBoundingBox::from_min_max(
+                            -cuboid.half_extents,
+                            cuboid.half_extents,
+                        ),
+                        transform,
+                        color,
+                    ),
+                    _ => drawing_context.draw_transform(transform),
+                }
+            }
+        }
+
+        if layers.is_enabled(DebugLayer::Doors) {
+            for door in self.doors_container.doors.iter() {
+                if let Some(door) = graph
+                    .try_get(*door)
+                    .ok()
+                    .and_then(|node| node.try_get_script::<Door>())
+                {
+                    drawing_context.draw_sphere(
+                        door.initial_position(),
+                        16,
+                        8,
+                        Door::INTERACTION_RADIUS,
+                        Color::from_rgba(255, 200, 0, 255),
+                    );
+                }
+            }
+        }
+
+        if layers.is_enabled(DebugLayer::Elevators) {
+            for elevator in self.elevators.iter() {
+                let Some(elevator) = graph
+                    .try_get(*elevator)
+                    .ok()
+                    .and_then(|node| node.try_get_script::<Elevator>())
+                else {
+                    continue;
+                };
+                let floors = elevator
+                    .point_handles
+                    .iter()
+                    .filter_map(|point| graph.try_get(*point).ok())
+                    .map(|point| point.global_position())
+                    .collect::<Vec<_>>();
+                for floor in floors.iter() {
+                    drawing_context.draw_sphere(
+                        *floor,
+                        8,
+                        8,
+                        0.2,
+                        Color::from_rgba(255, 0, 255, 255),
+                    );
+                }
+                for pair in floors.windows(2) {
+                    drawing_context.add_line(Line {
+                        begin: pair[0],
+                        end: pair[1],
+                        color: Color::from_rgba(255, 0, 255, 255),
+                    });
+                }
+            }
+        }
+
+        if layers.is_enabled(DebugLayer::PointsOfInterest) {
+            for poi in self.pois.iter() {
+                if let Ok(poi) = graph.try_get(*poi) {
+                    drawing_context.draw_transform(poi.global_transform());
+                }
             }
         }
 
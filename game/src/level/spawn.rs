@@ -1,4 +1,7 @@
-use crate::character::{CharacterMessage, CharacterMessageData};
+use crate::{
+    character::{CharacterMessage, CharacterMessageData},
+    profiler, Game,
+};
 use fyrox::{
     core::{log::Log, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
     plugin::error::GameResult,
@@ -15,6 +18,9 @@ pub struct CharacterSpawnPoint {
     amount: usize,
     interval: f32,
     timer: f32,
+    // The amount is scaled according to the difficulty only once, the scaled amount is saved.
+    #[reflect(hidden)]
+    amount_scaled: bool,
 }
 
 impl Default for CharacterSpawnPoint {
@@ -25,12 +31,30 @@ impl Default for CharacterSpawnPoint {
             amount: 1,
             interval: 30.0,
             timer: 0.0,
+            amount_scaled: false,
         }
     }
 }
 
 impl ScriptTrait for CharacterSpawnPoint {
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("CharacterSpawnPoint");
+        // Spawn timer must not advance while the game is paused.
+        if ctx.plugins.get::<Game>().is_paused() {
+            return Ok(());
+        }
+
+        if !self.amount_scaled {
+            let factor = ctx
+                .plugins
+                .get::<Game>()
+                .difficulty()
+                .tuning()
+                .spawn_amount_factor;
+            self.amount = (self.amount as f32 * factor).round() as usize;
+            self.amount_scaled = true;
+        }
+
         self.timer -= ctx.dt;
         if self.timer <= 0.0 && self.amount > 0 {
             self.timer = self.interval;
@@ -1,4 +1,7 @@
-use crate::{character::Character, message::Message, Game};
+use crate::{
+    achievements::AchievementEvent, character::Character, level::Level, message::Message, profiler,
+    statistics::StatisticsEvent, Game,
+};
 use fyrox::plugin::error::GameResult;
 use fyrox::{
     core::{
@@ -30,6 +33,10 @@ pub enum TriggerAction {
     },
     BotCounter(BotCounter),
     EndGame,
+    /// Counts as a found secret when the player enters the trigger for the first time.
+    Secret {
+        found: bool,
+    },
 }
 
 stub_uuid_provider!(TriggerAction);
@@ -41,8 +48,27 @@ pub struct Trigger {
     kind: TriggerAction,
 }
 
+impl Trigger {
+    pub fn new(kind: TriggerAction) -> Self {
+        Self { kind }
+    }
+
+    pub fn is_secret(&self) -> bool {
+        matches!(self.kind, TriggerAction::Secret { .. })
+    }
+}
+
+fn record_level_finished(game: &Game, level: &Level) {
+    let damage_taken = level
+        .statistics
+        .current()
+        .map_or(0.0, |statistics| statistics.damage_taken);
+    game.record_achievement_event(AchievementEvent::LevelFinished { damage_taken });
+}
+
 impl ScriptTrait for Trigger {
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Trigger");
         let game = ctx.plugins.get::<Game>();
 
         if let Some(level) = game.level.as_ref() {
@@ -59,15 +85,23 @@ impl ScriptTrait for Trigger {
             match self.kind {
                 TriggerAction::LoadLevel { ref path } => {
                     if contains_player {
+                        record_level_finished(game, level);
                         game.message_sender
                             .send(Message::LoadLevel { path: path.clone() })
                     }
                 }
                 TriggerAction::EndGame => {
                     if contains_player {
+                        record_level_finished(game, level);
                         game.message_sender.send(Message::EndGame)
                     }
                 }
+                TriggerAction::Secret { ref mut found } => {
+                    if contains_player && !*found {
+                        *found = true;
+                        game.record_statistics(StatisticsEvent::SecretFound);
+                    }
+                }
                 TriggerAction::None => {}
                 TriggerAction::BotCounter(ref mut bot_counter) => {
                     let mut despawn_list = Vec::new();
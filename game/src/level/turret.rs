@@ -1,5 +1,7 @@
 use crate::character::Character;
-use crate::{sound::SoundManager, weapon::projectile::Projectile, Game, Player};
+use crate::{
+    profiler, rng::GameRng, sound::SoundManager, weapon::projectile::Projectile, Game, Player,
+};
 use fyrox::{
     core::{
         algebra::{Matrix4, Point3, Vector3},
@@ -7,7 +9,7 @@ use fyrox::{
         color::Color,
         math::{frustum::Frustum, ray::Ray, SmoothAngle, Vector3Ext},
         pool::Handle,
-        rand::{seq::SliceRandom, thread_rng},
+        rand::seq::SliceRandom,
         reflect::prelude::*,
         stub_uuid_provider,
         type_traits::prelude::*,
@@ -149,12 +151,14 @@ impl Default for Turret {
 
 impl ScriptTrait for Turret {
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
-        let level_ref = ctx
-            .plugins
-            .get::<Game>()
-            .level
-            .as_ref()
-            .expect("Level must exist!");
+        let _scope = profiler::scope("Turret");
+        let game = ctx.plugins.get::<Game>();
+        // The world could be frozen in photo mode.
+        if game.is_paused() {
+            return Ok(());
+        }
+
+        let level_ref = game.level.as_ref().expect("Level must exist!");
 
         self.update_frustum(ctx.scene);
 
@@ -213,6 +217,7 @@ impl ScriptTrait for Turret {
                                 ctx.scene,
                                 target_position,
                                 &level_ref.sound_manager,
+                                game.rng(),
                             );
                             self.barrel_index += 1;
                             if self.barrel_index >= self.barrels.len() as u32 {
@@ -227,6 +232,7 @@ impl ScriptTrait for Turret {
                                 ctx.scene,
                                 target_position,
                                 &level_ref.sound_manager,
+                                game.rng(),
                             );
                         }
                     }
@@ -288,6 +294,7 @@ impl Barrel {
         scene: &mut Scene,
         target_position: Vector3<f32>,
         sound_manager: &SoundManager,
+        mut rng: &GameRng,
     ) {
         self.offset = Vector3::new(-20.0, 0.0, 0.0);
 
@@ -312,7 +319,7 @@ impl Barrel {
 
         sound_manager.play_sound(
             &mut scene.graph,
-            sounds.choose(&mut thread_rng()).unwrap(),
+            sounds.choose(&mut rng).unwrap(),
             shot_position,
             1.0,
             1.0,
@@ -383,6 +390,7 @@ impl Turret {
                 }
 
                 let ray = Ray::from_two_points(actor_position, self_position);
+                profiler::count("Raycasts");
                 scene.graph.physics.cast_ray(
                     RayCastOptions {
                         ray_origin: Point3::from(ray.origin),
@@ -1,20 +1,40 @@
 #![allow(clippy::too_many_arguments)]
 
+pub mod achievements;
+pub mod aim_assist;
 pub mod bot;
 pub mod character;
 pub mod config;
+pub mod console;
 pub mod control_scheme;
+pub mod damage;
+pub mod debug_layers;
+pub mod demo;
+pub mod difficulty;
 pub mod door;
 pub mod effects;
 pub mod elevator;
+pub mod free_camera;
+pub mod gamepad;
 pub mod gui;
 pub mod highlight;
+pub mod input;
 pub mod inventory;
+pub mod launch;
 pub mod level;
 pub mod light;
+pub mod localization;
 pub mod message;
 pub mod player;
+pub mod profiler;
+pub mod rng;
+pub mod saved_game;
+pub mod simulation;
 pub mod sound;
+pub mod stamina;
+pub mod statistics;
+pub mod status_effect;
+pub mod telemetry;
 pub mod utils;
 pub mod weapon;
 
@@ -22,23 +42,33 @@ pub use fyrox;
 
 use crate::gui::final_screen::FinalScreenData;
 use crate::{
+    achievements::{AchievementEvent, Achievements, LocalAchievementBackend},
     bot::{Bot, BotHostility},
     character::Character,
-    config::Config,
+    config::{AutosaveTrigger, Config},
+    console::{Cheats, Console},
+    control_scheme::{Action, ControlButton, GamepadButton},
+    demo::{Demo, DemoPlayer, DemoRecorder, DemoSource, DemoState, InputEvent},
+    difficulty::Difficulty,
     door::Door,
     effects::{beam::Beam, rail::Rail},
     elevator::{
         call_button::{CallButton, CallButtonKind},
         Elevator,
     },
+    free_camera::FreeCamera,
     gui::death_screen::DeathScreenData,
     gui::final_screen::FinalScreen,
     gui::{
-        inventory::InventoryItem, item_display::ItemDisplay, journal::JournalDisplay,
-        loading_screen::LoadingScreen, menu::Menu, menu::MenuData, weapon_display::WeaponDisplay,
+        achievement_toast::AchievementToast, inventory::InventoryItem, item_display::ItemDisplay,
+        journal::JournalDisplay, loading_screen::LoadingScreen, menu::Menu, menu::MenuData,
+        navigation::NavigationInput, pause_menu::PauseMenu, status_effects::StatusEffectDisplay,
+        weapon_display::WeaponDisplay,
     },
     highlight::HighlightRenderPass,
+    input::{ActionEvent, ActionState, Input, InputSource},
     inventory::{Inventory, ItemEntry},
+    launch::LaunchOptions,
     level::{
         arrival::enemy_trap::EnemyTrap,
         death_zone::DeathZone,
@@ -55,8 +85,13 @@ use crate::{
     },
     light::AnimatedLight,
     message::Message,
-    player::{camera::CameraController, Player},
+    player::{camera::CameraController, Player, PlayerPersistentData},
+    profiler::Profiler,
+    rng::GameRng,
+    saved_game::{SavedGame, SavedGameHeader},
     sound::SoundManager,
+    statistics::{RunStatistics, StatisticsEvent},
+    telemetry::{Telemetry, TelemetryEvent},
     utils::use_hrtf,
     weapon::{
         kinetic::KineticGun,
@@ -73,11 +108,12 @@ use fyrox::{
         log::Log,
         pool::Handle,
         reflect::prelude::*,
-        visitor::{Visit, VisitResult, Visitor},
+        visitor::{Visit, VisitError, VisitResult, Visitor},
     },
     dpi::LogicalSize,
     engine::GraphicsContext,
     event::{ElementState, Event, WindowEvent},
+    graph::SceneGraph,
     gui::{
         button::ButtonMessage,
         check_box::CheckBoxMessage,
@@ -96,7 +132,9 @@ use fyrox::{
     renderer::ui_renderer::UiRenderInfo,
     scene::{
         base::BaseBuilder,
+        loader::SceneLoader,
         sound::{SoundBuffer, SoundBuilder, Status},
+        Scene,
     },
     utils::translate_event,
     window::CursorGrabMode,
@@ -116,6 +154,7 @@ use std::{
 #[reflect(hide_all, non_cloneable)]
 pub struct Game {
     menu: Option<Menu>,
+    pause_menu: Option<PauseMenu>,
     level: Option<Level>,
     debug_text: Handle<Text>,
     debug_string: String,
@@ -136,14 +175,89 @@ pub struct Game {
     #[visit(skip)]
     highlighter: Option<Rc<RefCell<HighlightRenderPass>>>,
     font: FontResource,
+    #[visit(skip)]
+    headless: bool,
+    rng: GameRng,
+    #[visit(skip)]
+    level_source: Option<DemoSource>,
+    #[visit(skip)]
+    demo: Option<DemoState>,
+    // Demo that will start right after the level is loaded.
+    #[visit(skip)]
+    pending_demo: Option<DemoState>,
+    #[visit(skip)]
+    console: Console,
+    #[visit(skip)]
+    cheats: Cheats,
+    #[visit(skip)]
+    telemetry: Telemetry,
+    // State of the player from the previous level, it is applied to the player of the next level.
+    #[visit(skip)]
+    player_persistent_data: Option<PlayerPersistentData>,
+    // Total playtime of current game in seconds, it is stored in the headers of saved games.
+    #[visit(skip)]
+    playtime: f32,
+    #[visit(skip)]
+    launch_options: LaunchOptions,
+    // Difficulty of current game, it is stored in the level when the level is saved.
+    #[visit(skip)]
+    difficulty: Difficulty,
+    // Statistics of the previous levels of the run, they are continued by the next level.
+    #[visit(skip)]
+    run_statistics: Option<RunStatistics>,
+    #[visit(skip)]
+    achievements: Achievements,
+    #[visit(skip)]
+    achievement_toast: AchievementToast,
+    #[visit(skip)]
+    status_effect_display: StatusEffectDisplay,
+    // The level is frozen while any menu is shown or by the photo mode, see `set_paused`.
+    #[visit(skip)]
+    paused: bool,
+    #[visit(skip)]
+    free_camera: FreeCamera,
+    #[visit(skip)]
+    profiler: Profiler,
+    #[visit(skip)]
+    input: Input,
+    // Actions of the game itself, the player has its own state, so it could replay demos.
+    #[visit(skip)]
+    actions: ActionState,
+    // Menus are navigated with a gamepad regardless of the control scheme.
+    #[visit(skip)]
+    navigation_input: NavigationInput,
 }
 
 impl Default for Game {
     fn default() -> Self {
+        Self::with_config(Config::load())
+    }
+}
+
+#[repr(u16)]
+pub enum CollisionGroups {
+    ActorCapsule = 1 << 0,
+    All = u16::MAX,
+}
+
+#[derive(Clone, Debug)]
+pub struct MessageSender {
+    sender: Sender<Message>,
+}
+
+impl MessageSender {
+    pub fn send(&self, message: Message) {
+        Log::verify(self.sender.send(message))
+    }
+}
+
+impl Game {
+    fn with_config(config: Config) -> Self {
         let (tx, rx) = mpsc::channel();
         Self {
-            config: Config::load(),
+            config,
             menu: Default::default(),
+            pause_menu: Default::default(),
             level: None,
             debug_text: Default::default(),
             debug_string: Default::default(),
@@ -158,44 +272,136 @@ impl Default for Game {
             journal_display: Default::default(),
             highlighter: Default::default(),
             font: Default::default(),
+            headless: false,
+            rng: Default::default(),
+            level_source: None,
+            demo: None,
+            pending_demo: None,
+            console: Default::default(),
+            cheats: Default::default(),
+            telemetry: Default::default(),
+            player_persistent_data: None,
+            playtime: 0.0,
+            launch_options: Default::default(),
+            difficulty: Default::default(),
+            run_statistics: None,
+            achievements: Default::default(),
+            achievement_toast: Default::default(),
+            status_effect_display: Default::default(),
+            paused: false,
+            free_camera: Default::default(),
+            profiler: Default::default(),
+            input: Default::default(),
+            actions: Default::default(),
+            navigation_input: Default::default(),
         }
     }
-}
 
-#[repr(u16)]
-pub enum CollisionGroups {
-    ActorCapsule = 1 << 0,
-    All = u16::MAX,
-}
+    /// Creates the game that does not load any menus and does not expect a window to exist. Used
+    /// by [`simulation::Simulation`]. The settings of the user are neither read nor saved, see
+    /// [`Config::in_memory`].
+    pub fn headless() -> Self {
+        Self {
+            headless: true,
+            ..Self::with_config(Config::in_memory())
+        }
+    }
 
-#[derive(Clone, Debug)]
-pub struct MessageSender {
-    sender: Sender<Message>,
-}
+    /// Applies the options that define how the game starts. Must be called before the plugin is
+    /// initialized.
+    pub fn with_launch_options(mut self, options: LaunchOptions) -> Self {
+        if let Some(config) = options.config.as_ref() {
+            self.config = Config::load_from(config);
+        }
+        if let Some(seed) = options.seed {
+            self.set_seed(seed);
+        }
+        self.launch_options = options;
+        self
+    }
 
-impl MessageSender {
-    pub fn send(&self, message: Message) {
-        Log::verify(self.sender.send(message))
+    pub fn is_headless(&self) -> bool {
+        self.headless
+    }
+
+    pub fn level(&self) -> Option<&Level> {
+        self.level.as_ref()
+    }
+
+    /// Returns the generator that must be used for every random decision in the game.
+    pub fn rng(&self) -> &GameRng {
+        &self.rng
+    }
+
+    /// Writes a telemetry event produced by the given closure. The closure is called only if the
+    /// telemetry is enabled and there is a level.
+    pub fn record_telemetry<F, E>(&self, event: F)
+    where
+        F: FnOnce() -> E,
+        E: Into<Option<TelemetryEvent>>,
+    {
+        if let Some(level) = self.level.as_ref() {
+            self.telemetry.record(level, event);
+        }
+    }
+
+    /// Difficulty of current game.
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Counts the event in the statistics of current level.
+    pub fn record_statistics(&self, event: StatisticsEvent) {
+        self.message_sender.send(Message::RecordStatistics(event));
+    }
+
+    pub fn record_achievement_event(&self, event: AchievementEvent) {
+        self.message_sender
+            .send(Message::RecordAchievementEvent(event));
+    }
+
+    /// Achievements are not given during demo playback and when cheats are enabled.
+    fn achievements_enabled(&self) -> bool {
+        !matches!(self.demo, Some(DemoState::Playback(_)))
+            && !self.cheats.god_mode
+            && !self.cheats.noclip
+    }
+
+    /// Restarts the random number generator with the given seed.
+    pub fn set_seed(&mut self, seed: u64) {
+        Log::info(format!("Using random seed {seed}"));
+        self.rng = GameRng::new(seed);
     }
-}
 
-impl Game {
     fn handle_ui_message(
         &mut self,
         ctx: &mut PluginContext,
         message: &UiMessage,
         ui_handle: Handle<UserInterface>,
     ) -> GameResult {
+        self.console.handle_ui_message(message);
+
         if let Some(menu) = self.menu.as_mut() {
             menu.handle_ui_message(
                 ctx,
                 ui_handle,
                 message,
                 &mut self.config,
+                &self.achievements,
                 &self.message_sender,
             )?;
         };
 
+        if let Some(pause_menu) = self.pause_menu.as_mut() {
+            pause_menu.handle_ui_message(
+                ctx,
+                ui_handle,
+                message,
+                &mut self.config,
+                &self.message_sender,
+            )?;
+        }
+
         if let Some(death_screen) = self.death_screen.take() {
             self.death_screen =
                 death_screen.handle_ui_message(ctx, ui_handle, message, &self.message_sender);
@@ -255,8 +461,8 @@ impl Game {
     }
 
     fn debug_render(&mut self, context: &mut PluginContext) -> GameResult {
-        if let Some(level) = self.level.as_mut() {
-            level.debug_draw(context)?;
+        if let Some(level) = self.level.as_ref() {
+            level.debug_draw(&self.config.debug_layers, context)?;
         }
         Ok(())
     }
@@ -266,25 +472,126 @@ impl Game {
             .build(&mut context.user_interfaces.first_mut().build_ctx());
     }
 
-    pub fn save_game(&mut self, path: &Path, context: &mut PluginContext) -> VisitResult {
-        if let Some(level) = self.level.as_mut() {
-            let mut visitor = Visitor::new();
+    pub fn save_game(&mut self, path: &Path, context: &mut PluginContext) -> Result<(), String> {
+        let Some(level) = self.level.as_ref() else {
+            return Ok(());
+        };
+
+        let header = SavedGameHeader::new(
+            level.path.clone(),
+            self.player_location_name(context),
+            self.playtime,
+        );
 
-            context.scenes[level.scene].save("Scene", &mut visitor)?;
-            level.visit("Level", &mut visitor)?;
+        // The free camera is not a part of the level, it must not get into the saved game.
+        self.deactivate_free_camera(context);
 
-            // Debug output
+        let mut visitor = Visitor::new();
+        self.write_saved_game_payload(&mut visitor, context)
+            .map_err(|e| e.to_string())?;
+
+        if self.config.dump_saved_games {
             let mut debug_path = path.to_path_buf();
             debug_path.set_extension("txt");
-            visitor.save_ascii_to_file(debug_path)?;
-            visitor.save_binary_to_file(path)
-        } else {
-            Ok(())
+            Log::verify(visitor.save_ascii_to_file(debug_path));
+        }
+
+        let payload = visitor.save_binary_to_vec().map_err(|e| e.to_string())?;
+        SavedGame::new(header, payload).save(path)?;
+
+        self.record_telemetry(|| TelemetryEvent::GameSaved {
+            path: path.to_path_buf(),
+        });
+        self.telemetry.flush();
+
+        Ok(())
+    }
+
+    fn write_saved_game_payload(
+        &mut self,
+        visitor: &mut Visitor,
+        context: &mut PluginContext,
+    ) -> VisitResult {
+        if let Some(level) = self.level.as_mut() {
+            context.scenes[level.scene].save("Scene", visitor)?;
+            level.visit("Level", visitor)?;
+        }
+        self.rng.visit("Rng", visitor)
+    }
+
+    /// Reads the level state and the random number generator from the payload of a saved game.
+    /// Saves made before the generator was added to the game do not have its state, a new seed is
+    /// used for them.
+    pub fn read_saved_game_payload(payload: &[u8]) -> Result<(Level, GameRng), VisitError> {
+        let mut visitor = Visitor::load_from_memory(payload)?;
+        let mut level = Level::default();
+        level.visit("Level", &mut visitor)?;
+        let mut rng = GameRng::default();
+        if rng.visit("Rng", &mut visitor).is_err() {
+            rng = GameRng::default();
+            Log::warn(format!(
+                "Saved game does not have random generator state, using seed {}.",
+                rng.seed()
+            ));
         }
+        Ok((level, rng))
+    }
+
+    /// Checks whether the game could be saved right now: there must be a level with a live player.
+    fn can_save(&self, ctx: &PluginContext) -> bool {
+        self.level.as_ref().is_some_and(|level| {
+            ctx.scenes.try_get(level.scene).is_ok_and(|scene| {
+                scene
+                    .graph
+                    .try_get_script_component_of::<Character>(level.player)
+                    .is_ok_and(|player| !player.is_dead(&scene.graph))
+            })
+        })
+    }
+
+    /// Returns the name of the closest point of interest to the player, or the name of the level
+    /// if there are no points of interest.
+    fn player_location_name(&self, ctx: &PluginContext) -> String {
+        let Some(level) = self.level.as_ref() else {
+            return Default::default();
+        };
+
+        let level_name = level
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let Ok(scene) = ctx.scenes.try_get(level.scene) else {
+            return level_name;
+        };
+        let graph = &scene.graph;
+        let Ok(player) = graph.try_get_script_component_of::<Character>(level.player) else {
+            return level_name;
+        };
+        let position = player.position(graph);
+
+        level
+            .pois
+            .iter()
+            .filter_map(|poi| graph.try_get(*poi).ok())
+            .min_by(|a, b| {
+                let a = a.global_position().metric_distance(&position);
+                let b = b.global_position().metric_distance(&position);
+                a.total_cmp(&b)
+            })
+            .map(|poi| poi.name_owned())
+            .unwrap_or(level_name)
     }
 
     fn destroy_level(&mut self, context: &mut PluginContext) {
+        self.stop_demo();
+
+        self.deactivate_free_camera(context);
+
         if let Some(ref mut level) = self.level.take() {
+            self.telemetry.flush();
+            self.achievements.flush();
             level.destroy(context);
             Log::info("Current level destroyed!");
         }
@@ -311,6 +618,9 @@ impl Game {
         if let Some(menu) = self.menu.as_mut() {
             menu.set_visible(ctx, false)?;
         }
+        if let Some(pause_menu) = self.pause_menu.as_mut() {
+            pause_menu.set_visible(ctx, false)?;
+        }
         Ok(())
     }
 
@@ -319,35 +629,89 @@ impl Game {
         result: SceneLoaderResult,
         ctx: &mut PluginContext,
     ) -> GameResult {
-        let SceneLoaderOutput {
-            payload: scene,
-            data,
-            ..
-        } = result?;
+        let scene = match result {
+            Ok(SceneLoaderOutput { payload: scene, .. }) => scene,
+            Err(err) => {
+                return self.on_level_loading_failed(format!("Unable to load level: {err}"), ctx)
+            }
+        };
 
         let scene = ctx.scenes.add(scene);
 
-        if let Some(highlighter) = self.highlighter.as_mut() {
-            highlighter.borrow_mut().scene_handle = scene;
+        let mut level = Level::from_existing_scene(
+            44100,
+            &mut ctx.scenes[scene],
+            scene,
+            self.message_sender.clone(),
+            self.config.sound.clone(),
+            ctx.resource_manager.clone(),
+        );
+        if let Some(source) = self.level_source.as_ref() {
+            level.path = source.path().to_path_buf();
         }
 
-        if let Ok(mut visitor) = Visitor::load_from_memory(&data) {
-            let mut level = Level::default();
-            if level.visit("Level", &mut visitor).is_ok() {
-                // Means that we're loading a saved game.
-                level.scene = scene;
-                level.resolve(ctx, self.message_sender.clone());
-                self.level = Some(level);
-            } else {
-                self.level = Some(Level::from_existing_scene(
-                    44100,
-                    &mut ctx.scenes[scene],
-                    scene,
-                    self.message_sender.clone(),
-                    self.config.sound.clone(),
-                    ctx.resource_manager.clone(),
-                ));
+        self.on_level_ready(level, false, ctx)
+    }
+
+    fn on_saved_game_loaded(
+        &mut self,
+        result: Result<(Scene, Vec<u8>), VisitError>,
+        ctx: &mut PluginContext,
+    ) -> GameResult {
+        let (scene, payload) = match result {
+            Ok(result) => result,
+            Err(err) => {
+                return self
+                    .on_level_loading_failed(format!("Unable to load saved game: {err}"), ctx)
+            }
+        };
+
+        let (mut level, rng) = match Self::read_saved_game_payload(&payload) {
+            Ok(state) => state,
+            Err(err) => {
+                return self.on_level_loading_failed(
+                    format!("Saved game is corrupted, unable to read the level state: {err}"),
+                    ctx,
+                )
+            }
+        };
+
+        level.scene = ctx.scenes.add(scene);
+        level.resolve(ctx, self.message_sender.clone());
+        self.rng = rng;
+
+        self.on_level_ready(level, true, ctx)
+    }
+
+    fn on_level_ready(
+        &mut self,
+        mut level: Level,
+        from_save: bool,
+        ctx: &mut PluginContext,
+    ) -> GameResult {
+        if from_save {
+            self.difficulty = level.difficulty;
+            // Saved games made before the statistics were added.
+            if level.statistics.levels.is_empty() {
+                level.statistics.start_level(level.path.clone());
             }
+        } else {
+            level.difficulty = self.difficulty;
+            level.statistics = self.run_statistics.take().unwrap_or_default();
+            level.statistics.start_level(level.path.clone());
+        }
+
+        if let Some(highlighter) = self.highlighter.as_mut() {
+            highlighter.borrow_mut().scene_handle = level.scene;
+        }
+
+        self.level = Some(level);
+
+        self.record_telemetry(|| TelemetryEvent::LevelLoaded { from_save });
+
+        if !from_save {
+            self.message_sender
+                .send(Message::Autosave(AutosaveTrigger::LevelLoaded));
         }
 
         self.set_menu_visible(false, ctx)?;
@@ -359,13 +723,36 @@ impl Game {
             menu.sync_to_model(ctx, true)?;
         }
 
+        if let Some(demo) = self.pending_demo.take() {
+            self.start_demo(demo);
+        }
+
         Log::info("Level was loaded successfully!");
 
         Ok(())
     }
 
+    fn on_level_loading_failed(&mut self, error: String, ctx: &mut PluginContext) -> GameResult {
+        Log::err(error);
+
+        self.pending_demo = None;
+        self.level_source = None;
+        ctx.user_interfaces
+            .first()
+            .send(self.loading_screen.root, WidgetMessage::Visibility(false));
+        self.set_menu_visible(true, ctx)?;
+        if let Some(menu) = self.menu.as_mut() {
+            menu.sync_to_model(ctx, false)?;
+        }
+
+        Ok(())
+    }
+
     pub fn load_level(&mut self, path: PathBuf, ctx: &mut PluginContext) -> GameResult {
+        self.player_persistent_data = self.capture_player_persistent_data(ctx);
+        self.run_statistics = self.capture_statistics(ctx);
         self.before_load_level(ctx)?;
+        self.level_source = Some(DemoSource::level(&path));
         ctx.load_scene(path, true, |result, game: &mut Game, ctx| {
             game.on_level_loaded(result, ctx)
         });
@@ -373,13 +760,175 @@ impl Game {
     }
 
     pub fn load_game(&mut self, path: PathBuf, ctx: &mut PluginContext) -> GameResult {
+        // Saved game is read and migrated before destroying current level, so the game could
+        // continue if the save is broken.
+        let saved_game = match SavedGame::load(&path) {
+            Ok(saved_game) => saved_game,
+            Err(err) => {
+                Log::err(format!("Unable to load a saved game: {err}"));
+                return Ok(());
+            }
+        };
+
+        // Saved game already contains the player and the statistics.
+        self.player_persistent_data = None;
+        self.run_statistics = None;
         self.before_load_level(ctx)?;
-        ctx.load_scene(path, false, |result, game: &mut Game, ctx| {
-            game.on_level_loaded(result, ctx)
-        });
+        self.level_source = match DemoSource::saved_game(&path) {
+            Ok(source) => Some(source),
+            Err(err) => {
+                Log::err(err);
+                None
+            }
+        };
+        self.playtime = saved_game.header.playtime;
+
+        let serialization_context = ctx.serialization_context.clone();
+        let dyn_type_constructors = ctx.dyn_type_constructors.clone();
+        let resource_manager = ctx.resource_manager.clone();
+        ctx.task_pool.spawn_plugin_task(
+            async move {
+                let loader = {
+                    let mut visitor = Visitor::load_from_memory(&saved_game.payload)?;
+                    SceneLoader::load(
+                        "Scene",
+                        serialization_context,
+                        dyn_type_constructors,
+                        resource_manager,
+                        &mut visitor,
+                        Some(path),
+                    )?
+                };
+                Ok::<_, VisitError>((loader.finish().await, saved_game.payload))
+            },
+            |result, game: &mut Game, ctx| game.on_saved_game_loaded(result, ctx),
+        );
+        Ok(())
+    }
+
+    /// Loads a level from scratch, without the state of the player from the current level.
+    fn load_fresh_level(&mut self, path: PathBuf, ctx: &mut PluginContext) -> GameResult {
+        self.load_level(path, ctx)?;
+        self.player_persistent_data = None;
+        self.run_statistics = None;
+        self.playtime = 0.0;
+        Ok(())
+    }
+
+    /// Returns the statistics of the run with finished statistics of current level.
+    fn capture_statistics(&self, ctx: &PluginContext) -> Option<RunStatistics> {
+        let level = self.level.as_ref()?;
+        let graph = &ctx.scenes.try_get(level.scene).ok()?.graph;
+        let mut statistics = level.statistics.clone();
+        if let Some(current) = statistics.current_mut() {
+            current.time = level.time;
+            current.secrets_total = graph
+                .linear_iter()
+                .filter(|node| {
+                    node.try_get_script::<Trigger>()
+                        .is_some_and(|trigger| trigger.is_secret())
+                })
+                .count() as u32;
+        }
+        Some(statistics)
+    }
+
+    fn capture_player_persistent_data(&self, ctx: &PluginContext) -> Option<PlayerPersistentData> {
+        let level = self.level.as_ref()?;
+        let graph = &ctx.scenes.try_get(level.scene).ok()?.graph;
+        let player = graph.try_get_script_of::<Player>(level.player).ok()?;
+        if player.is_dead(graph) {
+            return None;
+        }
+        Some(player.persistent_data(graph))
+    }
+
+    fn load_source(&mut self, source: &DemoSource, ctx: &mut PluginContext) -> GameResult {
+        match source {
+            // Demos always start from the very beginning of a level.
+            DemoSource::Level(path) => self.load_fresh_level(path.clone(), ctx),
+            DemoSource::SavedGame { path, .. } => self.load_game(path.clone(), ctx),
+        }
+    }
+
+    fn record_demo(&mut self, path: PathBuf, ctx: &mut PluginContext) -> GameResult {
+        let Some(source) = self.level_source.clone() else {
+            Log::err("Unable to record a demo: there is no level to record it on!");
+            return Ok(());
+        };
+
+        // Every recording starts with a new seed from the very beginning of the level.
+        let seed = GameRng::default().seed();
+        self.set_seed(seed);
+        self.load_source(&source, ctx)?;
+        self.pending_demo = Some(DemoState::Recording(DemoRecorder::new(
+            path,
+            Demo::new(source, seed, self.difficulty),
+        )));
+
+        Ok(())
+    }
+
+    fn play_demo(&mut self, path: &Path, ctx: &mut PluginContext) -> GameResult {
+        let demo = match Demo::load(path).and_then(|demo| demo.validate_source().map(|_| demo)) {
+            Ok(demo) => demo,
+            Err(err) => {
+                Log::err(format!("Unable to play a demo: {err}"));
+                return Ok(());
+            }
+        };
+
+        self.set_seed(demo.header.seed);
+        self.difficulty = demo.header.difficulty;
+        self.load_source(&demo.header.source.clone(), ctx)?;
+        self.pending_demo = Some(DemoState::Playback(DemoPlayer::new(demo)));
+
         Ok(())
     }
 
+    fn start_demo(&mut self, demo: DemoState) {
+        if let DemoState::Playback(player) = &demo {
+            if self.level_source.as_ref() != Some(player.source()) {
+                Log::err(format!(
+                    "Demo was recorded on {}, but {} was loaded. Playback refused!",
+                    player.source().path().display(),
+                    self.level_source
+                        .as_ref()
+                        .map(|source| source.path().display().to_string())
+                        .unwrap_or_default(),
+                ));
+                return;
+            }
+            Log::info("Demo playback started.");
+        } else {
+            Log::info("Demo recording started.");
+        }
+
+        self.demo = Some(demo);
+    }
+
+    fn stop_demo(&mut self) {
+        match self.demo.take() {
+            Some(DemoState::Recording(recorder)) => recorder.finish(),
+            Some(DemoState::Playback(_)) => Log::info("Demo playback stopped."),
+            None => (),
+        }
+    }
+
+    pub fn is_demo_playing(&self) -> bool {
+        matches!(self.demo, Some(DemoState::Playback(_)))
+    }
+
+    pub fn is_demo_recording(&self) -> bool {
+        matches!(self.demo, Some(DemoState::Recording(_)))
+    }
+
+    /// Adds a source of input that is polled every frame, it could be used to drive the game
+    /// without any devices (in headless runs, for example).
+    pub fn add_input_source(&mut self, source: Box<dyn InputSource>) {
+        self.input.add_source(source);
+    }
+
     pub fn set_menu_visible(&mut self, visible: bool, context: &mut PluginContext) -> GameResult {
         if let Some(menu) = self.menu.as_mut() {
             menu.set_visible(context, visible)?;
@@ -391,14 +940,104 @@ impl Game {
         self.menu
             .as_ref()
             .is_some_and(|menu| menu.is_visible(context))
+            || self
+                .pause_menu
+                .as_ref()
+                .is_some_and(|pause_menu| pause_menu.is_visible(context))
             || self.death_screen.is_some()
             || self.final_screen.is_some()
     }
 
+    /// Returns `true` if the level is frozen. Scripts must not advance their timers in this case.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Freezes or unfreezes current level. A level behind a menu is disabled, so it does not
+    /// update its physics, scripts and animations. In photo mode the level must stay visible, so
+    /// only its physics is stopped here, the scripts check [`Self::is_paused`] and the free camera
+    /// stops the animations, particles and lifetimes of the nodes. The sound context is paused
+    /// separately, so every sound of the level resumes exactly where it was.
+    fn set_paused(&mut self, paused: bool, ctx: &mut PluginContext) {
+        let hidden = paused && self.is_any_menu_visible(ctx);
+        if let Some(level) = self.level.as_ref() {
+            if let Ok(scene) = ctx.scenes.try_get_mut(level.scene) {
+                scene.enabled.set_value_silent(!hidden);
+                scene.graph.physics.enabled.set_value_silent(!paused);
+                scene.graph.sound_context.state().pause(paused);
+            }
+        }
+        self.paused = paused;
+    }
+
+    pub fn free_camera(&self) -> &FreeCamera {
+        &self.free_camera
+    }
+
+    /// Switches the view between the player's camera and the free camera.
+    pub fn toggle_free_camera(&mut self, ctx: &mut PluginContext) -> GameResult {
+        if self.free_camera.is_active() {
+            self.deactivate_free_camera(ctx);
+            return Ok(());
+        }
+
+        let Some(level) = self.level.as_ref() else {
+            return Ok(());
+        };
+        let graph = &mut ctx.scenes.try_get_mut(level.scene)?.graph;
+        let player = graph.try_get_script_of::<Player>(level.player)?;
+        let camera = graph
+            .try_get_script_of::<CameraController>(player.camera_controller)?
+            .camera();
+        self.free_camera.activate(graph, camera)
+    }
+
+    fn deactivate_free_camera(&mut self, ctx: &mut PluginContext) {
+        if let Some(level) = self.level.as_ref() {
+            if let Ok(scene) = ctx.scenes.try_get_mut(level.scene) {
+                self.free_camera
+                    .deactivate(&mut scene.graph, ctx.user_interfaces.first());
+            }
+        }
+    }
+
+    /// Toggles the pause menu, Escape also hides the main menu if it was opened over the level.
+    fn toggle_pause_menu(&mut self, ctx: &mut PluginContext) -> GameResult {
+        if self.menu.as_ref().is_some_and(|menu| menu.is_visible(ctx)) {
+            return self.set_menu_visible(false, ctx);
+        }
+
+        if let Some(pause_menu) = self.pause_menu.as_mut() {
+            let visible = pause_menu.is_visible(ctx);
+            pause_menu.set_visible(ctx, !visible)?;
+        }
+        Ok(())
+    }
+
+    fn on_focus_lost(&mut self, ctx: &mut PluginContext) -> GameResult {
+        if self.level.is_some() && !self.is_any_menu_visible(ctx) {
+            if let Some(pause_menu) = self.pause_menu.as_mut() {
+                pause_menu.set_visible(ctx, true)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn update(&mut self, ctx: &mut PluginContext) -> GameResult {
         let debug = true;
 
-        self.config.save_if_needed();
+        self.input.poll();
+        for input_event in self.input.polled_events().to_vec() {
+            self.process_game_input(input_event, ctx)?;
+        }
+
+        let menu_visible = self.level.is_some() && self.is_any_menu_visible(ctx);
+        self.set_paused(menu_visible || self.free_camera.is_world_frozen(), ctx);
+
+        // Options changed in the pause menu are saved when the game is resumed.
+        if !self.paused {
+            self.config.save_if_needed();
+        }
 
         if let GraphicsContext::Initialized(ref graphics_context) = ctx.graphics_context {
             let window = &graphics_context.window;
@@ -421,9 +1060,16 @@ impl Game {
         );
 
         if let Some(ref mut level) = self.level {
-            if let Some(menu) = self.menu.as_ref() {
-                let enabled = !menu.is_visible(ctx);
-                ctx.scenes[level.scene].enabled.set_value_silent(enabled);
+            if !self.paused {
+                level.time += ctx.dt;
+                self.playtime += ctx.dt;
+            }
+
+            if !menu_visible {
+                if let Ok(scene) = ctx.scenes.try_get_mut(level.scene) {
+                    self.free_camera
+                        .update(&mut scene.graph, ctx.dt, &self.config.controls, ui)?;
+                }
             }
         }
 
@@ -431,8 +1077,14 @@ impl Game {
             menu.update(ctx)?;
         }
 
-        self.weapon_display.update(ctx.dt);
-        self.item_display.update(ctx.dt);
+        if let Some(pause_menu) = self.pause_menu.as_ref() {
+            pause_menu.update(ctx)?;
+        }
+
+        if !self.paused {
+            self.weapon_display.update(ctx.dt);
+            self.item_display.update(ctx.dt);
+        }
 
         for scene in ctx.scenes.iter_mut() {
             scene
@@ -444,14 +1096,48 @@ impl Game {
                 .set_gain(self.config.sound.master_volume);
         }
 
+        if let Some(DemoState::Playback(player)) = self.demo.as_ref() {
+            if player.is_finished() {
+                self.stop_demo();
+            }
+        }
+
         self.handle_messages(ctx)?;
 
+        for definition in self.achievements.take_newly_unlocked() {
+            self.achievement_toast.push(definition);
+        }
+        self.achievement_toast
+            .update(ctx.dt, ctx.user_interfaces.first());
+        self.achievement_toast
+            .set_visible(!self.free_camera.hides_hud(), ctx.user_interfaces.first());
+
+        let player_status_effects = self
+            .level
+            .as_ref()
+            .filter(|_| !self.free_camera.hides_hud())
+            .and_then(|level| {
+                ctx.scenes
+                    .try_get(level.scene)
+                    .ok()?
+                    .graph
+                    .try_get_script_component_of::<Character>(level.player)
+                    .ok()
+            })
+            .map(|player| &player.status_effects);
+        self.status_effect_display
+            .sync_to_model(player_status_effects, ctx.user_interfaces.first());
+
         self.update_statistics(0.0, ctx);
 
-        // <<<<<<<<< ENABLE THIS FOR DEBUGGING
-        if false {
-            self.debug_render(ctx)?;
-        }
+        self.debug_render(ctx)?;
+
+        let graph = self
+            .level
+            .as_ref()
+            .and_then(|level| ctx.scenes.try_get(level.scene).ok())
+            .map(|scene| &scene.graph);
+        self.profiler.end_frame(graph, ctx.user_interfaces.first());
 
         Ok(())
     }
@@ -459,13 +1145,55 @@ impl Game {
     fn handle_messages(&mut self, context: &mut PluginContext) -> GameResult {
         while let Ok(message) = self.message_receiver.try_recv() {
             match &message {
-                Message::StartNewGame => {
-                    self.load_level(Level::ARRIVAL_PATH.into(), context)?;
+                Message::StartNewGame(difficulty) => {
+                    self.difficulty = *difficulty;
+                    self.load_fresh_level(Level::ARRIVAL_PATH.into(), context)?;
                 }
                 Message::SaveGame(path) => match self.save_game(path, context) {
                     Ok(_) => Log::info("Successfully saved"),
                     Err(e) => Log::err(format!("Failed to make a save at {path:?}, reason: {e}")),
                 },
+                Message::QuickSave => {
+                    if self.can_save(context) {
+                        let path = saved_game::quicksave_path();
+                        match self.save_game(&path, context) {
+                            Ok(_) => Log::info("Quicksave was made"),
+                            Err(e) => Log::err(format!("Failed to make a quicksave, reason: {e}")),
+                        }
+                    }
+                }
+                Message::QuickLoad => {
+                    let path = saved_game::quicksave_path();
+                    if path.exists() {
+                        self.load_game(path, context)?;
+                    } else {
+                        Log::warn("There is no quicksave to load!");
+                    }
+                }
+                Message::Autosave(trigger) => {
+                    // Demos and headless runs must not touch saved games.
+                    if self.config.autosave.is_triggered_by(*trigger)
+                        && !self.headless
+                        && self.demo.is_none()
+                        && self.can_save(context)
+                    {
+                        let path = saved_game::next_autosave_path(self.config.autosave.slots);
+                        match self.save_game(&path, context) {
+                            Ok(_) => Log::info(format!("Autosave was made to {path:?}")),
+                            Err(e) => Log::err(format!("Failed to make an autosave, reason: {e}")),
+                        }
+                    }
+                }
+                Message::LoadLastSave => match saved_game::latest_saved_game() {
+                    Some(path) => self.load_game(path, context)?,
+                    None => {
+                        Log::warn("There are no saved games to load!");
+                        // The death screen is closed already, there must be something to show.
+                        if self.level.is_none() {
+                            self.set_menu_visible(true, context)?;
+                        }
+                    }
+                },
                 Message::LoadGame(path) => {
                     self.load_game(path.clone(), context)?;
                 }
@@ -477,25 +1205,71 @@ impl Game {
                     self.running = false;
                 }
                 Message::EndMatch => {
+                    if let Some(level) = self.level.as_ref() {
+                        let graph = &context.scenes[level.scene].graph;
+                        if let Ok(player) = graph.try_get_script_of::<Player>(level.player) {
+                            let position = player.position(graph);
+                            self.telemetry.record(level, || TelemetryEvent::PlayerDied {
+                                position: position.into(),
+                            });
+                        }
+                    }
+                    let statistics = self.capture_statistics(context).unwrap_or_default();
                     self.destroy_level(context);
-                    context.load_ui("data/ui/death_screen.ui", |result, game: &mut Game, ctx| {
-                        game.death_screen = Some(DeathScreen::new(result?.payload, ctx));
-                        Ok(())
-                    });
+                    context.load_ui(
+                        "data/ui/death_screen.ui",
+                        move |result, game: &mut Game, ctx| {
+                            game.death_screen = Some(DeathScreen::new(
+                                result?.payload,
+                                ctx,
+                                &statistics,
+                                game.font.clone(),
+                            ));
+                            Ok(())
+                        },
+                    );
                     if let Some(menu) = self.menu.as_mut() {
                         menu.sync_to_model(context, false)?;
                     }
                 }
                 Message::EndGame => {
+                    let statistics = self.capture_statistics(context).unwrap_or_default();
                     self.destroy_level(context);
-                    context.load_ui("data/ui/final_screen.ui", |result, game: &mut Game, ctx| {
-                        game.final_screen = Some(FinalScreen::new(result?.payload, ctx));
-                        Ok(())
-                    });
+                    context.load_ui(
+                        "data/ui/final_screen.ui",
+                        move |result, game: &mut Game, ctx| {
+                            game.final_screen = Some(FinalScreen::new(
+                                result?.payload,
+                                ctx,
+                                &statistics,
+                                game.font.clone(),
+                            ));
+                            Ok(())
+                        },
+                    );
                     if let Some(menu) = self.menu.as_mut() {
                         menu.sync_to_model(context, false)?;
                     }
                 }
+                Message::RecordStatistics(event) => {
+                    if let Some(statistics) = self
+                        .level
+                        .as_mut()
+                        .and_then(|level| level.statistics.current_mut())
+                    {
+                        statistics.register(event.clone());
+                    }
+                    if let Some(event) = AchievementEvent::from_statistics(event) {
+                        if self.achievements_enabled() {
+                            self.achievements.handle_event(&event);
+                        }
+                    }
+                }
+                Message::RecordAchievementEvent(event) => {
+                    if self.achievements_enabled() {
+                        self.achievements.handle_event(event);
+                    }
+                }
                 Message::SetMusicVolume(volume) => {
                     self.config.sound.music_volume = *volume;
                     if let Some(menu) = self.menu.as_mut() {
@@ -528,6 +1302,27 @@ impl Game {
                 Message::SetMasterVolume(volume) => {
                     self.config.sound.master_volume = *volume;
                 }
+                Message::SetLanguage(language) => {
+                    self.config.language.clone_from(language);
+                    localization::set_language(language);
+                    self.weapon_display.on_language_changed();
+                    if let Some(menu) = self.menu.as_mut() {
+                        menu.on_language_changed(context, &self.config)?;
+                    }
+                    if let Some(pause_menu) = self.pause_menu.as_mut() {
+                        pause_menu.on_language_changed(context, &self.config)?;
+                    }
+                }
+                Message::QuitToMainMenu => {
+                    self.destroy_level(context);
+                    if let Some(pause_menu) = self.pause_menu.as_mut() {
+                        pause_menu.set_visible(context, false)?;
+                    }
+                    if let Some(menu) = self.menu.as_mut() {
+                        menu.set_visible(context, true)?;
+                        menu.sync_to_model(context, false)?;
+                    }
+                }
                 Message::ToggleMainMenu => {
                     if let Some(menu) = self.menu.as_mut() {
                         menu.set_visible(context, true)?;
@@ -543,6 +1338,15 @@ impl Game {
                         self.journal_display.sync_to_model(&player_ref.journal);
                     }
                 }
+                Message::RecordDemo(path) => {
+                    self.record_demo(path.clone(), context)?;
+                }
+                Message::PlayDemo(path) => {
+                    self.play_demo(path, context)?;
+                }
+                Message::StopDemo => {
+                    self.stop_demo();
+                }
                 Message::Play2DSound { path, gain } => {
                     if let Ok(buffer) = fyrox::core::futures::executor::block_on(
                         context.resource_manager.request::<SoundBuffer>(path),
@@ -604,15 +1408,14 @@ impl Game {
 
         ui.send(
             self.debug_text,
-            WidgetMessage::Visibility(self.config.show_debug_info),
+            WidgetMessage::Visibility(self.config.show_debug_info && !self.free_camera.hides_hud()),
         );
     }
 
     fn process_dispatched_event(&mut self, event: &Event<()>) {
         if let Event::WindowEvent { event, .. } = event {
             if let Some(event) = translate_event(event) {
-                self.journal_display
-                    .process_os_event(&event, &self.config.controls);
+                self.journal_display.process_os_event(&event);
             }
         }
     }
@@ -625,6 +1428,10 @@ impl Game {
         height: f32,
     ) {
         self.loading_screen.resize(ui, width, height);
+        self.achievement_toast.resize(ui, width, height);
+        self.status_effect_display.resize(ui, width, height);
+        self.free_camera.resize(ui, width, height);
+        self.profiler.resize(ui, width, height);
         self.create_highlighter(graphics_context, width as usize, height as usize);
     }
 
@@ -656,6 +1463,80 @@ impl Game {
         }
     }
 
+    /// Handles the actions of the game itself (quick saves, the photo mode, the journal). The player
+    /// processes the same input events on its own.
+    fn process_game_input(
+        &mut self,
+        input_event: InputEvent,
+        context: &mut PluginContext,
+    ) -> GameResult {
+        let mut actions = Vec::new();
+        self.actions
+            .process(input_event, &self.config.controls, &mut actions);
+
+        // The free camera must not move while something else takes the input.
+        if self.console.is_visible() || self.is_any_menu_visible(context) {
+            self.free_camera.release_input();
+        } else {
+            self.free_camera.process_input(input_event, &actions);
+        }
+
+        // Start works like Escape, the menus are navigated with the rest of the buttons.
+        if let InputEvent::Button {
+            button: ControlButton::Gamepad(GamepadButton::Start),
+            pressed: true,
+        } = input_event
+        {
+            if self.level.is_some() && !self.console.is_visible() {
+                self.toggle_pause_menu(context)?;
+            }
+        } else if !self.console.is_visible() {
+            let command = self.navigation_input.process(input_event);
+            if let Some(menu) = self.menu.as_mut().filter(|menu| menu.is_visible(context)) {
+                menu.process_gamepad_input(context, input_event, command, &mut self.config)?;
+            } else if let Some(pause_menu) = self
+                .pause_menu
+                .as_mut()
+                .filter(|pause_menu| pause_menu.is_visible(context))
+            {
+                pause_menu.process_gamepad_input(
+                    context,
+                    input_event,
+                    command,
+                    &mut self.config,
+                )?;
+            }
+        }
+
+        for action in actions {
+            let ActionEvent::Pressed(action) = action else {
+                continue;
+            };
+            let console_visible = self.console.is_visible();
+            match action {
+                Action::QuickSave if self.level.is_some() && !console_visible => {
+                    self.message_sender.send(Message::QuickSave);
+                }
+                Action::QuickLoad if !console_visible => {
+                    self.message_sender.send(Message::QuickLoad);
+                }
+                Action::PhotoMode
+                    if self.level.is_some()
+                        && !console_visible
+                        && !self.is_any_menu_visible(context) =>
+                {
+                    self.toggle_free_camera(context)?;
+                }
+                Action::CursorUp | Action::CursorDown => {
+                    self.journal_display.process_action(action);
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn process_input_event(
         &mut self,
         event: &Event<()>,
@@ -663,22 +1544,44 @@ impl Game {
     ) -> GameResult {
         self.process_dispatched_event(event);
 
+        let mut input_events = Vec::new();
+        self.input.translate_os_event(event, &mut input_events);
+        for input_event in input_events {
+            self.process_game_input(input_event, context)?;
+        }
+
         if let Event::WindowEvent {
             event: WindowEvent::KeyboardInput { event: input, .. },
             ..
         } = event
         {
             if let ElementState::Pressed = input.state {
-                if input.physical_key == KeyCode::Escape && self.level.is_some() {
-                    self.set_menu_visible(!self.is_any_menu_visible(context), context)?;
+                if input.physical_key == KeyCode::Backquote {
+                    self.toggle_console(context);
+                } else if input.physical_key == KeyCode::Escape && self.console.is_visible() {
+                    self.toggle_console(context);
+                    return Ok(());
+                } else if input.physical_key == KeyCode::Escape && self.level.is_some() {
+                    self.toggle_pause_menu(context)?;
                 }
             }
         }
 
+        if let Some(line) = self
+            .console
+            .process_input_event(event, context.user_interfaces.first())
+        {
+            self.execute_console_command(&line, context);
+        }
+
         if let Some(menu) = self.menu.as_mut() {
             menu.process_input_event(context, event, &mut self.config)?;
         }
 
+        if let Some(pause_menu) = self.pause_menu.as_mut() {
+            pause_menu.process_input_event(context, event, &mut self.config)?;
+        }
+
         Ok(())
     }
 }
@@ -747,13 +1650,11 @@ impl Plugin for Game {
     }
 
     fn init(&mut self, scene_path: Option<&str>, mut ctx: PluginContext) -> GameResult {
+        localization::set_language(&self.config.language);
+
         ctx.user_interfaces
             .add(UserInterface::new(Vector2::repeat(100.0)));
 
-        if let Some(scene_path) = scene_path {
-            self.load_level(PathBuf::from(scene_path), &mut ctx)?;
-        }
-
         let font = ctx
             .resource_manager
             .request::<Font>(Path::new("data/ui/SquaresBold.ttf"));
@@ -765,17 +1666,60 @@ impl Plugin for Game {
 
         let item_display = ItemDisplay::new(font.clone());
         let journal_display = JournalDisplay::new();
+        let achievement_toast = AchievementToast::new(
+            font.clone(),
+            &mut ctx.user_interfaces.first_mut().build_ctx(),
+        );
+        let status_effect_display = StatusEffectDisplay::new(
+            font.clone(),
+            &mut ctx.user_interfaces.first_mut().build_ctx(),
+        );
+        let free_camera = FreeCamera::new(
+            font.clone(),
+            &mut ctx.user_interfaces.first_mut().build_ctx(),
+        );
+        let profiler = Profiler::new(
+            font.clone(),
+            &mut ctx.user_interfaces.first_mut().build_ctx(),
+        );
+        // Headless runs must not touch the profile of the player.
+        let achievements = if self.headless {
+            Achievements::default()
+        } else {
+            Achievements::new(Box::new(LocalAchievementBackend::load(
+                LocalAchievementBackend::profile_path(),
+            )))
+        };
 
-        ctx.load_ui("data/ui/main_menu.ui", |result, game: &mut Game, ctx| {
-            game.menu = Some(Menu::new(result?.payload, ctx, game.font.clone()));
-            Ok(())
-        });
+        let pause_menu = (!self.headless).then(|| PauseMenu::new(&mut ctx, font.clone()));
+
+        #[cfg_attr(not(feature = "gamepad"), allow(unused_mut))]
+        let mut input = Input::default();
+        #[cfg(feature = "gamepad")]
+        if !self.headless {
+            match gamepad::Gamepad::with_backend() {
+                Ok(gamepad) => input.add_source(Box::new(gamepad)),
+                Err(err) => Log::warn(format!("Gamepads are not available. Reason: {err}")),
+            }
+        }
+
+        if !self.headless {
+            ctx.load_ui("data/ui/main_menu.ui", |result, game: &mut Game, ctx| {
+                game.menu = Some(Menu::new(result?.payload, ctx, game.font.clone()));
+                // A level could be requested before the menu is loaded, see launch options.
+                if game.level.is_some() || game.level_source.is_some() {
+                    game.set_menu_visible(false, ctx)?;
+                }
+                Ok(())
+            });
+        }
 
         *self = Game {
             config: self.config.clone(),
             loading_screen: LoadingScreen::new(&mut ctx.user_interfaces.first_mut().build_ctx()),
             running: true,
             menu: None,
+            pause_menu,
             death_screen: None,
             final_screen: None,
             debug_text: Handle::NONE,
@@ -788,9 +1732,44 @@ impl Plugin for Game {
             message_sender,
             highlighter: None,
             font,
+            headless: self.headless,
+            rng: self.rng.clone(),
+            level_source: None,
+            demo: None,
+            pending_demo: None,
+            console: Console::default(),
+            cheats: Cheats::default(),
+            telemetry: Telemetry::new(&self.config.telemetry),
+            player_persistent_data: None,
+            playtime: 0.0,
+            launch_options: self.launch_options.clone(),
+            difficulty: self.launch_options.difficulty.unwrap_or_default(),
+            run_statistics: None,
+            achievements,
+            achievement_toast,
+            status_effect_display,
+            paused: false,
+            free_camera,
+            profiler,
+            input,
+            actions: Default::default(),
+            navigation_input: Default::default(),
         };
 
         self.create_debug_ui(&mut ctx);
+        self.console = Console::new(
+            self.font.clone(),
+            &mut ctx.user_interfaces.first_mut().build_ctx(),
+        );
+
+        if let Some(saved_game) = self.launch_options.saved_game.clone() {
+            self.load_game(saved_game, &mut ctx)?;
+        } else if let Some(scene_path) = scene_path {
+            self.load_fresh_level(PathBuf::from(scene_path), &mut ctx)?;
+        } else if self.launch_options.skip_menu {
+            self.message_sender
+                .send(Message::StartNewGame(self.difficulty));
+        }
 
         Ok(())
     }
@@ -812,14 +1791,21 @@ impl Plugin for Game {
             match event {
                 WindowEvent::CloseRequested => {
                     self.destroy_level(&mut ctx);
+                    self.config.save_if_needed();
                     ctx.loop_controller.exit();
                 }
-                WindowEvent::Resized(new_size) => self.on_window_resized(
-                    ctx.user_interfaces.first(),
-                    ctx.graphics_context,
-                    new_size.width as f32,
-                    new_size.height as f32,
-                ),
+                WindowEvent::Resized(new_size) => {
+                    self.on_window_resized(
+                        ctx.user_interfaces.first(),
+                        ctx.graphics_context,
+                        new_size.width as f32,
+                        new_size.height as f32,
+                    );
+                    if let Some(pause_menu) = self.pause_menu.as_ref() {
+                        pause_menu.resize(&ctx, new_size.width as f32, new_size.height as f32);
+                    }
+                }
+                WindowEvent::Focused(false) => self.on_focus_lost(&mut ctx)?,
                 _ => (),
             }
         }
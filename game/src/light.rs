@@ -1,3 +1,4 @@
+use crate::{profiler, Game};
 use fyrox::plugin::error::GameResult;
 use fyrox::{
     core::{
@@ -6,7 +7,6 @@ use fyrox::{
         type_traits::prelude::*,
         visitor::{Visit, VisitResult, Visitor},
     },
-    rand::thread_rng,
     script::{ScriptContext, ScriptTrait},
 };
 
@@ -18,6 +18,11 @@ pub struct AnimatedLight {
 
 impl ScriptTrait for AnimatedLight {
     fn on_update(&mut self, context: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("AnimatedLight");
+        if context.plugins.get::<Game>().is_paused() {
+            return Ok(());
+        }
+
         self.timer -= context.dt;
 
         if self.timer < 0.0 {
@@ -25,7 +30,8 @@ impl ScriptTrait for AnimatedLight {
             let new_visibility = !node.visibility();
             node.set_visibility(new_visibility);
 
-            self.timer = thread_rng().gen_range(0.1..0.5);
+            let mut rng = context.plugins.get::<Game>().rng();
+            self.timer = rng.gen_range(0.1..0.5);
         }
         Ok(())
     }
@@ -0,0 +1,176 @@
+//! Localization of player-facing text. Every language has its own string table in
+//! `data/localization/<code>.ron`, where the code is a language code (`en`, `ru`, etc.). Text is
+//! referenced by keys (`menu.new_game`, `item.medkit.name`, etc.) and resolved with [`tr`].
+//!
+//! English is the fallback language: a key that is missing in the current language is taken from
+//! the English table. Missing keys are reported to the log only once, so the log is not flooded by
+//! text that is updated every frame.
+
+use fyrox::{core::log::Log, fxhash::FxHashSet};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::{LazyLock, Mutex},
+};
+
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+const FOLDER: &str = "data/localization";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct StringTable {
+    /// Name of the language in this language, it is shown in the language selector.
+    pub name: String,
+    pub strings: HashMap<String, String>,
+}
+
+impl StringTable {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| {
+            format!(
+                "Unable to open string table {}. Reason: {e}",
+                path.display()
+            )
+        })?;
+        ron::de::from_reader(file).map_err(|e| {
+            format!(
+                "Unable to read string table {}. Reason: {e}",
+                path.display()
+            )
+        })
+    }
+}
+
+fn table_path(language: &str) -> PathBuf {
+    Path::new(FOLDER).join(format!("{language}.ron"))
+}
+
+fn load_table(language: &str) -> StringTable {
+    StringTable::load(&table_path(language)).unwrap_or_else(|err| {
+        Log::err(err);
+        StringTable::default()
+    })
+}
+
+struct Localization {
+    language: String,
+    current: StringTable,
+    fallback: StringTable,
+    reported: FxHashSet<String>,
+}
+
+impl Localization {
+    fn new() -> Self {
+        Self {
+            language: FALLBACK_LANGUAGE.to_string(),
+            current: Default::default(),
+            fallback: load_table(FALLBACK_LANGUAGE),
+            reported: Default::default(),
+        }
+    }
+
+    fn translate(&mut self, key: &str) -> String {
+        if key.is_empty() {
+            return String::new();
+        }
+
+        if let Some(text) = self.current.strings.get(key) {
+            return text.clone();
+        }
+
+        let text = self.fallback.strings.get(key);
+
+        if self.reported.insert(key.to_string()) {
+            match text {
+                Some(_) if self.language != FALLBACK_LANGUAGE => Log::warn(format!(
+                    "There is no text for key {key} in language {}, English text is used.",
+                    self.language
+                )),
+                Some(_) => (),
+                None => Log::warn(format!("There is no text for key {key}!")),
+            }
+        }
+
+        // The key is shown as is, it is better than nothing.
+        text.cloned().unwrap_or_else(|| key.to_string())
+    }
+}
+
+static LOCALIZATION: LazyLock<Mutex<Localization>> =
+    LazyLock::new(|| Mutex::new(Localization::new()));
+
+fn localization() -> std::sync::MutexGuard<'static, Localization> {
+    LOCALIZATION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Switches current language. An empty code selects the fallback language.
+pub fn set_language(language: &str) {
+    let language = if language.is_empty() {
+        FALLBACK_LANGUAGE
+    } else {
+        language
+    };
+
+    let mut localization = localization();
+    if localization.language == language {
+        return;
+    }
+
+    localization.current = if language == FALLBACK_LANGUAGE {
+        Default::default()
+    } else {
+        load_table(language)
+    };
+    localization.language = language.to_string();
+    localization.reported.clear();
+
+    Log::info(format!("Language was set to {language}"));
+}
+
+pub fn language() -> String {
+    localization().language.clone()
+}
+
+/// Returns codes and names of every language that has a string table, sorted by code.
+pub fn languages() -> Vec<(String, String)> {
+    let mut languages = Vec::new();
+    if let Ok(dir_iterator) = std::fs::read_dir(FOLDER) {
+        for entry in dir_iterator.flatten() {
+            let path = entry.path();
+            if path.extension() != Some(OsStr::new("ron")) {
+                continue;
+            }
+            let Some(code) = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+            match StringTable::load(&path) {
+                Ok(table) => languages.push((code, table.name)),
+                Err(err) => Log::err(err),
+            }
+        }
+    }
+    languages.sort();
+    languages
+}
+
+/// Returns the text for the given key in current language.
+pub fn tr(key: &str) -> String {
+    localization().translate(key)
+}
+
+/// Same as [`tr`], but also replaces `{name}` placeholders in the text with the given values.
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+    let mut text = tr(key);
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), value);
+    }
+    text
+}
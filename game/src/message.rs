@@ -3,22 +3,52 @@
 //! required entity. This is very effective decoupling mechanism that works perfectly with
 //! strict ownership rules of Rust.
 
+use crate::{
+    achievements::AchievementEvent, config::AutosaveTrigger, difficulty::Difficulty,
+    statistics::StatisticsEvent,
+};
 use std::path::PathBuf;
 
 #[derive(Debug)]
 pub enum Message {
-    Play2DSound { path: PathBuf, gain: f32 },
+    Play2DSound {
+        path: PathBuf,
+        gain: f32,
+    },
     SaveGame(PathBuf),
     LoadGame(PathBuf),
-    StartNewGame,
+    StartNewGame(Difficulty),
+    QuickSave,
+    QuickLoad,
+    /// Writes the next autosave slot, if autosaves are enabled for the trigger.
+    Autosave(AutosaveTrigger),
+    /// Loads the most recent saved game of any kind.
+    LoadLastSave,
     QuitGame,
-    LoadLevel { path: PathBuf },
+    LoadLevel {
+        path: PathBuf,
+    },
+    /// Destroys current level and shows the main menu.
+    QuitToMainMenu,
     ToggleMainMenu,
     EndMatch,
     EndGame,
     SyncJournal,
+    /// Counts an event in the statistics of current level.
+    RecordStatistics(StatisticsEvent),
+    /// Passes an event to the achievements, statistics events are passed automatically.
+    RecordAchievementEvent(AchievementEvent),
+    /// Restarts current level (or saved game) and records player input to a demo file at the
+    /// given path.
+    RecordDemo(PathBuf),
+    /// Loads the level (or saved game) the demo was recorded from and plays the demo.
+    PlayDemo(PathBuf),
+    /// Stops recording or playback of a demo.
+    StopDemo,
     // Sound-related messages.
     SetMusicVolume(f32),
     SetUseHrtf(bool),
     SetMasterVolume(f32),
+    /// Switches the language of the game, the argument is the code of the language.
+    SetLanguage(String),
 }
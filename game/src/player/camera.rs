@@ -1,4 +1,4 @@
-use crate::Player;
+use crate::{profiler, rng::GameRng, Game, Player};
 use fyrox::scene::collider::Collider;
 use fyrox::{
     core::{
@@ -12,7 +12,6 @@ use fyrox::{
     },
     graph::SceneGraph,
     plugin::error::GameResult,
-    rand,
     scene::{
         graph::physics::{Intersection, RayCastOptions},
         node::Node,
@@ -63,6 +62,7 @@ impl CameraController {
             origin: ray_origin,
             dir,
         };
+        profiler::count("Raycasts");
         scene.graph.physics.cast_ray(
             RayCastOptions {
                 ray_origin: Point3::from(ray.origin),
@@ -87,16 +87,15 @@ impl CameraController {
         Ok(())
     }
 
-    fn update_shake(&mut self, dt: f32) {
+    fn update_shake(&mut self, dt: f32, mut rng: &GameRng) {
         let xy_range = -0.027..0.027;
         let z_range = 0.01..0.05;
         if self.shake_timer > 0.0 {
             self.shake_timer -= dt;
-            let mut rnd = rand::thread_rng();
             self.target_shake_offset = Vector3::new(
-                rnd.gen_range(xy_range.clone()),
-                rnd.gen_range(xy_range),
-                rnd.gen_range(z_range),
+                rng.gen_range(xy_range.clone()),
+                rng.gen_range(xy_range),
+                rng.gen_range(z_range),
             );
         } else {
             self.shake_timer = 0.0;
@@ -108,6 +107,7 @@ impl CameraController {
 
 impl ScriptTrait for CameraController {
     fn on_update(&mut self, context: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("CameraController");
         let player = context
             .scene
             .graph
@@ -120,7 +120,10 @@ impl ScriptTrait for CameraController {
         self.target_camera_offset.y = 0.0;
         self.target_camera_offset.z = if is_aiming { 0.2 } else { 0.8 };
 
-        self.update_shake(context.dt);
+        let game = context.plugins.get::<Game>();
+        if !game.is_paused() {
+            self.update_shake(context.dt, game.rng());
+        }
         self.check_occlusion(self.ignorable_collider, context.scene)?;
 
         self.target_camera_offset += self.shake_offset;
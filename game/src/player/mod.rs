@@ -1,16 +1,25 @@
 use crate::{
+    aim_assist::{self, AimAssistTarget},
+    bot::Bot,
     character::{Character, CharacterMessage, CharacterMessageData},
-    control_scheme::ControlButton,
-    door::{door_mut, DoorContainer},
+    control_scheme::{Action, Axis, ControlScheme},
+    demo::{DemoState, InputEvent},
+    door::{door_mut, Door, DoorContainer},
     elevator::call_button::{CallButton, CallButtonKind},
     gui::inventory::InventoryInterface,
     gui::journal::Journal,
+    input::{ActionEvent, ActionState, MouseFilter},
     inventory::Inventory,
-    level::hit_box::HitBoxMessage,
+    level::hit_box::{HitBox, HitBoxMessage},
     level::item::ItemAction,
     message::Message,
     player::state_machine::{StateMachine, StateMachineInput},
+    profiler,
+    rng::GameRng,
     sound::SoundManager,
+    statistics::StatisticsEvent,
+    status_effect::StatusEffects,
+    telemetry::TelemetryEvent,
     utils::{self},
     weapon::{
         projectile::Projectile, weapon_ref, CombatWeaponKind, Weapon, WeaponMessage,
@@ -21,7 +30,7 @@ use crate::{
 use fyrox::{
     asset::manager::ResourceManager,
     core::{
-        algebra::{UnitQuaternion, Vector2, Vector3},
+        algebra::{Point3, UnitQuaternion, Vector2, Vector3},
         color::Color,
         futures::executor::block_on,
         log::Log,
@@ -33,10 +42,9 @@ use fyrox::{
         visitor::prelude::*,
     },
     engine::GraphicsContext,
-    event::{DeviceEvent, ElementState, Event, MouseScrollDelta, WindowEvent},
+    event::Event,
     fxhash::FxHashMap,
     graph::SceneGraph,
-    keyboard::PhysicalKey,
     plugin::error::{GameError, GameResult},
     renderer::ui_renderer::UiRenderInfo,
     resource::{
@@ -45,7 +53,7 @@ use fyrox::{
     },
     scene::{
         animation::{absm, absm::prelude::*, prelude::*},
-        graph::Graph,
+        graph::{physics::RayCastOptions, Graph},
         mesh::Mesh,
         node::Node,
         sprite::Sprite,
@@ -107,12 +115,14 @@ impl RequiredWeapon {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct PlayerPersistentData {
     pub inventory: Inventory,
-    pub current_weapon: usize,
+    /// Resource of the selected weapon, weapons without a resource are not carried over.
+    pub current_weapon: Option<ModelResource>,
     pub weapons: Vec<ModelResource>,
     pub hit_box_health: FxHashMap<Handle<Node>, f32>,
+    pub status_effects: StatusEffects,
 }
 
 #[derive(Visit, Reflect, Debug, TypeUuidProvider, ComponentProvider)]
@@ -176,6 +186,14 @@ pub struct Player {
     #[reflect(hidden)]
     controller: InputController,
 
+    #[visit(skip)]
+    #[reflect(hidden)]
+    input: ActionState,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    mouse_filter: MouseFilter,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub script_message_sender: Option<ScriptMessageSender>,
@@ -191,6 +209,8 @@ impl Default for Player {
             inventory_display: Default::default(),
             model: Default::default(),
             controller: Default::default(),
+            input: Default::default(),
+            mouse_filter: Default::default(),
             spine: Default::default(),
             hips: Default::default(),
             model_yaw: SmoothAngle {
@@ -289,6 +309,8 @@ impl Clone for Player {
             weapon_change_direction: self.weapon_change_direction.clone(),
             journal: Default::default(),
             controller: Default::default(),
+            input: Default::default(),
+            mouse_filter: Default::default(),
             animation_player: self.animation_player,
             target_yaw: self.target_yaw,
             machine: self.machine,
@@ -311,6 +333,11 @@ impl Clone for Player {
 }
 
 impl Player {
+    /// Analog movement below the threshold is ignored, so worn out sticks do not move the player.
+    const MOVE_AXIS_THRESHOLD: f32 = 0.3;
+    /// Turning speed at the full deflection of a look axis, in radians per second.
+    const LOOK_AXIS_SPEED: f32 = 3.0;
+
     pub fn persistent_data(&self, graph: &Graph) -> PlayerPersistentData {
         PlayerPersistentData {
             inventory: self.inventory.clone(),
@@ -320,7 +347,16 @@ impl Player {
                     (graph[node_handle].original_handle_in_resource(), *hb.health)
                 })
                 .collect::<FxHashMap<_, _>>(),
-            current_weapon: self.current_weapon,
+            status_effects: {
+                // The entities that caused the effects stay on the previous level.
+                let mut status_effects = self.status_effects.clone();
+                status_effects.forget_dealers();
+                status_effects
+            },
+            current_weapon: self
+                .weapons
+                .get(self.current_weapon)
+                .and_then(|w| graph[*w].root_resource()),
             weapons: self
                 .weapons
                 .iter()
@@ -329,6 +365,58 @@ impl Player {
         }
     }
 
+    /// Restores the state of the player that was captured on a previous level. Weapons are
+    /// re-instantiated using messages, so they will appear on the next frame.
+    pub fn apply_persistent_data(
+        &mut self,
+        data: PlayerPersistentData,
+        graph: &mut Graph,
+        self_handle: Handle<Node>,
+        script_message_sender: &ScriptMessageSender,
+    ) {
+        self.inventory = data.inventory;
+        self.status_effects = data.status_effects;
+
+        let hit_boxes = self
+            .hit_box_iter(graph)
+            .map(|(hit_box, _)| hit_box)
+            .collect::<Vec<_>>();
+        for hit_box in hit_boxes {
+            let original_handle = graph[hit_box].original_handle_in_resource();
+            if let Some(health) = data.hit_box_health.get(&original_handle) {
+                if let Ok(hit_box) = graph.try_get_script_component_of_mut::<HitBox>(hit_box) {
+                    hit_box.health.set_value_and_mark_modified(*health);
+                }
+            }
+        }
+
+        for weapon in data.weapons.iter() {
+            let has_weapon = self
+                .weapons
+                .iter()
+                .any(|w| graph[*w].root_resource().as_ref() == Some(weapon));
+            if !has_weapon {
+                script_message_sender.send_to_target(
+                    self_handle,
+                    CharacterMessage {
+                        character: self_handle,
+                        data: CharacterMessageData::AddWeapon(weapon.clone()),
+                    },
+                );
+            }
+        }
+
+        if let Some(current_weapon) = data.current_weapon {
+            script_message_sender.send_to_target(
+                self_handle,
+                CharacterMessage {
+                    character: self_handle,
+                    data: CharacterMessageData::SelectWeapon(current_weapon),
+                },
+            );
+        }
+    }
+
     fn check_items(
         &mut self,
         game: &mut Game,
@@ -392,7 +480,8 @@ impl Player {
         if self.controller.action {
             for &door_handle in &door_container.doors {
                 let door = door_mut(door_handle, &mut scene.graph);
-                let close_enough = self_position.metric_distance(&door.initial_position()) < 1.25;
+                let close_enough = self_position.metric_distance(&door.initial_position())
+                    < Door::INTERACTION_RADIUS;
                 if close_enough {
                     door.try_open(Some(&self.inventory));
                 }
@@ -473,6 +562,7 @@ impl Player {
         is_walking: bool,
         has_ground_contact: bool,
         sound_manager: &SoundManager,
+        rng: &GameRng,
     ) -> GameResult {
         let absm = scene.graph.try_get(self.machine)?;
 
@@ -564,6 +654,7 @@ impl Player {
                 }
             } else if event.name == StateMachine::HIT_STARTED_SIGNAL {
                 self.melee_attack_context = Some(Default::default());
+                self.stamina.spend_on_melee();
             } else if event.name == StateMachine::HIT_ENDED_SIGNAL {
                 self.melee_attack_context = None;
             }
@@ -575,7 +666,7 @@ impl Player {
 
                 if is_walking && has_ground_contact {
                     self.character
-                        .footstep_ray_check(begin, scene, sound_manager);
+                        .footstep_ray_check(begin, scene, sound_manager, rng);
                 }
             }
         }
@@ -597,7 +688,7 @@ impl Player {
         Ok(())
     }
 
-    fn update_velocity(&mut self, scene: &mut Scene, dt: f32) -> GameResult {
+    fn update_velocity(&mut self, scene: &mut Scene, dt: f32, noclip: bool) -> GameResult {
         let transform = &scene.graph.try_get(self.model)?.global_transform();
 
         if let Some(root_motion) = self
@@ -612,12 +703,46 @@ impl Player {
                 .scale(1.0 / dt);
         }
 
+        // Noclip flies in the direction of the camera and ignores gravity and collisions.
+        let noclip_vertical_velocity = if noclip {
+            let camera_controller = scene
+                .graph
+                .try_get_script_component_of::<CameraController>(self.camera_controller)?;
+            let look = scene
+                .graph
+                .try_get(camera_controller.camera())?
+                .look_vector()
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default();
+            let direction = if self.controller.walk_forward {
+                1.0
+            } else if self.controller.walk_backward {
+                -1.0
+            } else {
+                0.0
+            };
+            Some(direction * look.y * self.velocity.xz().norm())
+        } else {
+            None
+        };
+
+        let capsule = scene.graph.try_get_mut(self.capsule_collider)?;
+        if capsule.is_sensor() != noclip {
+            capsule.set_is_sensor(noclip);
+        }
+
         let body = &mut scene.graph[self.body];
 
+        let gravity_scale = if noclip { 0.0 } else { 1.0 };
+        if body.gravity_scale() != gravity_scale {
+            body.set_gravity_scale(gravity_scale);
+        }
         body.set_ang_vel(Default::default());
         body.set_lin_vel(Vector3::new(
             self.velocity.x,
-            if self.velocity.y > 0.001 {
+            if let Some(vertical_velocity) = noclip_vertical_velocity {
+                vertical_velocity
+            } else if self.velocity.y > 0.001 {
                 self.velocity.y
             } else {
                 body.lin_vel().y
@@ -658,9 +783,11 @@ impl Player {
             is_aiming: self.controller.aim && !self.character.weapons.is_empty(),
             run_factor: self.run_factor,
             is_dead: self.is_dead(&scene.graph),
-            // TODO: Handle stun properly.
-            should_be_stunned: false,
-            melee_attack: self.controller.shoot && !self.controller.aim,
+            should_be_stunned: self.status_effects.is_stunned(),
+            melee_attack: self.controller.shoot
+                && !self.controller.aim
+                && !self.status_effects.is_stunned(),
+            melee_attack_speed: self.stamina.melee_speed(),
             machine: self.machine,
             weapon_kind,
             toss_grenade: self.controller.toss_grenade,
@@ -721,7 +848,10 @@ impl Player {
         dt: f32,
         elapsed_time: f32,
         script_message_sender: &ScriptMessageSender,
+        game: &Game,
     ) -> GameResult {
+        let mut rng = game.rng();
+
         self.v_recoil.update(dt);
         self.h_recoil.update(dt);
 
@@ -744,17 +874,26 @@ impl Player {
                     .set_position(ammo_indicator_offset);
 
                 let current_weapon = weapon_ref(current_weapon_handle, &scene.graph);
-                if self.controller.shoot && current_weapon.can_shoot(elapsed_time) {
-                    let ammo_per_shot = *current_weapon.ammo_consumption_per_shot;
+                if self.controller.shoot
+                    && !self.status_effects.is_stunned()
+                    && current_weapon.can_shoot(elapsed_time)
+                {
+                    let ammo_per_shot =
+                        current_weapon.player_ammo_consumption_per_shot(game.difficulty());
 
                     // A weapon could have infinite ammo, in this case ammo item is not specified.
+                    // Some shots spend no ammo (see the difficulty), but there still must be some.
                     let enough_ammo = current_weapon.ammo_item.as_ref().is_none_or(|ammo_item| {
-                        self.inventory
-                            .try_extract_exact_items(ammo_item, ammo_per_shot)
-                            == ammo_per_shot
+                        self.inventory.item_count(ammo_item) > 0
+                            && self
+                                .inventory
+                                .try_extract_exact_items(ammo_item, ammo_per_shot)
+                                == ammo_per_shot
                     });
 
                     if enough_ammo {
+                        game.record_statistics(StatisticsEvent::ShotFired);
+
                         script_message_sender.send_to_target(
                             current_weapon_handle,
                             WeaponMessage {
@@ -767,9 +906,9 @@ impl Player {
 
                         if *current_weapon.shake_camera_on_shot {
                             self.v_recoil
-                                .set_target(current_weapon.gen_v_recoil_angle());
+                                .set_target(current_weapon.gen_v_recoil_angle(&mut rng));
                             self.h_recoil
-                                .set_target(current_weapon.gen_h_recoil_angle());
+                                .set_target(current_weapon.gen_h_recoil_angle(&mut rng));
 
                             scene
                                 .graph
@@ -778,6 +917,11 @@ impl Player {
                                 )?
                                 .request_shake_camera();
                         }
+
+                        scene
+                            .graph
+                            .try_get_script_component_of_mut::<Weapon>(current_weapon_handle)?
+                            .on_player_shot(game.difficulty());
                     }
                 }
             } else {
@@ -838,7 +982,9 @@ impl Player {
     fn is_running(&self, scene: &Scene) -> bool {
         !self.is_dead(&scene.graph)
             && self.controller.run
+            && self.stamina.can_run()
             && !self.controller.aim
+            && !self.status_effects.is_stunned()
             && !self.state_machine.is_stunned(scene, self.animation_player)
     }
 
@@ -885,6 +1031,21 @@ impl Player {
         Ok(())
     }
 
+    /// Hides the displays attached to the player if the photo mode asks for it.
+    fn update_photo_mode(&self, ctx: &mut ScriptContext) -> GameResult {
+        if ctx.plugins.get::<Game>().free_camera.hides_displays() {
+            for display in [
+                self.weapon_display,
+                self.inventory_display,
+                self.journal_display,
+                self.item_display,
+            ] {
+                ctx.scene.graph.try_get_mut(display)?.set_visibility(false);
+            }
+        }
+        Ok(())
+    }
+
     fn render_offscreen_ui(&mut self, context: &mut ScriptContext) {
         if let GraphicsContext::Initialized(ref mut graphics_context) = context.graphics_context {
             let renderer = &mut graphics_context.renderer;
@@ -903,135 +1064,56 @@ impl Player {
             }
         }
     }
-}
-
-impl ScriptTrait for Player {
-    fn on_init(&mut self, ctx: &mut ScriptContext) -> GameResult {
-        if let Some(item_display_prefab) = self.item_display_prefab.as_ref() {
-            self.item_display = item_display_prefab.instantiate(ctx.scene);
-        }
-
-        if let Some(grenade_item) = self.grenade_item.deref().clone() {
-            self.inventory.add_item(&grenade_item, 10);
-        }
-
-        let level = ctx.plugins.get_mut::<Game>().level.as_mut().unwrap();
-
-        level.actors.push(ctx.handle);
-        // Also register player in special variable to speed up access.
-        level.player = ctx.handle;
-
-        Ok(())
-    }
-
-    fn on_start(&mut self, ctx: &mut ScriptContext) -> GameResult {
-        self.character.on_start(ctx);
-
-        let game = ctx.plugins.get::<Game>();
-
-        ctx.message_dispatcher
-            .subscribe_to::<CharacterMessage>(ctx.handle);
-        ctx.message_dispatcher
-            .subscribe_to::<WeaponMessage>(ctx.handle);
-        ctx.message_dispatcher
-            .subscribe_to::<HitBoxMessage>(ctx.handle);
-
-        self.script_message_sender = Some(ctx.message_sender.clone());
-        self.state_machine = StateMachine::new(self.machine, &ctx.scene.graph).unwrap();
-        self.inventory_gui = InventoryInterface::new();
 
-        self.resolve(
-            ctx.scene,
-            game.weapon_display.render_target.clone(),
-            self.inventory_gui.render_target.clone(),
-            game.item_display.render_target.clone(),
-            game.journal_display.render_target.clone(),
-        )?;
-
-        Ok(())
-    }
-
-    fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) -> GameResult {
-        if let Some(level) = ctx.plugins.get_mut::<Game>().level.as_mut() {
-            level.player = Handle::NONE;
-
-            if let Some(position) = level.actors.iter().position(|a| *a == ctx.node_handle) {
-                level.actors.remove(position);
-            }
+    /// Applies OS-independent input event to the player. Input events come either from the OS
+    /// or from a demo that is being played.
+    fn apply_input_event(
+        &mut self,
+        input_event: InputEvent,
+        ctx: &mut ScriptContext,
+    ) -> GameResult {
+        let mut actions = Vec::new();
+        self.input.process(
+            input_event,
+            &ctx.plugins.get::<Game>().config.controls,
+            &mut actions,
+        );
+        for action in actions {
+            self.apply_action(action, ctx);
         }
-
         Ok(())
     }
 
-    fn on_os_event(&mut self, event: &Event<()>, ctx: &mut ScriptContext) -> GameResult {
+    fn apply_action(&mut self, action_event: ActionEvent, ctx: &mut ScriptContext) {
         let game = ctx.plugins.get::<Game>();
         let control_scheme = &game.config.controls;
         let sender = &game.message_sender;
 
-        let button_state = match event {
-            Event::WindowEvent { event, .. } => {
-                if let Some(event) = translate_event(event) {
-                    self.inventory_gui.process_os_event(
-                        &event,
-                        &game.config.controls,
-                        ctx.handle,
-                        ctx.message_sender,
-                    );
-                }
-
-                if let WindowEvent::KeyboardInput { event: input, .. } = event {
-                    if let PhysicalKey::Code(key) = input.physical_key {
-                        Some((ControlButton::Key(key), input.state))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
+        let (action, pressed) = match action_event {
+            ActionEvent::Pressed(action) => (action, true),
+            ActionEvent::Released(action) => (action, false),
+            ActionEvent::Axis { axis, value } => {
+                // The motion is applied once per frame, see `update_analog_input`.
+                self.mouse_filter.add_motion(axis, value);
+                return;
             }
-            Event::DeviceEvent { event, .. } => match event {
-                &DeviceEvent::MouseWheel { delta } => match delta {
-                    MouseScrollDelta::LineDelta(_, y) => {
-                        if y < 0.0 {
-                            Some((ControlButton::WheelDown, ElementState::Pressed))
-                        } else {
-                            Some((ControlButton::WheelUp, ElementState::Pressed))
-                        }
-                    }
-                    MouseScrollDelta::PixelDelta(delta) => {
-                        if delta.y < 0.0 {
-                            Some((ControlButton::WheelDown, ElementState::Pressed))
-                        } else {
-                            Some((ControlButton::WheelUp, ElementState::Pressed))
-                        }
-                    }
-                },
-                &DeviceEvent::Button { button, state } => {
-                    Some((ControlButton::Mouse(button as u16), state))
-                }
-                DeviceEvent::MouseMotion { delta } => {
-                    let mouse_sens = control_scheme.mouse_sens * ctx.dt;
-                    self.target_yaw -= (delta.0 as f32) * mouse_sens;
-                    let pitch_direction = if control_scheme.mouse_y_inverse {
-                        -1.0
-                    } else {
-                        1.0
-                    };
-                    self.target_pitch = (self.target_pitch
-                        + pitch_direction * (delta.1 as f32) * mouse_sens)
-                        .clamp(-90.0f32.to_radians(), 90.0f32.to_radians());
-                    None
-                }
-                _ => None,
-            },
-            _ => None,
         };
 
+        if pressed {
+            self.inventory_gui
+                .process_action(action, ctx.handle, ctx.message_sender);
+        }
+
+        let has_ground_contact = self
+            .has_ground_contact(&ctx.scene.graph)
+            .unwrap_or_default();
+
         let animations_container =
             utils::fetch_animation_container_mut(&mut ctx.scene.graph, self.animation_player);
 
         let jump_anim = animations_container.get(self.state_machine.jump_animation);
-        let can_jump = !jump_anim.is_enabled() || jump_anim.has_ended();
+        let can_jump =
+            (!jump_anim.is_enabled() || jump_anim.has_ended()) && !self.status_effects.is_stunned();
 
         let can_change_weapon = self.weapon_change_direction.is_none()
             && animations_container[self.state_machine.grab_animation].has_ended()
@@ -1046,75 +1128,86 @@ impl ScriptTrait for Player {
 
         let mut weapon_change_direction = None;
 
-        if let Some((button, state)) = button_state {
-            if button == control_scheme.aim.button {
-                self.controller.aim = state == ElementState::Pressed;
-                if state == ElementState::Pressed {
+        match action {
+            Action::Aim => {
+                control_scheme
+                    .activation_mode(action)
+                    .apply(pressed, &mut self.controller.aim);
+                if self.controller.aim {
                     ctx.scene.graph[self.inventory_display].set_visibility(false);
                     ctx.scene.graph[self.journal_display].set_visibility(false);
                 }
-            } else if button == control_scheme.move_forward.button {
-                self.controller.walk_forward = state == ElementState::Pressed;
-            } else if button == control_scheme.move_backward.button {
-                self.controller.walk_backward = state == ElementState::Pressed;
-            } else if button == control_scheme.move_left.button {
-                self.controller.walk_left = state == ElementState::Pressed;
-            } else if button == control_scheme.move_right.button {
-                self.controller.walk_right = state == ElementState::Pressed;
-            } else if button == control_scheme.jump.button {
-                self.controller.jump = state == ElementState::Pressed && can_jump;
-            } else if button == control_scheme.run.button {
-                self.controller.run = state == ElementState::Pressed;
-            } else if button == control_scheme.flash_light.button {
-                if state == ElementState::Pressed {
-                    let enabled = *self.flash_light_enabled;
+            }
+            Action::Jump => {
+                self.controller.jump = pressed && can_jump;
+                if self.controller.jump && has_ground_contact {
+                    self.stamina.spend_on_jump();
+                }
+            }
+            Action::Run => control_scheme
+                .activation_mode(action)
+                .apply(pressed, &mut self.controller.run),
+            Action::FlashLight => {
+                let mut enabled = *self.flash_light_enabled;
+                control_scheme
+                    .activation_mode(action)
+                    .apply(pressed, &mut enabled);
+                if enabled != *self.flash_light_enabled {
                     self.flash_light_enabled
-                        .set_value_and_mark_modified(!enabled);
+                        .set_value_and_mark_modified(enabled);
                 }
-            } else if button == control_scheme.grab_ak47.button && can_change_weapon {
+            }
+            Action::GrabAk47 if can_change_weapon => {
                 if current_weapon_kind != self.ak47_weapon {
                     if let Some(ak47_weapon) = self.ak47_weapon.clone() {
                         weapon_change_direction = Some(RequiredWeapon::Specific(ak47_weapon));
                     }
                 }
-            } else if button == control_scheme.grab_m4.button && can_change_weapon {
+            }
+            Action::GrabM4 if can_change_weapon => {
                 if current_weapon_kind != self.m4_weapon {
                     if let Some(m4_weapon) = self.m4_weapon.clone() {
                         weapon_change_direction = Some(RequiredWeapon::Specific(m4_weapon));
                     }
                 }
-            } else if button == control_scheme.grab_plasma_gun.button && can_change_weapon {
+            }
+            Action::GrabPlasmaGun if can_change_weapon => {
                 if current_weapon_kind != self.plasma_gun_weapon {
                     if let Some(plasma_gun_weapon) = self.plasma_gun_weapon.clone() {
                         weapon_change_direction = Some(RequiredWeapon::Specific(plasma_gun_weapon));
                     }
                 }
-            } else if button == control_scheme.grab_pistol.button && can_change_weapon {
+            }
+            Action::GrabPistol if can_change_weapon => {
                 if current_weapon_kind != self.glock_weapon {
                     if let Some(glock_weapon) = self.glock_weapon.clone() {
                         weapon_change_direction = Some(RequiredWeapon::Specific(glock_weapon));
                     }
                 }
-            } else if button == control_scheme.next_weapon.button {
-                if state == ElementState::Pressed
+            }
+            Action::NextWeapon => {
+                if pressed
                     && self.current_weapon < self.weapons.len().saturating_sub(1)
                     && can_change_weapon
                 {
                     weapon_change_direction = Some(RequiredWeapon::Next);
                 }
-            } else if button == control_scheme.prev_weapon.button {
-                if state == ElementState::Pressed && self.current_weapon > 0 && can_change_weapon {
+            }
+            Action::PrevWeapon => {
+                if pressed && self.current_weapon > 0 && can_change_weapon {
                     weapon_change_direction = Some(RequiredWeapon::Previous);
                 }
-            } else if button == control_scheme.toss_grenade.button {
+            }
+            Action::TossGrenade => {
                 if let Some(grenade_item) = self.grenade_item.as_ref() {
                     if self.inventory.item_count(grenade_item) > 0 {
-                        self.controller.toss_grenade = state == ElementState::Pressed;
+                        self.controller.toss_grenade = pressed;
                     }
                 }
-            } else if button == control_scheme.quick_heal.button {
+            }
+            Action::QuickHeal => {
                 let most_wounded = self.most_wounded_hit_box(&ctx.scene.graph);
-                if state == ElementState::Pressed && most_wounded.is_some() {
+                if pressed && most_wounded.is_some() {
                     let mut min_health = f32::MAX;
                     let mut suitable_item = None;
                     for item in self.inventory.items() {
@@ -1143,27 +1236,19 @@ impl ScriptTrait for Player {
                         }
                     }
                 }
-            } else if button == control_scheme.shoot.button {
-                self.controller.shoot = state == ElementState::Pressed;
-            } else if button == control_scheme.cursor_up.button {
-                self.controller.cursor_up = state == ElementState::Pressed;
-            } else if button == control_scheme.cursor_down.button {
-                self.controller.cursor_down = state == ElementState::Pressed;
-            } else if button == control_scheme.action.button {
-                self.controller.action = state == ElementState::Pressed;
-            } else if button == control_scheme.inventory.button
-                && state == ElementState::Pressed
-                && !self.controller.aim
-            {
+            }
+            Action::Shoot => self.controller.shoot = pressed,
+            Action::CursorUp => self.controller.cursor_up = pressed,
+            Action::CursorDown => self.controller.cursor_down = pressed,
+            Action::Interact => self.controller.action = pressed,
+            Action::Inventory if pressed && !self.controller.aim => {
                 ctx.scene.graph[self.journal_display].set_visibility(false);
 
                 let inventory = &mut ctx.scene.graph[self.inventory_display];
                 let new_visibility = !inventory.visibility();
                 inventory.set_visibility(new_visibility);
-            } else if button == control_scheme.journal.button
-                && state == ElementState::Pressed
-                && !self.controller.aim
-            {
+            }
+            Action::Journal if pressed && !self.controller.aim => {
                 ctx.scene.graph[self.inventory_display].set_visibility(false);
 
                 let journal = &mut ctx.scene.graph[self.journal_display];
@@ -1173,11 +1258,229 @@ impl ScriptTrait for Player {
                     sender.send(Message::SyncJournal);
                 }
             }
+            // Movement is read from the state of the actions, see `update_analog_input`.
+            _ => (),
         }
 
         if let Some(weapon_change_direction) = weapon_change_direction {
             self.weapon_change_direction = weapon_change_direction;
         }
+    }
+
+    /// Finds the target of the aim assist among the bots of the level, see [`aim_assist`].
+    fn find_aim_assist_target(
+        &self,
+        graph: &Graph,
+        actors: &[Handle<Node>],
+    ) -> Option<AimAssistTarget> {
+        let camera_controller = graph
+            .try_get_script_component_of::<CameraController>(self.camera_controller)
+            .ok()?;
+        let camera = graph.try_get(camera_controller.camera()).ok()?;
+        let eye = camera.global_position();
+        let targets = actors.iter().filter_map(|&actor| {
+            let bot = graph.try_get_script_of::<Bot>(actor).ok()?;
+            if bot.is_dead(graph) {
+                return None;
+            }
+            let point = bot.most_vulnerable_point(graph);
+            (eye.metric_distance(&point) <= aim_assist::MAX_DISTANCE
+                && self.can_see(graph, eye, point, bot))
+            .then_some(point)
+        });
+        aim_assist::find_target(eye, camera.look_vector(), targets)
+    }
+
+    fn can_see(&self, graph: &Graph, eye: Vector3<f32>, point: Vector3<f32>, bot: &Bot) -> bool {
+        let direction = point - eye;
+        let distance = direction.norm();
+        let mut intersections = Vec::new();
+        profiler::count("Raycasts");
+        graph.physics.cast_ray(
+            RayCastOptions {
+                ray_origin: Point3::from(eye),
+                ray_direction: direction,
+                max_len: distance,
+                groups: Default::default(),
+                sort_results: true,
+            },
+            &mut intersections,
+        );
+        let Some(obstacle) = intersections
+            .iter()
+            .find(|i| i.collider != self.capsule_collider && !self.has_hit_box(i.collider))
+        else {
+            return true;
+        };
+        obstacle.collider == bot.capsule_collider
+            || bot.has_hit_box(obstacle.collider)
+            || obstacle.toi >= distance - 0.1
+    }
+
+    /// Merges the state of the movement actions with the analog axes (gamepad sticks) and turns
+    /// the camera by the mouse and the look axes, the aim assist works only with the look axes.
+    fn update_analog_input(
+        &mut self,
+        control_scheme: &ControlScheme,
+        aim_assist_target: Option<AimAssistTarget>,
+        dt: f32,
+    ) {
+        let move_x = self.input.axis(Axis::MoveX, control_scheme);
+        let move_y = self.input.axis(Axis::MoveY, control_scheme);
+        let input = &self.input;
+        let controller = &mut self.controller;
+        controller.walk_forward =
+            input.is_active(Action::MoveForward) || move_y > Self::MOVE_AXIS_THRESHOLD;
+        controller.walk_backward =
+            input.is_active(Action::MoveBackward) || move_y < -Self::MOVE_AXIS_THRESHOLD;
+        controller.walk_left =
+            input.is_active(Action::MoveLeft) || move_x < -Self::MOVE_AXIS_THRESHOLD;
+        controller.walk_right =
+            input.is_active(Action::MoveRight) || move_x > Self::MOVE_AXIS_THRESHOLD;
+
+        let look_x = self.input.axis(Axis::LookX, control_scheme);
+        let look_y = self.input.axis(Axis::LookY, control_scheme);
+        let yaw_direction = if control_scheme.mouse_x_inverse {
+            -1.0
+        } else {
+            1.0
+        };
+        let pitch_direction = if control_scheme.mouse_y_inverse {
+            -1.0
+        } else {
+            1.0
+        };
+
+        let mouse = self.mouse_filter.update(
+            control_scheme.mouse_smoothing,
+            control_scheme.mouse_acceleration,
+            dt,
+        );
+        let mouse_sens =
+            control_scheme.mouse_sensitivity(self.controller.aim) * ControlScheme::MOUSE_SCALE;
+        self.target_yaw -= yaw_direction * mouse.x * mouse_sens;
+        self.target_pitch = (self.target_pitch + pitch_direction * mouse.y * mouse_sens)
+            .clamp(-90.0f32.to_radians(), 90.0f32.to_radians());
+
+        let gamepad = &control_scheme.gamepad;
+        let look_sensitivity = if self.controller.aim {
+            gamepad.aim_look_sensitivity
+        } else {
+            gamepad.look_sensitivity
+        };
+        let mut look_speed = Self::LOOK_AXIS_SPEED * look_sensitivity * dt;
+        let mut correction = Vector2::default();
+        if let Some(target) = aim_assist_target {
+            look_speed *= target.slowdown(gamepad.aim_assist_strength);
+            correction = target.magnetism(
+                gamepad.aim_assist_strength,
+                Vector2::new(look_x, look_y).norm(),
+                dt,
+            );
+        }
+        self.target_yaw += correction.x - yaw_direction * look_x * look_speed;
+        self.target_pitch =
+            (self.target_pitch + correction.y + pitch_direction * look_y * look_speed)
+                .clamp(-90.0f32.to_radians(), 90.0f32.to_radians());
+    }
+}
+
+impl ScriptTrait for Player {
+    fn on_init(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        if let Some(item_display_prefab) = self.item_display_prefab.as_ref() {
+            self.item_display = item_display_prefab.instantiate(ctx.scene);
+        }
+
+        if let Some(grenade_item) = self.grenade_item.deref().clone() {
+            self.inventory.add_item(&grenade_item, 10);
+        }
+
+        let level = ctx.plugins.get_mut::<Game>().level.as_mut().unwrap();
+
+        level.actors.push(ctx.handle);
+        // Also register player in special variable to speed up access.
+        level.player = ctx.handle;
+
+        Ok(())
+    }
+
+    fn on_start(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        self.character.on_start(ctx);
+
+        let game = ctx.plugins.get::<Game>();
+
+        ctx.message_dispatcher
+            .subscribe_to::<CharacterMessage>(ctx.handle);
+        ctx.message_dispatcher
+            .subscribe_to::<WeaponMessage>(ctx.handle);
+        ctx.message_dispatcher
+            .subscribe_to::<HitBoxMessage>(ctx.handle);
+
+        self.script_message_sender = Some(ctx.message_sender.clone());
+        self.state_machine = StateMachine::new(self.machine, &ctx.scene.graph).unwrap();
+        self.inventory_gui = InventoryInterface::new();
+
+        self.resolve(
+            ctx.scene,
+            game.weapon_display.render_target.clone(),
+            self.inventory_gui.render_target.clone(),
+            game.item_display.render_target.clone(),
+            game.journal_display.render_target.clone(),
+        )?;
+
+        if let Some(data) = ctx.plugins.get_mut::<Game>().player_persistent_data.take() {
+            self.apply_persistent_data(data, &mut ctx.scene.graph, ctx.handle, ctx.message_sender);
+        }
+
+        Ok(())
+    }
+
+    fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) -> GameResult {
+        if let Some(level) = ctx.plugins.get_mut::<Game>().level.as_mut() {
+            level.player = Handle::NONE;
+
+            if let Some(position) = level.actors.iter().position(|a| *a == ctx.node_handle) {
+                level.actors.remove(position);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_os_event(&mut self, event: &Event<()>, ctx: &mut ScriptContext) -> GameResult {
+        let game = ctx.plugins.get_mut::<Game>();
+
+        // Live input is ignored completely while a demo is playing or the console is open.
+        if game.is_demo_playing() || game.console.is_visible() {
+            return Ok(());
+        }
+
+        // The free camera takes the input, the player stops as if every button was released.
+        if game.free_camera.is_active() {
+            self.controller = Default::default();
+            self.input = Default::default();
+            self.mouse_filter.reset();
+            return Ok(());
+        }
+
+        if let Event::WindowEvent { event, .. } = event {
+            if let Some(event) = translate_event(event) {
+                self.inventory_gui.process_os_event(&event);
+            }
+        }
+
+        let mut input_events = Vec::new();
+        game.input.translate_os_event(event, &mut input_events);
+
+        if let Some(DemoState::Recording(recorder)) = game.demo.as_mut() {
+            for input_event in input_events.iter() {
+                recorder.record(*input_event);
+            }
+        }
+
+        for input_event in input_events {
+            self.apply_input_event(input_event, ctx)?;
+        }
 
         Ok(())
     }
@@ -1192,7 +1495,26 @@ impl ScriptTrait for Player {
                 return Ok(());
             }
 
-            let level = ctx.plugins.get::<Game>().level.as_ref().unwrap();
+            let game = ctx.plugins.get::<Game>();
+            let level = game.level.as_ref().unwrap();
+
+            if let CharacterMessageData::PickupItem(item) = char_message.data {
+                let count = ctx
+                    .scene
+                    .graph
+                    .try_get_script_component_of::<Item>(item)
+                    .map(|item| *item.stack_size)
+                    .unwrap_or(1);
+                game.record_statistics(StatisticsEvent::ItemCollected(count));
+            }
+
+            game.record_telemetry(|| {
+                TelemetryEvent::from_character_message(
+                    &ctx.scene.graph,
+                    ctx.handle,
+                    &char_message.data,
+                )
+            });
 
             self.character.on_character_message(
                 &char_message.data,
@@ -1210,21 +1532,74 @@ impl ScriptTrait for Player {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Player");
+        if ctx.plugins.get::<Game>().is_paused() {
+            return self.update_photo_mode(ctx);
+        }
+
+        // Polled sources (gamepads) are recorded and applied the same way as the OS events.
+        let game = ctx.plugins.get_mut::<Game>();
+        let polled_events = if game.is_demo_playing()
+            || game.console.is_visible()
+            || game.free_camera.is_active()
+        {
+            Vec::new()
+        } else {
+            game.input.polled_events().to_vec()
+        };
+        if let Some(DemoState::Recording(recorder)) = game.demo.as_mut() {
+            for input_event in polled_events.iter() {
+                recorder.record(*input_event);
+            }
+        }
+        for input_event in polled_events {
+            self.apply_input_event(input_event, ctx)?;
+        }
+
+        // Demo frames are bound to player updates, so the input is applied exactly at the same
+        // moment as it was recorded.
+        let demo_events = match ctx.plugins.get_mut::<Game>().demo.as_mut() {
+            Some(DemoState::Recording(recorder)) => {
+                recorder.end_frame(ctx.dt);
+                None
+            }
+            Some(DemoState::Playback(player)) => player.next_frame(ctx.dt),
+            None => None,
+        };
+        for input_event in demo_events.into_iter().flatten() {
+            self.apply_input_event(input_event, ctx)?;
+        }
+
         self.inventory_gui.update(ctx.dt, &self.character.inventory);
         self.render_offscreen_ui(ctx);
 
         let game = ctx.plugins.get_mut::<Game>();
-        game.weapon_display.sync_to_model(self, &ctx.scene.graph);
+        game.weapon_display
+            .sync_to_model(self, &ctx.scene.graph, game.difficulty());
         game.journal_display.update(ctx.dt, &self.journal);
 
         let game = ctx.plugins.get::<Game>();
         let level = game.level.as_ref().unwrap();
 
+        let controls = &game.config.controls;
+        // The aim assist helps only with the sticks, the mouse is precise enough.
+        let aim_assist_target = if controls.gamepad.aim_assist
+            && (self.input.axis(Axis::LookX, controls) != 0.0
+                || self.input.axis(Axis::LookY, controls) != 0.0)
+        {
+            self.find_aim_assist_target(&ctx.scene.graph, &level.actors)
+        } else {
+            None
+        };
+        self.update_analog_input(controls, aim_assist_target, ctx.dt);
+
+        // Exhausted player walks until the stamina is partially restored.
+        let run = self.controller.run && self.stamina.can_run();
         self.target_local_velocity = Vector2::default();
         if self.controller.walk_forward
             || (!self.controller.aim && (self.controller.walk_left || self.controller.walk_right))
         {
-            self.target_local_velocity.y = if self.controller.run && !self.controller.aim {
+            self.target_local_velocity.y = if run && !self.controller.aim {
                 1.0
             } else {
                 0.5
@@ -1233,7 +1608,7 @@ impl ScriptTrait for Player {
         if self.controller.walk_backward {
             self.target_local_velocity.y = if self.controller.aim {
                 -1.0
-            } else if self.controller.run {
+            } else if run {
                 1.0
             } else {
                 0.5
@@ -1247,6 +1622,9 @@ impl ScriptTrait for Player {
                 self.target_local_velocity.x = 1.0;
             }
         }
+        if self.status_effects.is_stunned() {
+            self.target_local_velocity = Vector2::default();
+        }
 
         self.local_velocity.follow(&self.target_local_velocity, 0.1);
 
@@ -1273,10 +1651,10 @@ impl ScriptTrait for Player {
         }
 
         let has_ground_contact = self.has_ground_contact(&ctx.scene.graph)?;
-        let is_walking = self.is_walking();
+        let is_walking = self.is_walking() && !self.status_effects.is_stunned();
         let is_jumping = has_ground_contact && self.controller.jump;
 
-        self.update_melee_attack(ctx.scene, ctx.message_sender, ctx.handle)?;
+        self.update_melee_attack(ctx.scene, ctx.message_sender, ctx.handle, game.rng())?;
         self.update_animation_machines(ctx.scene, is_walking, is_jumping)?;
 
         if self
@@ -1290,6 +1668,13 @@ impl ScriptTrait for Player {
 
         let is_running = self.is_running(ctx.scene);
 
+        self.stamina.update(ctx.dt);
+        if is_running && is_walking {
+            self.stamina.spend_on_run(ctx.dt);
+        }
+        self.character
+            .update_status_effects(ctx.dt, &ctx.scene.graph, ctx.message_sender);
+
         self.handle_animation_signals(
             ctx.scene,
             &game.message_sender,
@@ -1300,6 +1685,7 @@ impl ScriptTrait for Player {
             is_walking,
             has_ground_contact,
             &level.sound_manager,
+            game.rng(),
         )?;
 
         if !self.is_dead(&ctx.scene.graph) {
@@ -1311,7 +1697,7 @@ impl ScriptTrait for Player {
             self.run_factor += (self.target_run_factor - self.run_factor) * 0.1;
 
             let can_move = self.can_move(&ctx.scene.graph)?;
-            self.update_velocity(ctx.scene, ctx.dt)?;
+            self.update_velocity(ctx.scene, ctx.dt, game.cheats.noclip)?;
 
             ctx.scene
                 .graph
@@ -1406,7 +1792,13 @@ impl ScriptTrait for Player {
 
             self.check_doors(ctx.scene, &level.doors_container);
             self.check_elevators(ctx.scene, &level.elevators)?;
-            self.update_shooting(ctx.scene, ctx.dt, ctx.elapsed_time, ctx.message_sender)?;
+            self.update_shooting(
+                ctx.scene,
+                ctx.dt,
+                ctx.elapsed_time,
+                ctx.message_sender,
+                game,
+            )?;
             self.check_items(
                 ctx.plugins.get_mut::<Game>(),
                 ctx.scene,
@@ -1428,6 +1820,6 @@ impl ScriptTrait for Player {
             body.set_lin_vel(Vector3::new(0.0, body.lin_vel().y, 0.0));
         }
 
-        Ok(())
+        self.update_photo_mode(ctx)
     }
 }
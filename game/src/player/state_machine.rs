@@ -22,6 +22,8 @@ pub struct StateMachineInput<'a> {
     pub is_dead: bool,
     pub should_be_stunned: bool,
     pub melee_attack: bool,
+    /// Speed of the melee attack animations, see [`crate::stamina::Stamina::melee_speed`].
+    pub melee_attack_speed: f32,
     pub machine: Handle<AnimationBlendingStateMachine>,
     pub scene: &'a mut Scene,
     pub local_velocity: Vector2<f32>,
@@ -39,6 +41,7 @@ pub struct StateMachine {
     pub land_state: Handle<State>,
     pub aim_state: Handle<State>,
     pub grab_animation: Handle<Animation>,
+    pub melee_attack_animations: Vec<Handle<Animation>>,
 }
 
 impl StateMachine {
@@ -80,6 +83,10 @@ impl StateMachine {
             land_state: lower_body.find_state_by_name_ref("Land")?.0,
             aim_state: upper_body.find_state_by_name_ref("Aim")?.0,
             grab_animation: animations.find_by_name_ref("agent_grab")?.0,
+            melee_attack_animations: upper_body
+                .find_state_by_name_ref("MeleeAttack")
+                .map(|(state, _)| upper_body.animations_of_state(state).collect())
+                .unwrap_or_default(),
         })
     }
 
@@ -146,6 +153,7 @@ impl StateMachine {
             is_dead,
             should_be_stunned,
             melee_attack,
+            melee_attack_speed,
             machine,
             scene,
             local_velocity,
@@ -155,7 +163,10 @@ impl StateMachine {
         let animation_player = scene.graph.try_get(machine)?.animation_player();
 
         let animations_container =
-            utils::fetch_animation_container_ref(&scene.graph, animation_player);
+            utils::fetch_animation_container_mut(&mut scene.graph, animation_player);
+        for animation in self.melee_attack_animations.iter() {
+            animations_container[*animation].set_speed(melee_attack_speed);
+        }
 
         let current_hit_reaction_animation = match weapon_kind {
             CombatWeaponKind::Rifle => self.hit_reaction_rifle_animation,
@@ -0,0 +1,340 @@
+//! Script profiler. Scripts measure their updates with [`scope`], interesting events (raycasts,
+//! for example) are counted with [`count`]. Samples of every frame are collected by [`Profiler`],
+//! that shows them as an overlay sorted by cost and could capture them to a CSV file. Nothing is
+//! measured while the overlay is hidden and there is no active capture.
+//!
+//! Scopes could be nested (bots measure their behavior trees, weapons update their items), the
+//! time of a nested scope is included in the time of the outer one.
+
+use crate::{level::decal::Decal, weapon::projectile::Projectile};
+use fyrox::{
+    core::{color::Color, log::Log, pool::Handle},
+    fxhash::FxHashMap,
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        font::FontResource,
+        grid::{Column, Grid, GridBuilder, Row},
+        text::{Text, TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, HorizontalAlignment, Thickness, UserInterface, VerticalAlignment,
+    },
+    scene::graph::Graph,
+};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    fmt::Write as _,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    // Samples of the current frame. Scripts have only shared access to the plugins and some of
+    // the measured code has no access to them at all, so the samples are collected here.
+    static FRAME: RefCell<FrameSamples> = RefCell::new(FrameSamples::default());
+}
+
+#[derive(Default, Debug, Copy, Clone)]
+struct Timing {
+    time: Duration,
+    calls: usize,
+}
+
+#[derive(Default, Debug)]
+struct FrameSamples {
+    enabled: bool,
+    timings: FxHashMap<Cow<'static, str>, Timing>,
+    counters: FxHashMap<&'static str, usize>,
+}
+
+/// Measures the time from its creation until it is dropped, see [`scope`].
+#[must_use]
+pub struct Scope {
+    sample: Option<(Cow<'static, str>, Instant)>,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if let Some((name, start)) = self.sample.take() {
+            let time = start.elapsed();
+            FRAME.with_borrow_mut(|frame| {
+                let timing = frame.timings.entry(name).or_default();
+                timing.time += time;
+                timing.calls += 1;
+            });
+        }
+    }
+}
+
+fn is_enabled() -> bool {
+    FRAME.with_borrow(|frame| frame.enabled)
+}
+
+/// Starts measuring a section with the given name, the time is recorded when the scope is
+/// dropped. Times of sections with the same name are summed up.
+pub fn scope(name: &'static str) -> Scope {
+    scope_with(|| name)
+}
+
+/// Same as [`scope`], but the name is produced only if the profiler is enabled.
+pub fn scope_with<F, N>(name: F) -> Scope
+where
+    F: FnOnce() -> N,
+    N: Into<Cow<'static, str>>,
+{
+    Scope {
+        sample: is_enabled().then(|| (name().into(), Instant::now())),
+    }
+}
+
+/// Increments a per-frame counter with the given name.
+pub fn count(counter: &'static str) {
+    FRAME.with_borrow_mut(|frame| {
+        if frame.enabled {
+            *frame.counters.entry(counter).or_default() += 1;
+        }
+    });
+}
+
+fn csv_escape(value: &str) -> Cow<str> {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+#[derive(Debug)]
+struct Capture {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    frame: usize,
+    frames: usize,
+}
+
+impl Capture {
+    fn write_frame(&mut self, samples: &FrameSamples) -> std::io::Result<()> {
+        for (name, timing) in samples.timings.iter() {
+            writeln!(
+                self.writer,
+                "{},{},{:.4},{}",
+                self.frame,
+                csv_escape(name),
+                timing.time.as_secs_f64() * 1000.0,
+                timing.calls
+            )?;
+        }
+        for (name, count) in samples.counters.iter() {
+            writeln!(
+                self.writer,
+                "{},{},,{}",
+                self.frame,
+                csv_escape(name),
+                count
+            )?;
+        }
+        self.frame += 1;
+        Ok(())
+    }
+}
+
+/// Collects the samples of every frame, shows them as an overlay and writes them to captures.
+#[derive(Default, Debug)]
+pub struct Profiler {
+    root: Handle<Grid>,
+    text: Handle<Text>,
+    visible: bool,
+    // Samples accumulated since the overlay was refreshed last time.
+    accumulated: FrameSamples,
+    accumulated_frames: usize,
+    capture: Option<Capture>,
+    overlay_string: String,
+}
+
+impl Profiler {
+    /// Amount of frames the overlay shows the average values for.
+    const REFRESH_FRAMES: usize = 30;
+    const MAX_ROWS: usize = 24;
+
+    pub fn new(font: FontResource, ctx: &mut BuildContext) -> Self {
+        let text = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(8.0)))
+            .with_font(font)
+            .with_font_size(14.0.into())
+            .build(ctx);
+
+        let panel = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_width(420.0)
+                .with_margin(Thickness {
+                    left: 10.0,
+                    top: 120.0,
+                    right: 10.0,
+                    bottom: 10.0,
+                })
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_vertical_alignment(VerticalAlignment::Top)
+                .with_background(Brush::Solid(Color::from_rgba(0, 0, 0, 160)).into())
+                .with_child(text),
+        )
+        .build(ctx);
+
+        // The root fills the screen, so the panel could be aligned to the side.
+        let root = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_child(panel),
+        )
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        Self {
+            root,
+            text,
+            ..Default::default()
+        }
+    }
+
+    pub fn resize(&self, ui: &UserInterface, width: f32, height: f32) {
+        ui.send(self.root, WidgetMessage::Width(width));
+        ui.send(self.root, WidgetMessage::Height(height));
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool, ui: &UserInterface) {
+        self.visible = visible;
+        self.accumulated = Default::default();
+        self.accumulated_frames = 0;
+        ui.send(self.root, WidgetMessage::Visibility(visible));
+        self.sync_enabled();
+    }
+
+    /// Starts writing the samples of the next `frames` frames to a CSV file at the given path.
+    pub fn start_capture(&mut self, path: &Path, frames: usize) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|err| format!("Unable to create {}. Reason: {err}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "frame,name,time_ms,count").map_err(|err| err.to_string())?;
+        self.capture = Some(Capture {
+            writer,
+            path: path.to_path_buf(),
+            frame: 0,
+            frames,
+        });
+        self.sync_enabled();
+        Ok(())
+    }
+
+    fn sync_enabled(&self) {
+        let enabled = self.visible || self.capture.is_some();
+        FRAME.with_borrow_mut(|frame| frame.enabled = enabled);
+    }
+
+    /// Finishes the current frame. The graph of the level is used to count active projectiles and
+    /// decals.
+    pub fn end_frame(&mut self, graph: Option<&Graph>, ui: &UserInterface) {
+        let mut samples = FRAME.with_borrow_mut(|frame| FrameSamples {
+            enabled: frame.enabled,
+            timings: std::mem::take(&mut frame.timings),
+            counters: std::mem::take(&mut frame.counters),
+        });
+
+        if !samples.enabled {
+            return;
+        }
+
+        if let Some(graph) = graph {
+            for (_, node) in graph.pair_iter() {
+                if node.has_script::<Projectile>() {
+                    *samples.counters.entry("Projectiles").or_default() += 1;
+                } else if node.has_script::<Decal>() {
+                    *samples.counters.entry("Decals").or_default() += 1;
+                }
+            }
+        }
+
+        if let Some(mut capture) = self.capture.take() {
+            match capture.write_frame(&samples) {
+                Ok(()) if capture.frame < capture.frames => self.capture = Some(capture),
+                Ok(()) => match capture.writer.flush() {
+                    Ok(()) => Log::info(format!(
+                        "Profiler capture of {} frames was written to {}",
+                        capture.frames,
+                        capture.path.display()
+                    )),
+                    Err(err) => Log::err(format!("Unable to write profiler capture: {err}")),
+                },
+                Err(err) => Log::err(format!("Unable to write profiler capture: {err}")),
+            }
+            self.sync_enabled();
+        }
+
+        if self.visible {
+            for (name, timing) in samples.timings {
+                let accumulated = self.accumulated.timings.entry(name).or_default();
+                accumulated.time += timing.time;
+                accumulated.calls += timing.calls;
+            }
+            for (name, count) in samples.counters {
+                *self.accumulated.counters.entry(name).or_default() += count;
+            }
+            self.accumulated_frames += 1;
+
+            if self.accumulated_frames >= Self::REFRESH_FRAMES {
+                self.refresh_overlay(ui);
+            }
+        }
+    }
+
+    fn refresh_overlay(&mut self, ui: &UserInterface) {
+        let frames = self.accumulated_frames as f64;
+
+        let mut timings = self.accumulated.timings.iter().collect::<Vec<_>>();
+        timings.sort_by(|(_, a), (_, b)| b.time.cmp(&a.time));
+
+        let mut counters = self.accumulated.counters.iter().collect::<Vec<_>>();
+        counters.sort_by_key(|(name, _)| **name);
+
+        self.overlay_string.clear();
+        let _ = writeln!(
+            self.overlay_string,
+            "Average per frame over {} frames:",
+            self.accumulated_frames
+        );
+        for (name, timing) in timings.into_iter().take(Self::MAX_ROWS) {
+            let _ = writeln!(
+                self.overlay_string,
+                "{:.3} ms  x{:.0}  {}",
+                timing.time.as_secs_f64() * 1000.0 / frames,
+                timing.calls as f64 / frames,
+                name
+            );
+        }
+        for (name, count) in counters {
+            let _ = writeln!(
+                self.overlay_string,
+                "{}: {:.0}",
+                name,
+                *count as f64 / frames
+            );
+        }
+        if let Some(capture) = self.capture.as_ref() {
+            let _ = write!(
+                self.overlay_string,
+                "Capturing: {}/{}",
+                capture.frame, capture.frames
+            );
+        }
+
+        ui.send(self.text, TextMessage::Text(self.overlay_string.clone()));
+
+        self.accumulated = Default::default();
+        self.accumulated_frames = 0;
+    }
+}
@@ -0,0 +1,80 @@
+//! Game-owned random number generator. Every random decision in the game (recoil, vfx, sounds,
+//! AI timeouts, etc.) must be made using this generator, so a run could be reproduced from its
+//! seed and a stream of input events. The state of the generator is stored in saved games.
+
+use fyrox::{
+    core::visitor::prelude::*,
+    rand::{self, Rng, RngCore},
+};
+use std::cell::Cell;
+
+/// SplitMix64 generator. It uses interior mutability, so it could be used by scripts that have
+/// only shared access to the plugins. Use `&GameRng` as an argument for methods of [`Rng`].
+#[derive(Clone, Debug)]
+pub struct GameRng {
+    seed: u64,
+    state: Cell<u64>,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(rand::thread_rng().gen())
+    }
+}
+
+impl Visit for GameRng {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.seed.visit("Seed", &mut region)?;
+        self.state.get_mut().visit("State", &mut region)?;
+
+        Ok(())
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: Cell::new(seed),
+        }
+    }
+
+    /// Returns the seed the generator was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn next_value(&self) -> u64 {
+        let state = self.state.get().wrapping_add(0x9E3779B97F4A7C15);
+        self.state.set(state);
+
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl RngCore for &GameRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_value() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.next_value()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_value().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
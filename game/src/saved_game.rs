@@ -0,0 +1,376 @@
+//! Saved game container. A saved game consists of a small header with the metadata (format and
+//! game versions, level, location, playtime, etc.) followed by the payload - a binary visitor
+//! with the scene, the level and the state of the random number generator. The header could be
+//! read without reading the payload, so the save/load dialog could show it cheaply.
+//!
+//! File layout: magic bytes, header length (u32, little-endian), header (RON), payload.
+//!
+//! Besides manual saves, there is a quicksave and a rotating pool of autosaves. They live in the
+//! same folder, but use reserved names, so they never overwrite manual saves. The folder is located
+//! in the per-user data directory, see [`saved_games_folder`].
+
+use crate::localization::{tr, tr_args};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    ffi::OsStr,
+    fs::File,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const MAGIC: &[u8; 8] = b"IAPETUS\0";
+
+/// Older versions of the game kept saves in the working directory. Saves from there are still
+/// listed and could be loaded, but new saves always go to [`saved_games_folder`].
+const LEGACY_SAVED_GAMES_FOLDER: &str = "./saved_games";
+/// Name of the folder of the game in the per-user data directory.
+pub const GAME_DATA_FOLDER: &str = "StationIapetus";
+pub const SAVED_GAME_EXT: &str = "rgs";
+const QUICKSAVE_NAME: &str = "quicksave";
+const AUTOSAVE_PREFIX: &str = "autosave_";
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct SavedGameHeader {
+    pub format_version: u32,
+    /// Version of the game that made the save.
+    pub game_version: String,
+    /// Path of the level the save was made on.
+    pub level: PathBuf,
+    /// Name of the place on the level where the player was.
+    pub location: String,
+    /// Total playtime in seconds.
+    pub playtime: f32,
+    /// Unix timestamp (in seconds) of the moment the save was made.
+    pub timestamp: u64,
+}
+
+impl SavedGameHeader {
+    pub fn new(level: PathBuf, location: String, playtime: f32) -> Self {
+        Self {
+            format_version: SavedGame::FORMAT_VERSION,
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            level,
+            location,
+            playtime,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// A step that upgrades a saved game from `from` version to the next one.
+struct Migration {
+    from: u32,
+    migrate: fn(&mut SavedGame) -> Result<(), String>,
+}
+
+/// Every change of the payload or the header that breaks compatibility must increment
+/// [`SavedGame::FORMAT_VERSION`] and register a migration step here.
+const MIGRATIONS: &[Migration] = &[Migration {
+    // Version 1 saves are bare visitors without the header, the header is made up when reading
+    // the file, see `SavedGame::read_legacy`. The oldest of them do not have the state of the
+    // random number generator, it is handled when the payload is read, see
+    // `Game::read_saved_game_payload`.
+    from: 1,
+    migrate: |_| Ok(()),
+}];
+
+#[derive(Clone, Debug)]
+pub struct SavedGame {
+    pub header: SavedGameHeader,
+    pub payload: Vec<u8>,
+}
+
+impl SavedGame {
+    pub const FORMAT_VERSION: u32 = 2;
+
+    pub fn new(header: SavedGameHeader, payload: Vec<u8>) -> Self {
+        Self { header, payload }
+    }
+
+    /// Writes the saved game atomically: the data is written to a temporary file first, which
+    /// then replaces the file at the given path. An existing save is left intact if the game
+    /// crashes in the middle of writing.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let header = ron::ser::to_string_pretty(&self.header, PrettyConfig::default())
+            .map_err(|e| format!("Unable to serialize saved game header. Reason: {e}"))?;
+
+        if let Some(folder) = path.parent() {
+            if !folder.as_os_str().is_empty() && !folder.exists() {
+                std::fs::create_dir_all(folder).map_err(|e| {
+                    format!("Unable to create folder {}. Reason: {e}", folder.display())
+                })?;
+            }
+        }
+
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let mut file = File::create(&temp_path).map_err(|e| {
+            format!(
+                "Unable to create saved game {}. Reason: {e}",
+                temp_path.display()
+            )
+        })?;
+
+        file.write_all(MAGIC)
+            .and_then(|_| file.write_all(&(header.len() as u32).to_le_bytes()))
+            .and_then(|_| file.write_all(header.as_bytes()))
+            .and_then(|_| file.write_all(&self.payload))
+            .and_then(|_| file.sync_all())
+            .map_err(|e| format!("Unable to write saved game {}. Reason: {e}", path.display()))?;
+        drop(file);
+
+        std::fs::rename(&temp_path, path).map_err(|e| {
+            format!(
+                "Unable to replace saved game {}. Reason: {e}",
+                path.display()
+            )
+        })
+    }
+
+    /// Reads only the header of the saved game at the given path. The header is not migrated.
+    pub fn load_header(path: &Path) -> Result<SavedGameHeader, String> {
+        let mut file = File::open(path)
+            .map_err(|e| format!("Unable to open saved game {}. Reason: {e}", path.display()))?;
+
+        match read_header(&mut file, path)? {
+            Some(header) => Ok(header),
+            None => Ok(legacy_header(path)),
+        }
+    }
+
+    /// Reads the saved game at the given path and upgrades it to the current format version.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut file = File::open(path)
+            .map_err(|e| format!("Unable to open saved game {}. Reason: {e}", path.display()))?;
+
+        let mut saved_game = match read_header(&mut file, path)? {
+            Some(header) => {
+                let mut payload = Vec::new();
+                file.read_to_end(&mut payload).map_err(|e| {
+                    format!("Unable to read saved game {}. Reason: {e}", path.display())
+                })?;
+                Self { header, payload }
+            }
+            None => Self::read_legacy(path)?,
+        };
+
+        saved_game.migrate()?;
+
+        Ok(saved_game)
+    }
+
+    fn read_legacy(path: &Path) -> Result<Self, String> {
+        let payload = std::fs::read(path)
+            .map_err(|e| format!("Unable to read saved game {}. Reason: {e}", path.display()))?;
+        Ok(Self {
+            header: legacy_header(path),
+            payload,
+        })
+    }
+
+    fn migrate(&mut self) -> Result<(), String> {
+        while self.header.format_version < Self::FORMAT_VERSION {
+            let version = self.header.format_version;
+            let migration = MIGRATIONS
+                .iter()
+                .find(|migration| migration.from == version)
+                .ok_or_else(|| {
+                    format!(
+                        "Saved game has format version {version}, which can not be upgraded to \
+                        version {}!",
+                        Self::FORMAT_VERSION
+                    )
+                })?;
+            (migration.migrate)(self)?;
+            self.header.format_version = version + 1;
+        }
+
+        if self.header.format_version > Self::FORMAT_VERSION {
+            return Err(format!(
+                "Saved game has format version {}, but only versions up to {} are supported. \
+                It was made by a newer version of the game ({}).",
+                self.header.format_version,
+                Self::FORMAT_VERSION,
+                self.header.game_version
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `None` if the file does not have the header, which means that it is a legacy save.
+fn read_header(file: &mut File, path: &Path) -> Result<Option<SavedGameHeader>, String> {
+    let error = |e: std::io::Error| {
+        format!(
+            "Unable to read saved game header {}. Reason: {e}",
+            path.display()
+        )
+    };
+
+    let mut magic = [0; MAGIC.len()];
+    if file.read_exact(&mut magic).is_err() || &magic != MAGIC {
+        return Ok(None);
+    }
+
+    let mut length = [0; 4];
+    file.read_exact(&mut length).map_err(error)?;
+    let mut header = vec![0; u32::from_le_bytes(length) as usize];
+    file.read_exact(&mut header).map_err(error)?;
+
+    let header = String::from_utf8(header).map_err(|e| {
+        format!(
+            "Saved game header {} is corrupted. Reason: {e}",
+            path.display()
+        )
+    })?;
+    ron::de::from_str(&header).map(Some).map_err(|e| {
+        format!(
+            "Saved game header {} is corrupted. Reason: {e}",
+            path.display()
+        )
+    })
+}
+
+fn legacy_header(path: &Path) -> SavedGameHeader {
+    SavedGameHeader {
+        format_version: 1,
+        game_version: "Unknown".to_string(),
+        level: Default::default(),
+        location: "Unknown".to_string(),
+        playtime: 0.0,
+        timestamp: path
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+    }
+}
+
+/// Returns the folder for saved games in the per-user data directory (for example,
+/// `~/.local/share/StationIapetus/saved_games` on Linux or
+/// `%APPDATA%\\StationIapetus\\saved_games` on Windows). Falls back to the working directory if
+/// the platform has no such directory.
+pub fn saved_games_folder() -> PathBuf {
+    match dirs::data_dir() {
+        Some(data_dir) => data_dir.join(GAME_DATA_FOLDER).join("saved_games"),
+        None => PathBuf::from(LEGACY_SAVED_GAMES_FOLDER),
+    }
+}
+
+/// Returns paths of every saved game (manual, quick and auto) in the saved games folder and in the
+/// legacy folder.
+pub fn saved_games() -> Vec<PathBuf> {
+    let mut folders = vec![saved_games_folder()];
+    if folders[0] != Path::new(LEGACY_SAVED_GAMES_FOLDER) {
+        folders.push(PathBuf::from(LEGACY_SAVED_GAMES_FOLDER));
+    }
+
+    let mut saved_games = Vec::new();
+    for folder in folders {
+        if let Ok(dir_iterator) = std::fs::read_dir(folder) {
+            for entry in dir_iterator.flatten() {
+                let path = entry.path();
+                if path.extension() == Some(OsStr::new(SAVED_GAME_EXT)) {
+                    saved_games.push(path);
+                }
+            }
+        }
+    }
+    saved_games
+}
+
+/// Returns the most recent saved game of any kind.
+pub fn latest_saved_game() -> Option<PathBuf> {
+    saved_games()
+        .into_iter()
+        .filter_map(|path| {
+            let header = SavedGame::load_header(&path).ok()?;
+            Some((header.timestamp, path))
+        })
+        .max_by_key(|(timestamp, _)| *timestamp)
+        .map(|(_, path)| path)
+}
+
+/// Returns a path of the saved game with the given name in the saved games folder.
+pub fn saved_game_path(file_stem: &str) -> PathBuf {
+    saved_games_folder().join(format!("{file_stem}.{SAVED_GAME_EXT}"))
+}
+
+/// Checks whether the name could be used for a manual save. Returns a human-readable reason if it
+/// could not.
+pub fn validate_name(file_stem: &str) -> Result<(), String> {
+    if file_stem.is_empty() {
+        Err(tr("save_load.name_empty"))
+    } else if !file_stem.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        Err(tr("save_load.name_invalid_chars"))
+    } else if is_reserved_name(file_stem) {
+        Err(tr_args("save_load.name_reserved", &[("name", file_stem)]))
+    } else {
+        Ok(())
+    }
+}
+
+/// Renames the saved game, it stays in the same folder. Fails if there is a saved game with the
+/// new name already.
+pub fn rename(path: &Path, new_file_stem: &str) -> Result<PathBuf, String> {
+    validate_name(new_file_stem)?;
+
+    let new_path = path.with_file_name(format!("{new_file_stem}.{SAVED_GAME_EXT}"));
+    if new_path.exists() {
+        return Err(tr_args("save_load.name_exists", &[("name", new_file_stem)]));
+    }
+
+    std::fs::rename(path, &new_path).map_err(|e| {
+        format!(
+            "Unable to rename saved game {}. Reason: {e}",
+            path.display()
+        )
+    })?;
+
+    Ok(new_path)
+}
+
+pub fn delete(path: &Path) -> Result<(), String> {
+    std::fs::remove_file(path).map_err(|e| {
+        format!(
+            "Unable to delete saved game {}. Reason: {e}",
+            path.display()
+        )
+    })
+}
+
+pub fn quicksave_path() -> PathBuf {
+    saved_game_path(QUICKSAVE_NAME)
+}
+
+/// Returns a path of the autosave slot that should be written next: the first unused slot or the
+/// oldest one, if every slot is used.
+pub fn next_autosave_path(slot_count: usize) -> PathBuf {
+    (0..slot_count.max(1))
+        .map(|slot| saved_game_path(&format!("{AUTOSAVE_PREFIX}{slot}")))
+        .min_by_key(|path| {
+            if path.exists() {
+                SavedGame::load_header(path)
+                    .map(|header| header.timestamp)
+                    .unwrap_or_default()
+            } else {
+                0
+            }
+        })
+        .expect("There is at least one slot!")
+}
+
+/// Quicksave and autosaves use reserved names, manual saves must not use them.
+pub fn is_reserved_name(file_stem: &str) -> bool {
+    file_stem == QUICKSAVE_NAME || file_stem.starts_with(AUTOSAVE_PREFIX)
+}
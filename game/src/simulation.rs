@@ -0,0 +1,216 @@
+//! Headless runner for the game plugin. It creates an engine without a window or graphics context,
+//! adds [`Game`] to it and steps it with a fixed time step. Intended to be used by integration tests
+//! and batch tools that need to inspect level state without a live executor.
+
+use crate::{level::Level, message::Message, Game};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    engine::{executor::Executor, ApplicationLoopController, GraphicsContextParams},
+    fxhash::FxHashMap,
+    plugin::error::{GameError, GameResult},
+    scene::Scene,
+};
+use std::{cell::Cell, path::Path};
+
+pub struct Simulation {
+    executor: Executor,
+    running: Cell<bool>,
+    dt: f32,
+    lag: f32,
+    frame: usize,
+}
+
+impl Simulation {
+    /// Default time step, matches the desired update rate of the executor (60 Hz).
+    pub const DEFAULT_DT: f32 = 1.0 / 60.0;
+
+    /// Creates new headless engine instance with the game plugin attached and initialized. No
+    /// level is loaded, use [`Self::load_level`] to do so.
+    pub fn new(dt: f32) -> Result<Self, GameError> {
+        Self::with_game(Game::headless(), None, dt)
+    }
+
+    /// Same as [`Self::new`], but uses the given game plugin instance. It must be created with
+    /// [`Game::headless`], launch options could be set on it to load a saved game. The scene at the
+    /// given path is loaded as a fresh level, the same way as a scene passed by the executor.
+    pub fn with_game(game: Game, scene_path: Option<&str>, dt: f32) -> Result<Self, GameError> {
+        let mut executor = Executor::from_params(
+            None,
+            GraphicsContextParams {
+                window_attributes: Default::default(),
+                vsync: false,
+                msaa_sample_count: None,
+                graphics_server_constructor: Default::default(),
+                named_objects: false,
+            },
+        );
+
+        executor.add_plugin(game);
+
+        let running = Cell::new(true);
+        executor.enable_plugins(
+            scene_path,
+            true,
+            Some(ApplicationLoopController::Headless { running: &running }),
+        )?;
+
+        Ok(Self {
+            executor,
+            running,
+            dt,
+            lag: 0.0,
+            frame: 0,
+        })
+    }
+
+    /// Requests the level at the given path and steps the simulation until it is loaded. Fails
+    /// if the level wasn't loaded in `max_frames` frames.
+    pub fn load_level(&mut self, path: impl AsRef<Path>, max_frames: usize) -> GameResult {
+        self.send(Message::LoadLevel {
+            path: path.as_ref().to_path_buf(),
+        });
+
+        if self.run_until(max_frames, |simulation| simulation.level().is_some()) {
+            Log::info(format!(
+                "[Simulation]: {} was loaded in {} frames.",
+                path.as_ref().display(),
+                self.frame
+            ));
+            Ok(())
+        } else {
+            Err(GameError::str(format!(
+                "Unable to load {} in {max_frames} frames!",
+                path.as_ref().display()
+            )))
+        }
+    }
+
+    /// Performs exactly one update of the engine and all its plugins and scripts.
+    pub fn step(&mut self) -> GameResult {
+        if !self.is_running() {
+            return Err(GameError::str("The game has stopped running!"));
+        }
+
+        // Force exactly one fixed step per call, regardless of how long the step actually takes.
+        self.lag = self.dt;
+        self.executor.update(
+            self.dt,
+            ApplicationLoopController::Headless {
+                running: &self.running,
+            },
+            &mut self.lag,
+            FxHashMap::default(),
+        );
+        self.frame += 1;
+
+        Ok(())
+    }
+
+    /// Performs the given amount of updates.
+    pub fn run_frames(&mut self, count: usize) -> GameResult {
+        for _ in 0..count {
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Steps the simulation until the predicate returns `true`, or until `max_frames` updates were
+    /// performed. Returns `true` if the predicate was satisfied.
+    pub fn run_until<F>(&mut self, max_frames: usize, mut predicate: F) -> bool
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        for _ in 0..max_frames {
+            if predicate(self) {
+                return true;
+            }
+            if let Err(err) = self.step() {
+                Log::err(format!("[Simulation]: {err}"));
+                return false;
+            }
+        }
+        predicate(self)
+    }
+
+    /// Restarts the game random number generator with the given seed. Should be called before
+    /// loading a level to make the run reproducible.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.game_mut().set_seed(seed);
+    }
+
+    /// Plays the demo at the given path and steps the simulation until the playback ends. Fails
+    /// if the demo could not be played or if it didn't end in `max_frames` frames.
+    pub fn play_demo(&mut self, path: impl AsRef<Path>, max_frames: usize) -> GameResult {
+        self.send(Message::PlayDemo(path.as_ref().to_path_buf()));
+
+        // Playback starts only when the level of the demo is loaded.
+        if !self.run_until(max_frames, |simulation| simulation.game().is_demo_playing()) {
+            return Err(GameError::str(format!(
+                "Unable to start playback of {}!",
+                path.as_ref().display()
+            )));
+        }
+
+        if self.run_until(max_frames, |simulation| {
+            !simulation.game().is_demo_playing()
+        }) {
+            Ok(())
+        } else {
+            Err(GameError::str(format!(
+                "Demo {} did not end in {max_frames} frames!",
+                path.as_ref().display()
+            )))
+        }
+    }
+
+    /// Sends a message to the game, it will be handled on the next update.
+    pub fn send(&self, message: Message) {
+        self.game().message_sender.send(message);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.get()
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    pub fn dt(&self) -> f32 {
+        self.dt
+    }
+
+    pub fn game(&self) -> &Game {
+        self.executor
+            .plugins()
+            .iter()
+            .find_map(|plugin| plugin.cast::<Game>())
+            .expect("Game plugin must exist!")
+    }
+
+    pub fn game_mut(&mut self) -> &mut Game {
+        self.executor
+            .plugins_mut()
+            .iter_mut()
+            .find_map(|plugin| plugin.cast_mut::<Game>())
+            .expect("Game plugin must exist!")
+    }
+
+    pub fn level(&self) -> Option<&Level> {
+        self.game().level.as_ref()
+    }
+
+    pub fn scene(&self) -> Option<&Scene> {
+        self.level()
+            .and_then(|level| self.executor.scenes.try_get(level.scene).ok())
+    }
+
+    pub fn scene_mut(&mut self) -> Option<&mut Scene> {
+        let scene = self.level().map(|level| level.scene)?;
+        self.executor.scenes.try_get_mut(scene).ok()
+    }
+
+    pub fn scene_handle(&self) -> Handle<Scene> {
+        self.level().map(|level| level.scene).unwrap_or_default()
+    }
+}
@@ -9,7 +9,7 @@ use fyrox::{
         log::{Log, MessageKind},
         pool::Handle,
     },
-    rand::{self, seq::SliceRandom},
+    rand::{seq::SliceRandom, Rng},
     scene::{
         base::BaseBuilder,
         graph::{physics::FeatureId, Graph},
@@ -286,6 +286,7 @@ impl SoundManager {
         gain: f32,
         rolloff_factor: f32,
         radius: f32,
+        rng: &mut impl Rng,
     ) {
         let material = self.sound_map.ranges_of(collider).and_then(|ranges| {
             match feature {
@@ -311,7 +312,7 @@ impl SoundManager {
         if let Some(material) = material {
             if let Some(map) = self.sound_base.material_to_sound.get(&material) {
                 if let Some(sound_list) = map.get(&sound_kind) {
-                    if let Some(sound) = sound_list.choose(&mut rand::thread_rng()) {
+                    if let Some(sound) = sound_list.choose(rng) {
                         self.play_sound(graph, sound, position, gain, rolloff_factor, radius);
                     }
                 } else {
@@ -0,0 +1,120 @@
+//! Stamina of characters. Running, jumping and melee attacks spend it, it regenerates once nothing
+//! has spent it for a while. An exhausted character can not run and attacks slower until a part of
+//! the stamina is restored.
+
+use fyrox::core::{reflect::prelude::*, variable::InheritableVariable, visitor::prelude::*};
+
+#[derive(Visit, Reflect, Debug, Clone)]
+#[visit(optional)]
+pub struct Stamina {
+    #[reflect(min_value = 1.0, max_value = 1000.0)]
+    pub max: InheritableVariable<f32>,
+    /// Stamina restored per second.
+    #[reflect(min_value = 0.0, max_value = 1000.0)]
+    pub regeneration_rate: InheritableVariable<f32>,
+    /// Time (in seconds) the stamina does not regenerate after it was spent.
+    #[reflect(min_value = 0.0, max_value = 10.0)]
+    pub regeneration_delay: InheritableVariable<f32>,
+    /// Stamina spent per second of running.
+    #[reflect(min_value = 0.0, max_value = 1000.0)]
+    pub run_cost: InheritableVariable<f32>,
+    #[reflect(min_value = 0.0, max_value = 1000.0)]
+    pub jump_cost: InheritableVariable<f32>,
+    #[reflect(min_value = 0.0, max_value = 1000.0)]
+    pub melee_cost: InheritableVariable<f32>,
+    /// Fraction of the stamina that must be restored to recover from the exhaustion.
+    #[reflect(min_value = 0.0, max_value = 1.0)]
+    pub recovery_threshold: InheritableVariable<f32>,
+    // The spent amount is stored instead of the current one, so the max could be changed in
+    // prefabs without touching the saved state.
+    #[reflect(hidden)]
+    spent: f32,
+    #[reflect(hidden)]
+    regeneration_timer: f32,
+    #[reflect(hidden)]
+    exhausted: bool,
+}
+
+impl Default for Stamina {
+    fn default() -> Self {
+        Self {
+            max: 100.0.into(),
+            regeneration_rate: 20.0.into(),
+            regeneration_delay: 1.0.into(),
+            run_cost: 10.0.into(),
+            jump_cost: 15.0.into(),
+            melee_cost: 10.0.into(),
+            recovery_threshold: 0.3.into(),
+            spent: 0.0,
+            regeneration_timer: 0.0,
+            exhausted: false,
+        }
+    }
+}
+
+impl Stamina {
+    /// Speed of the melee attack animations of an exhausted character.
+    pub const EXHAUSTED_MELEE_SPEED: f32 = 0.6;
+
+    pub fn value(&self) -> f32 {
+        (*self.max - self.spent).max(0.0)
+    }
+
+    /// Current stamina in `[0; 1]` range.
+    pub fn fraction(&self) -> f32 {
+        self.value() / self.max.max(f32::EPSILON)
+    }
+
+    /// Exhaustion starts when the stamina is spent completely and lasts until the stamina is
+    /// restored up to the recovery threshold.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    pub fn can_run(&self) -> bool {
+        !self.exhausted
+    }
+
+    pub fn melee_speed(&self) -> f32 {
+        if self.exhausted {
+            Self::EXHAUSTED_MELEE_SPEED
+        } else {
+            1.0
+        }
+    }
+
+    pub fn spend(&mut self, amount: f32) {
+        if amount <= 0.0 {
+            return;
+        }
+        self.spent = (self.spent + amount).min(*self.max);
+        self.regeneration_timer = *self.regeneration_delay;
+        if self.value() <= 0.0 {
+            self.exhausted = true;
+        }
+    }
+
+    pub fn spend_on_run(&mut self, dt: f32) {
+        self.spend(*self.run_cost * dt);
+    }
+
+    pub fn spend_on_jump(&mut self) {
+        self.spend(*self.jump_cost);
+    }
+
+    pub fn spend_on_melee(&mut self) {
+        self.spend(*self.melee_cost);
+    }
+
+    /// Regenerates the stamina, must be called once per frame before anything spends it.
+    pub fn update(&mut self, dt: f32) {
+        if self.regeneration_timer > 0.0 {
+            self.regeneration_timer -= dt;
+            return;
+        }
+        self.spent = (self.spent - *self.regeneration_rate * dt).max(0.0);
+        if self.exhausted && self.fraction() >= *self.recovery_threshold {
+            self.exhausted = false;
+        }
+    }
+}
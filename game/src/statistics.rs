@@ -0,0 +1,197 @@
+//! Statistics of a run: kills, accuracy, damage taken and so on. Every level of a run has its own
+//! statistics, they are shown on the death and final screens. Statistics are stored in the level,
+//! so they survive save/load.
+
+use crate::{
+    localization::{tr, tr_args},
+    utils,
+};
+use fyrox::core::visitor::prelude::*;
+use std::{collections::HashMap, path::PathBuf};
+
+/// Something that happened during the run and should be counted. Scripts send them with
+/// [`crate::Game::record_statistics`].
+#[derive(Debug, Clone)]
+pub enum StatisticsEvent {
+    ShotFired,
+    ShotHit,
+    Headshot,
+    LimbSevered,
+    DamageTaken(f32),
+    /// An enemy was killed by the player, `victim` is the name of the enemy prefab.
+    Kill {
+        victim: String,
+    },
+    ItemCollected(u32),
+    SecretFound,
+}
+
+#[derive(Visit, Default, Debug, Clone)]
+pub struct LevelStatistics {
+    /// Path of the level, empty for the totals of a run.
+    pub level: PathBuf,
+    pub kills: HashMap<String, u32>,
+    pub shots_fired: u32,
+    pub shots_hit: u32,
+    pub headshots: u32,
+    pub limbs_severed: u32,
+    pub damage_taken: f32,
+    pub items_collected: u32,
+    pub secrets_found: u32,
+    pub secrets_total: u32,
+    pub time: f32,
+}
+
+impl LevelStatistics {
+    pub fn new(level: PathBuf) -> Self {
+        Self {
+            level,
+            ..Default::default()
+        }
+    }
+
+    pub fn register(&mut self, event: StatisticsEvent) {
+        match event {
+            StatisticsEvent::ShotFired => self.shots_fired += 1,
+            StatisticsEvent::ShotHit => self.shots_hit += 1,
+            StatisticsEvent::Headshot => self.headshots += 1,
+            StatisticsEvent::LimbSevered => self.limbs_severed += 1,
+            StatisticsEvent::DamageTaken(amount) => self.damage_taken += amount,
+            StatisticsEvent::Kill { victim } => *self.kills.entry(victim).or_default() += 1,
+            StatisticsEvent::ItemCollected(count) => self.items_collected += count,
+            StatisticsEvent::SecretFound => self.secrets_found += 1,
+        }
+    }
+
+    pub fn total_kills(&self) -> u32 {
+        self.kills.values().sum()
+    }
+
+    /// Returns the percentage of the shots that hit something, `None` if there were no shots.
+    pub fn accuracy(&self) -> Option<f32> {
+        // A shot could hit several hit boxes (splash damage), so the accuracy is clamped.
+        (self.shots_fired > 0)
+            .then(|| (self.shots_hit as f32 / self.shots_fired as f32 * 100.0).min(100.0))
+    }
+
+    fn add(&mut self, other: &LevelStatistics) {
+        for (victim, count) in other.kills.iter() {
+            *self.kills.entry(victim.clone()).or_default() += count;
+        }
+        self.shots_fired += other.shots_fired;
+        self.shots_hit += other.shots_hit;
+        self.headshots += other.headshots;
+        self.limbs_severed += other.limbs_severed;
+        self.damage_taken += other.damage_taken;
+        self.items_collected += other.items_collected;
+        self.secrets_found += other.secrets_found;
+        self.secrets_total += other.secrets_total;
+        self.time += other.time;
+    }
+
+    fn format(&self, text: &mut String) {
+        let mut line = |string: String| {
+            text.push_str(&string);
+            text.push('\n');
+        };
+
+        line(tr_args(
+            "statistics.time",
+            &[("time", utils::format_time(self.time).as_str())],
+        ));
+        line(tr_args(
+            "statistics.kills",
+            &[("count", self.total_kills().to_string().as_str())],
+        ));
+        let mut kills = self.kills.iter().collect::<Vec<_>>();
+        kills.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (victim, count) in kills {
+            line(format!("    {victim}: {count}"));
+        }
+        line(tr_args(
+            "statistics.accuracy",
+            &[
+                ("hits", self.shots_hit.to_string().as_str()),
+                ("shots", self.shots_fired.to_string().as_str()),
+                (
+                    "percent",
+                    format!("{:.0}", self.accuracy().unwrap_or_default()).as_str(),
+                ),
+            ],
+        ));
+        line(tr_args(
+            "statistics.headshots",
+            &[("count", self.headshots.to_string().as_str())],
+        ));
+        line(tr_args(
+            "statistics.limbs_severed",
+            &[("count", self.limbs_severed.to_string().as_str())],
+        ));
+        line(tr_args(
+            "statistics.damage_taken",
+            &[("amount", format!("{:.0}", self.damage_taken).as_str())],
+        ));
+        line(tr_args(
+            "statistics.items_collected",
+            &[("count", self.items_collected.to_string().as_str())],
+        ));
+        line(tr_args(
+            "statistics.secrets",
+            &[
+                ("found", self.secrets_found.to_string().as_str()),
+                ("total", self.secrets_total.to_string().as_str()),
+            ],
+        ));
+    }
+}
+
+/// Statistics of every level of a run, the last one is the current level.
+#[derive(Visit, Default, Debug, Clone)]
+pub struct RunStatistics {
+    pub levels: Vec<LevelStatistics>,
+}
+
+impl RunStatistics {
+    pub fn start_level(&mut self, level: PathBuf) {
+        self.levels.push(LevelStatistics::new(level));
+    }
+
+    pub fn current(&self) -> Option<&LevelStatistics> {
+        self.levels.last()
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut LevelStatistics> {
+        self.levels.last_mut()
+    }
+
+    pub fn total(&self) -> LevelStatistics {
+        let mut total = LevelStatistics::default();
+        for level in self.levels.iter() {
+            total.add(level);
+        }
+        total
+    }
+
+    /// Creates a human-readable report, levels are listed separately if there is more than one.
+    pub fn format(&self) -> String {
+        let mut text = String::new();
+        if self.levels.len() > 1 {
+            for level in self.levels.iter() {
+                text.push_str(
+                    &level
+                        .level
+                        .file_stem()
+                        .unwrap_or_default()
+                        .to_string_lossy(),
+                );
+                text.push('\n');
+                level.format(&mut text);
+                text.push('\n');
+            }
+            text.push_str(&tr("statistics.total"));
+            text.push('\n');
+        }
+        self.total().format(&mut text);
+        text
+    }
+}
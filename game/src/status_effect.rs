@@ -0,0 +1,197 @@
+//! Timed status effects of characters. Damaging effects tick periodically, the damage of a tick is
+//! taken by a single hit box of the character, see [`crate::character::Character`]. Every kind has
+//! its own rules of stacking, see [`StatusEffects::apply`].
+
+use crate::damage::DamageType;
+use fyrox::{
+    core::{pool::Handle, reflect::prelude::*, type_traits::prelude::*, visitor::prelude::*},
+    scene::node::Node,
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    TypeUuidProvider,
+)]
+#[type_uuid(id = "8e2d6b41-93f7-4c0a-b5d8-1f6a7c3e9b52")]
+pub enum StatusEffectKind {
+    /// Caused by severed limbs.
+    #[default]
+    Bleeding,
+    /// Caused by fire and plasma hits.
+    Burning,
+    /// Caused by explosions, a stunned character can not move or attack.
+    Stun,
+    /// Caused by melee attacks of some monsters.
+    Poison,
+}
+
+impl StatusEffectKind {
+    /// Time (in seconds) the effect lasts after it was applied.
+    pub fn duration(self) -> f32 {
+        match self {
+            Self::Bleeding => 10.0,
+            Self::Burning => 4.0,
+            Self::Stun => 1.0,
+            Self::Poison => 8.0,
+        }
+    }
+
+    /// Damage per second of a single stack of the effect.
+    pub fn damage_per_second(self) -> f32 {
+        match self {
+            Self::Bleeding => 2.0,
+            Self::Burning => 8.0,
+            Self::Stun => 0.0,
+            Self::Poison => 3.0,
+        }
+    }
+
+    pub fn damage_type(self) -> Option<DamageType> {
+        match self {
+            Self::Bleeding => Some(DamageType::Bleeding),
+            Self::Burning => Some(DamageType::Fire),
+            Self::Stun => None,
+            Self::Poison => Some(DamageType::Poison),
+        }
+    }
+
+    /// Repeated applications add stacks up to this amount, every application restarts the effect.
+    pub fn max_stacks(self) -> u32 {
+        match self {
+            Self::Bleeding => 4,
+            Self::Burning | Self::Stun => 1,
+            Self::Poison => 5,
+        }
+    }
+
+    /// Returns the effect that is caused by a hit of the given type, if any.
+    pub fn caused_by(damage_type: DamageType) -> Option<Self> {
+        match damage_type {
+            DamageType::Plasma | DamageType::Fire => Some(Self::Burning),
+            DamageType::Explosive => Some(Self::Stun),
+            _ => None,
+        }
+    }
+
+    /// Localization key of the name, see [`crate::localization`].
+    pub fn name_key(self) -> String {
+        format!("status_effect.{}", self.as_ref().to_lowercase())
+    }
+}
+
+#[derive(Visit, Default, Clone, Debug, PartialEq)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub stacks: u32,
+    /// Time (in seconds) until the effect is over.
+    pub remaining: f32,
+    /// The entity that caused the effect, the damage is attributed to it.
+    pub dealer: Handle<Node>,
+    tick_timer: f32,
+}
+
+/// Damage of a tick of an effect.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusEffectDamage {
+    pub amount: f32,
+    pub damage_type: DamageType,
+    pub dealer: Handle<Node>,
+}
+
+#[derive(Visit, Default, Clone, Debug)]
+#[visit(optional)]
+pub struct StatusEffects {
+    effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Damaging effects deal their damage once per this time (in seconds).
+    pub const TICK_INTERVAL: f32 = 0.5;
+
+    pub fn apply(&mut self, kind: StatusEffectKind, dealer: Handle<Node>) {
+        if let Some(effect) = self.effects.iter_mut().find(|effect| effect.kind == kind) {
+            effect.stacks = (effect.stacks + 1).min(kind.max_stacks());
+            effect.remaining = kind.duration();
+            effect.dealer = dealer;
+        } else {
+            self.effects.push(StatusEffect {
+                kind,
+                stacks: 1,
+                remaining: kind.duration(),
+                dealer,
+                tick_timer: 0.0,
+            });
+        }
+    }
+
+    pub fn clear(&mut self, kind: StatusEffectKind) {
+        self.effects.retain(|effect| effect.kind != kind);
+    }
+
+    pub fn clear_all(&mut self) {
+        self.effects.clear();
+    }
+
+    pub fn get(&self, kind: StatusEffectKind) -> Option<&StatusEffect> {
+        self.effects.iter().find(|effect| effect.kind == kind)
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusEffectKind::Stun)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.effects.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Forgets the entities that caused the effects, used when the effects are moved to another
+    /// scene.
+    pub fn forget_dealers(&mut self) {
+        for effect in self.effects.iter_mut() {
+            effect.dealer = Handle::NONE;
+        }
+    }
+
+    /// Advances the effects, the damage of the ticks that happened during the frame is written to
+    /// the output.
+    pub fn update(&mut self, dt: f32, output: &mut Vec<StatusEffectDamage>) {
+        for effect in self.effects.iter_mut() {
+            // The last tick happens even if the effect is over in the middle of the frame.
+            effect.tick_timer += dt.min(effect.remaining);
+            effect.remaining -= dt;
+            while effect.tick_timer >= Self::TICK_INTERVAL {
+                effect.tick_timer -= Self::TICK_INTERVAL;
+                if let Some(damage_type) = effect.kind.damage_type() {
+                    output.push(StatusEffectDamage {
+                        amount: effect.kind.damage_per_second()
+                            * effect.stacks as f32
+                            * Self::TICK_INTERVAL,
+                        damage_type,
+                        dealer: effect.dealer,
+                    });
+                }
+            }
+        }
+        self.effects.retain(|effect| effect.remaining > 0.0);
+    }
+}
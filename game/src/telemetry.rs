@@ -0,0 +1,242 @@
+//! Gameplay telemetry. Interesting gameplay events (damage, kills, item usage, doors, etc.) are
+//! written to a file as JSON objects, one per line. Every line contains the path of the level
+//! and the elapsed game time on this level. The sink is configured in
+//! [`crate::config::TelemetryConfig`] and does nothing (not even formats the event) when disabled.
+
+use crate::{
+    character::{CharacterMessageData, DamageDealer},
+    config::TelemetryConfig,
+    level::{hit_box::LimbType, Level},
+    Item,
+};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    graph::SceneGraph,
+    resource::model::ModelResource,
+    scene::{graph::Graph, node::Node},
+};
+use serde::Serialize;
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    Damage {
+        target: String,
+        dealer: String,
+        limb: String,
+        amount: f32,
+        melee: bool,
+    },
+    Kill {
+        victim: String,
+        killer: String,
+    },
+    ItemPickup {
+        character: String,
+        item: String,
+        count: u32,
+    },
+    ItemDrop {
+        character: String,
+        item: String,
+        count: u32,
+    },
+    ItemUse {
+        character: String,
+        item: String,
+    },
+    DoorOpened {
+        door: String,
+        actor: String,
+    },
+    DoorAccessDenied {
+        door: String,
+        actor: String,
+    },
+    LevelLoaded {
+        from_save: bool,
+    },
+    GameSaved {
+        path: PathBuf,
+    },
+    PlayerDied {
+        position: [f32; 3],
+    },
+}
+
+impl TelemetryEvent {
+    pub fn damage(
+        graph: &Graph,
+        target: Handle<Node>,
+        dealer: &DamageDealer,
+        limb: LimbType,
+        amount: f32,
+        melee: bool,
+    ) -> Self {
+        Self::Damage {
+            target: entity_name(graph, target),
+            dealer: dealer_name(graph, dealer),
+            limb: limb.as_ref().to_string(),
+            amount,
+            melee,
+        }
+    }
+
+    pub fn kill(graph: &Graph, victim: Handle<Node>, killer: &DamageDealer) -> Self {
+        Self::Kill {
+            victim: entity_name(graph, victim),
+            killer: dealer_name(graph, killer),
+        }
+    }
+
+    /// Converts item-related character messages to telemetry events. Must be called before the
+    /// message is handled, because picked up items are disabled after that.
+    pub fn from_character_message(
+        graph: &Graph,
+        character: Handle<Node>,
+        data: &CharacterMessageData,
+    ) -> Option<Self> {
+        let character = entity_name(graph, character);
+        match data {
+            CharacterMessageData::PickupItem(item) => {
+                let item_node = graph.try_get(*item).ok()?;
+                let (item, count) = match item_node.try_get_script_component::<Item>() {
+                    Some(item) => (item.name.to_string(), *item.stack_size),
+                    None => (item_node.name_owned(), 1),
+                };
+                Some(Self::ItemPickup {
+                    character,
+                    item,
+                    count,
+                })
+            }
+            CharacterMessageData::DropItems { item, count } => Some(Self::ItemDrop {
+                character,
+                item: item_name(item),
+                count: *count,
+            }),
+            CharacterMessageData::UseItem { item } => Some(Self::ItemUse {
+                character,
+                item: item_name(item),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Returns a name of a scene node, that is used to identify entities in telemetry.
+pub fn entity_name(graph: &Graph, handle: Handle<Node>) -> String {
+    graph
+        .try_get(handle)
+        .map(|node| node.name_owned())
+        .unwrap_or_else(|_| format!("{handle:?}"))
+}
+
+fn dealer_name(graph: &Graph, dealer: &DamageDealer) -> String {
+    // Weapons report their owners as the dealers.
+    match dealer.as_character(graph) {
+        Some((character, _)) => entity_name(graph, character),
+        None => entity_name(graph, dealer.entity),
+    }
+}
+
+fn item_name(item: &ModelResource) -> String {
+    Item::from_resource(item, |item| item.map(|item| item.name.to_string()))
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+#[derive(Serialize)]
+struct TelemetryRecord<'a> {
+    level: &'a Path,
+    time: f32,
+    #[serde(flatten)]
+    event: TelemetryEvent,
+}
+
+/// Telemetry sink. It uses interior mutability, so it could be used by scripts that have only
+/// shared access to the plugins.
+#[derive(Default)]
+pub struct Telemetry {
+    sink: Option<RefCell<BufWriter<File>>>,
+}
+
+impl Debug for Telemetry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Telemetry (enabled: {})", self.is_enabled())
+    }
+}
+
+impl Telemetry {
+    pub fn new(config: &TelemetryConfig) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+
+        match OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)
+        {
+            Ok(file) => {
+                Log::info(format!("Telemetry is written to {}", config.path.display()));
+                Self {
+                    sink: Some(RefCell::new(BufWriter::new(file))),
+                }
+            }
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to open telemetry file {}. Reason: {err}",
+                    config.path.display()
+                ));
+                Self::default()
+            }
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// Writes an event produced by the given closure. The closure is called only if the
+    /// telemetry is enabled, it could return `None` if there is nothing to record.
+    pub fn record<F, E>(&self, level: &Level, event: F)
+    where
+        F: FnOnce() -> E,
+        E: Into<Option<TelemetryEvent>>,
+    {
+        let Some(sink) = self.sink.as_ref() else {
+            return;
+        };
+
+        let Some(event) = event().into() else {
+            return;
+        };
+
+        let record = TelemetryRecord {
+            level: &level.path,
+            time: level.time,
+            event,
+        };
+
+        let mut sink = sink.borrow_mut();
+        let result = serde_json::to_writer(&mut *sink, &record)
+            .map_err(|err| err.to_string())
+            .and_then(|_| writeln!(sink).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            Log::err(format!("Unable to write telemetry event. Reason: {err}"));
+        }
+    }
+
+    pub fn flush(&self) {
+        if let Some(sink) = self.sink.as_ref() {
+            Log::verify(sink.borrow_mut().flush());
+        }
+    }
+}
@@ -7,7 +7,7 @@ use fyrox::{
         algebra::{Point3, Unit, UnitQuaternion, Vector3},
         pool::Handle,
     },
-    rand::{self, seq::IteratorRandom},
+    rand::seq::IteratorRandom,
     scene::{
         animation::prelude::*,
         graph::Graph,
@@ -99,8 +99,8 @@ pub async fn use_hrtf(
         ));
 }
 
-pub fn is_probability_event_occurred(probability: f32) -> bool {
-    rand::thread_rng().gen_range(0.0..1.0) < probability.clamp(0.0, 1.0)
+pub fn is_probability_event_occurred(probability: f32, rng: &mut impl Rng) -> bool {
+    rng.gen_range(0.0..1.0) < probability.clamp(0.0, 1.0)
 }
 
 pub fn fetch_animation_container_ref(
@@ -130,10 +130,14 @@ pub fn is_any_sound_playing(sounds: &[Handle<Node>], graph: &Graph) -> bool {
     })
 }
 
-pub fn try_play_random_sound(sounds: &[Handle<Node>], graph: &mut Graph) -> bool {
+pub fn try_play_random_sound(
+    sounds: &[Handle<Node>],
+    graph: &mut Graph,
+    rng: &mut impl Rng,
+) -> bool {
     if let Some(random_sound) = sounds
         .iter()
-        .choose(&mut rand::thread_rng())
+        .choose(rng)
         .and_then(|s| graph.try_get_mut_of_type::<Sound>(*s).ok())
     {
         random_sound.play();
@@ -147,3 +151,14 @@ pub fn try_play_sound(sound_handle: Handle<Node>, graph: &mut Graph) -> GameResu
     graph.try_get_mut_of_type::<Sound>(sound_handle)?.try_play();
     Ok(())
 }
+
+/// Formats the given amount of seconds as `h:mm:ss`.
+pub fn format_time(seconds: f32) -> String {
+    let seconds = seconds as u64;
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
@@ -1,7 +1,9 @@
 use crate::{
+    achievements::AchievementEvent,
     character::{CharacterMessage, CharacterMessageData},
     highlight::HighlightEntry,
     player::{camera::CameraController, Player},
+    profiler,
     weapon::{find_parent_character, Weapon, WeaponMessage, WeaponMessageData},
     CollisionGroups, Game, Item,
 };
@@ -89,6 +91,7 @@ impl KineticGun {
         let ray = Ray::new(begin, dir);
 
         let mut query_buffer = Vec::default();
+        profiler::count("Raycasts");
         physics.cast_ray(
             RayCastOptions {
                 ray_origin: Point3::from(ray.origin),
@@ -208,6 +211,7 @@ impl ScriptTrait for KineticGun {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("KineticGun");
         self.weapon.on_update(ctx)?;
 
         if self.is_active {
@@ -317,7 +321,16 @@ impl ScriptTrait for KineticGun {
                             target_body.set_lin_vel(velocity);
                             target_body.wake_up();
 
-                            self.reset_target(ctx.plugins.get_mut::<Game>());
+                            let game = ctx.plugins.get_mut::<Game>();
+                            let thrown_by_player = game.level.as_ref().is_some_and(|level| {
+                                find_parent_character(ctx.handle, &ctx.scene.graph)
+                                    .is_some_and(|(character, _)| character == level.player)
+                            });
+                            if thrown_by_player {
+                                game.record_achievement_event(AchievementEvent::KineticGunThrow);
+                            }
+
+                            self.reset_target(game);
                         }
                         None => {
                             if let Ok(new_target) = self.try_pick_target(&mut ctx.scene.graph) {
@@ -1,6 +1,9 @@
 //! Weapon related stuff.
 
-use crate::{character::Character, level::item::Item, weapon::projectile::Projectile};
+use crate::{
+    character::Character, difficulty::Difficulty, level::item::Item, profiler,
+    weapon::projectile::Projectile, Game,
+};
 use fyrox::graph::SceneGraph;
 use fyrox::plugin::error::GameResult;
 use fyrox::{
@@ -81,6 +84,11 @@ pub struct Weapon {
     #[reflect(hidden)]
     last_shot_time: f32,
 
+    /// Fraction of ammo the player has used, but not spent yet, see
+    /// [`Self::player_ammo_consumption_per_shot`].
+    #[reflect(hidden)]
+    player_ammo_debt: f32,
+
     #[reflect(hidden)]
     #[visit(skip)]
     self_handle: Handle<Node>,
@@ -92,6 +100,7 @@ impl Default for Weapon {
             item: Default::default(),
             shot_point: Handle::NONE,
             last_shot_time: 0.0,
+            player_ammo_debt: 0.0,
             owner: Handle::NONE,
             shoot_interval: 0.15.into(),
             projectile: None,
@@ -128,6 +137,37 @@ impl Weapon {
         Self::from_resource(model_resource, |w| w.is_some())
     }
 
+    /// Returns the exact amount of ammo the player uses per shot, it depends on the difficulty.
+    /// Bots always spend [`Self::ammo_consumption_per_shot`].
+    pub fn player_ammo_usage_per_shot(&self, difficulty: Difficulty) -> f32 {
+        let factor = difficulty.tuning().ammo_consumption_factor.max(0.0);
+        *self.ammo_consumption_per_shot as f32 * factor
+    }
+
+    /// Returns the amount of ammo the player spends on the next shot. Fractions of ammo are carried
+    /// over to the next shots, so if the usage is halved, a weapon that spends one ammo per shot
+    /// spends it on every second shot.
+    pub fn player_ammo_consumption_per_shot(&self, difficulty: Difficulty) -> u32 {
+        (self.player_ammo_debt + self.player_ammo_usage_per_shot(difficulty)).floor() as u32
+    }
+
+    /// Must be called when the player has shot and spent
+    /// [`Self::player_ammo_consumption_per_shot`] ammo.
+    pub fn on_player_shot(&mut self, difficulty: Difficulty) {
+        let used = self.player_ammo_debt + self.player_ammo_usage_per_shot(difficulty);
+        self.player_ammo_debt = used.fract();
+    }
+
+    /// Returns the amount of shots the player can do with the given amount of ammo.
+    pub fn player_shots_left(&self, ammo: u32, difficulty: Difficulty) -> u32 {
+        let usage = self.player_ammo_usage_per_shot(difficulty);
+        if usage <= 0.0 {
+            u32::MAX
+        } else {
+            ((ammo as f32 - self.player_ammo_debt) / usage).max(0.0) as u32
+        }
+    }
+
     pub fn shot_position(&self, graph: &Graph) -> Vector3<f32> {
         if self.shot_point.is_some() {
             graph[self.shot_point].global_position()
@@ -157,14 +197,12 @@ impl Weapon {
         elapsed_time - self.last_shot_time >= *self.shoot_interval
     }
 
-    pub fn gen_v_recoil_angle(&self) -> f32 {
-        fyrox::rand::thread_rng()
-            .gen_range(self.v_recoil.x.to_radians()..self.v_recoil.y.to_radians())
+    pub fn gen_v_recoil_angle(&self, rng: &mut impl Rng) -> f32 {
+        rng.gen_range(self.v_recoil.x.to_radians()..self.v_recoil.y.to_radians())
     }
 
-    pub fn gen_h_recoil_angle(&self) -> f32 {
-        fyrox::rand::thread_rng()
-            .gen_range(self.h_recoil.x.to_radians()..self.h_recoil.y.to_radians())
+    pub fn gen_h_recoil_angle(&self, rng: &mut impl Rng) -> f32 {
+        rng.gen_range(self.h_recoil.x.to_radians()..self.h_recoil.y.to_radians())
     }
 
     fn shoot(
@@ -173,6 +211,7 @@ impl Weapon {
         scene: &mut Scene,
         elapsed_time: f32,
         direction: Option<Vector3<f32>>,
+        rng: &mut impl Rng,
     ) {
         self.last_shot_time = elapsed_time;
 
@@ -182,11 +221,7 @@ impl Weapon {
             .try_normalize(f32::EPSILON)
             .unwrap_or_else(Vector3::z);
 
-        if let Some(vfx) = self
-            .shot_vfx
-            .choose(&mut fyrox::rand::thread_rng())
-            .and_then(|vfx| vfx.as_ref())
-        {
+        if let Some(vfx) = self.shot_vfx.choose(rng).and_then(|vfx| vfx.as_ref()) {
             vfx.instantiate_at(scene, shot_position, vector_to_quat(direction));
         }
 
@@ -231,6 +266,7 @@ impl ScriptTrait for Weapon {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Weapon");
         self.item.enabled = self.owner.is_none();
         self.item.on_update(ctx)
     }
@@ -248,7 +284,8 @@ impl ScriptTrait for Weapon {
             }
 
             if let WeaponMessageData::Shoot { direction } = msg.data {
-                self.shoot(ctx.handle, ctx.scene, ctx.elapsed_time, direction);
+                let mut rng = ctx.plugins.get::<Game>().rng();
+                self.shoot(ctx.handle, ctx.scene, ctx.elapsed_time, direction, &mut rng);
             }
         }
         Ok(())
@@ -1,10 +1,13 @@
 use crate::level::hit_box::HitBoxDamage;
 use crate::{
     character::{Character, DamageDealer, DamagePosition},
+    damage::DamageType,
     level::{
         decal::Decal,
         hit_box::{HitBox, HitBoxMessage},
     },
+    profiler,
+    statistics::StatisticsEvent,
     CollisionGroups, Game, Weapon,
 };
 use fyrox::plugin::error::GameResult;
@@ -140,6 +143,8 @@ pub struct Projectile {
 
     damage: Damage,
 
+    damage_type: DamageType,
+
     #[reflect(min_value = 0.0, max_value = 1.0)]
     critical_hit_probability: f32,
 
@@ -165,6 +170,7 @@ impl Default for Projectile {
             random_appear_effects: Default::default(),
             one_frame: false,
             damage: Default::default(),
+            damage_type: Default::default(),
             critical_hit_probability: 0.025,
             collider: Default::default(),
         }
@@ -209,6 +215,7 @@ fn ray_hit(
     // TODO: Avoid allocation.
     let mut query_buffer = Vec::default();
 
+    profiler::count("Raycasts");
     physics.cast_ray(
         RayCastOptions {
             ray_origin: Point3::from(ray.origin),
@@ -267,9 +274,10 @@ impl ScriptTrait for Projectile {
             appear_effect.instantiate_at(ctx.scene, current_position, vector_to_quat(self.dir));
         }
 
+        let mut rng = ctx.plugins.get::<Game>().rng();
         if let Some(vfx) = self
             .random_appear_effects
-            .choose(&mut fyrox::rand::thread_rng())
+            .choose(&mut rng)
             .and_then(|vfx| vfx.as_ref())
         {
             vfx.instantiate_at(ctx.scene, current_position, vector_to_quat(self.dir));
@@ -290,8 +298,14 @@ impl ScriptTrait for Projectile {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("Projectile");
         let game = ctx.plugins.get::<Game>();
 
+        // The world could be frozen in photo mode.
+        if game.is_paused() {
+            return Ok(());
+        }
+
         // Movement of kinematic projectiles is controlled explicitly.
         if let Some(speed) = self.speed {
             if speed != 0.0 {
@@ -409,6 +423,7 @@ impl ScriptTrait for Projectile {
                                 HitBoxMessage::Damage(HitBoxDamage {
                                     hit_box,
                                     damage: amount,
+                                    damage_type: self.damage_type,
                                     dealer: DamageDealer {
                                         entity: hit.shooter_actor,
                                     },
@@ -417,6 +432,7 @@ impl ScriptTrait for Projectile {
                                         direction,
                                     }),
                                     is_melee: false,
+                                    is_status_effect: false,
                                 }),
                             );
                         }
@@ -430,6 +446,7 @@ impl ScriptTrait for Projectile {
                             HitBoxMessage::Damage(HitBoxDamage {
                                 hit_box,
                                 damage: amount,
+                                damage_type: self.damage_type,
                                 dealer: DamageDealer {
                                     entity: hit.shooter_actor,
                                 },
@@ -438,12 +455,28 @@ impl ScriptTrait for Projectile {
                                     direction,
                                 }),
                                 is_melee: false,
+                                is_status_effect: false,
                             }),
                         );
                     }
                 }
             }
 
+            if hit.hit_box.is_some() {
+                let shooter = DamageDealer {
+                    entity: hit.shooter_actor,
+                }
+                .as_character(&ctx.scene.graph)
+                .map(|(handle, _)| handle);
+                if game
+                    .level
+                    .as_ref()
+                    .is_some_and(|level| shooter == Some(level.player))
+                {
+                    game.record_statistics(StatisticsEvent::ShotHit);
+                }
+            }
+
             if hit.hit_box.is_none() {
                 if let Some(effect_prefab) = self.environment_impact_effect.as_ref() {
                     effect_prefab.instantiate_at(
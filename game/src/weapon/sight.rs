@@ -1,8 +1,9 @@
 use crate::level::hit_box::HitBoxMessage;
 use crate::{
     character::{CharacterMessage, CharacterMessageData},
+    profiler,
     weapon::find_parent_character,
-    CollisionGroups,
+    CollisionGroups, Game,
 };
 use fyrox::graph::SceneGraphNode;
 use fyrox::plugin::error::GameResult;
@@ -136,6 +137,7 @@ impl ScriptTrait for LaserSight {
     }
 
     fn on_update(&mut self, ctx: &mut ScriptContext) -> GameResult {
+        let _scope = profiler::scope("LaserSight");
         let ignore_collider = find_parent_character(ctx.handle, &ctx.scene.graph)
             .map(|(_, c)| c.capsule_collider)
             .unwrap_or_default();
@@ -150,6 +152,7 @@ impl ScriptTrait for LaserSight {
 
         let ray = Ray::new(position, direction.scale(max_toi));
 
+        profiler::count("Raycasts");
         ctx.scene.graph.physics.cast_ray(
             RayCastOptions {
                 ray_origin: Point3::from(ray.origin),
@@ -178,7 +181,9 @@ impl ScriptTrait for LaserSight {
                 .set_position(Vector3::new(0.0, 0.0, result.toi - 0.025));
         }
 
-        if let Some(reaction_state) = self.reaction_state.as_mut() {
+        // The reaction fades out in game time.
+        let paused = ctx.plugins.get::<Game>().is_paused();
+        if let Some(reaction_state) = self.reaction_state.as_mut().filter(|_| !paused) {
             match reaction_state {
                 ReactionState::HitDetected {
                     time_remaining,
@@ -0,0 +1,33 @@
+//! Tests for the local storage of achievements.
+
+use station_iapetus::achievements::{AchievementBackend, LocalAchievementBackend};
+use std::path::PathBuf;
+
+fn temp_profile(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("iapetus_{name}_{}.ron", std::process::id()))
+}
+
+#[test]
+fn unreadable_profile_is_kept_until_it_changes() {
+    let path = temp_profile("unreadable_profile");
+    std::fs::write(&path, "not a profile").unwrap();
+
+    let mut backend = LocalAchievementBackend::load(path.clone());
+    assert!(!backend.is_unlocked("first_blood"));
+    backend.flush();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "not a profile");
+
+    backend.unlock("first_blood");
+    backend.set_counter("kills", 3);
+    backend.flush();
+
+    let reloaded = LocalAchievementBackend::load(path.clone());
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_left = PathBuf::from(temp_path).exists();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(reloaded.is_unlocked("first_blood"));
+    assert_eq!(reloaded.counter("kills"), 3);
+    assert!(!temp_left);
+}
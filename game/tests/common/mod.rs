@@ -0,0 +1,27 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::sync::Once;
+
+/// Makes the workspace root the working directory of the test process, game data paths are
+/// relative to it. The working directory is shared by all tests, so it is changed only once.
+pub fn enter_workspace_root() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    });
+}
+
+pub fn assert_near(a: f32, b: f32) {
+    assert!((a - b).abs() < 1.0e-4, "{a} != {b}");
+}
+
+/// Calls the update with the given time step until the given time has passed.
+pub fn simulate(time: f32, dt: f32, mut update: impl FnMut(f32)) {
+    let mut elapsed = 0.0;
+    while elapsed < time {
+        update(dt);
+        elapsed += dt;
+    }
+}
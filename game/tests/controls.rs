@@ -0,0 +1,337 @@
+//! Tests for the action map and the migration of the legacy control scheme.
+
+use station_iapetus::{
+    control_scheme::{
+        Action, ActivationMode, Axis, Binding, ConflictResolution, ControlButton, ControlScheme,
+        GamepadButton, LegacyControlScheme, Modifiers,
+    },
+    demo::InputEvent,
+    fyrox::keyboard::KeyCode,
+    input::{ActionEvent, ActionState, MouseFilter},
+};
+
+fn key(code: KeyCode, pressed: bool) -> InputEvent {
+    InputEvent::Button {
+        button: ControlButton::Key(code),
+        pressed,
+    }
+}
+
+fn process(state: &mut ActionState, scheme: &ControlScheme, event: InputEvent) -> Vec<ActionEvent> {
+    let mut actions = Vec::new();
+    state.process(event, scheme, &mut actions);
+    actions
+}
+
+#[test]
+fn legacy_scheme_is_migrated() {
+    let legacy: LegacyControlScheme = ron::from_str(
+        r#"(
+            move_forward: (description: "Move Forward", button: Key(ArrowUp)),
+            action: (description: "controls.action", button: Key(KeyF)),
+            mouse_sens: 0.5,
+        )"#,
+    )
+    .unwrap();
+
+    let mut scheme = ControlScheme {
+        version: 0,
+        ..Default::default()
+    };
+    legacy.migrate(&mut scheme);
+    assert_eq!(scheme.version, 1);
+    assert_eq!(
+        scheme.bindings(Action::Interact),
+        [Binding::new(ControlButton::Key(KeyCode::KeyF))]
+    );
+
+    // Upgrade adds the gamepad bindings to the migrated ones.
+    scheme.upgrade();
+    assert_eq!(scheme.version, ControlScheme::VERSION);
+    assert_eq!(
+        scheme.bindings(Action::MoveForward),
+        [Binding::new(ControlButton::Key(KeyCode::ArrowUp))]
+    );
+    assert_eq!(
+        scheme.bindings(Action::Interact),
+        [
+            Binding::new(ControlButton::Key(KeyCode::KeyF)),
+            Binding::new(ControlButton::Gamepad(GamepadButton::West))
+        ]
+    );
+    // Actions missing in the legacy scheme keep the default bindings.
+    assert_eq!(
+        scheme.bindings(Action::Jump),
+        [
+            Binding::new(ControlButton::Key(KeyCode::Space)),
+            Binding::new(ControlButton::Gamepad(GamepadButton::South))
+        ]
+    );
+
+    // Upgrading again changes nothing.
+    let upgraded = scheme.clone();
+    scheme.upgrade();
+    assert_eq!(scheme, upgraded);
+}
+
+#[test]
+fn every_binding_of_an_action_triggers_it() {
+    let mut scheme = ControlScheme::default();
+    scheme.set_binding(
+        Action::Jump,
+        1,
+        Some(Binding::new(ControlButton::Key(KeyCode::KeyV))),
+    );
+
+    let mut state = ActionState::default();
+    assert_eq!(
+        process(&mut state, &scheme, key(KeyCode::Space, true)),
+        [ActionEvent::Pressed(Action::Jump)]
+    );
+    // The action is already active, the second binding does not press it again.
+    assert!(process(&mut state, &scheme, key(KeyCode::KeyV, true)).is_empty());
+    assert!(process(&mut state, &scheme, key(KeyCode::Space, false)).is_empty());
+    assert!(state.is_active(Action::Jump));
+    assert_eq!(
+        process(&mut state, &scheme, key(KeyCode::KeyV, false)),
+        [ActionEvent::Released(Action::Jump)]
+    );
+}
+
+#[test]
+fn modifier_binding_takes_precedence() {
+    let mut scheme = ControlScheme::default();
+    scheme.set_binding(
+        Action::QuickSave,
+        0,
+        Some(
+            Binding::new(ControlButton::Key(KeyCode::KeyS)).with_modifiers(Modifiers {
+                ctrl: true,
+                ..Default::default()
+            }),
+        ),
+    );
+
+    let mut state = ActionState::default();
+    assert_eq!(
+        process(&mut state, &scheme, key(KeyCode::KeyS, true)),
+        [ActionEvent::Pressed(Action::MoveBackward)]
+    );
+    process(&mut state, &scheme, key(KeyCode::KeyS, false));
+
+    process(&mut state, &scheme, key(KeyCode::ControlLeft, true));
+    assert_eq!(
+        process(&mut state, &scheme, key(KeyCode::KeyS, true)),
+        [ActionEvent::Pressed(Action::QuickSave)]
+    );
+    // Key repeats are ignored.
+    assert!(process(&mut state, &scheme, key(KeyCode::KeyS, true)).is_empty());
+    // The action is released with the button it was triggered by, not with the modifier.
+    assert!(process(&mut state, &scheme, key(KeyCode::ControlLeft, false)).is_empty());
+    assert_eq!(
+        process(&mut state, &scheme, key(KeyCode::KeyS, false)),
+        [ActionEvent::Released(Action::QuickSave)]
+    );
+}
+
+#[test]
+fn wheel_actions_are_released_immediately() {
+    let scheme = ControlScheme::default();
+    let mut state = ActionState::default();
+    let event = InputEvent::Button {
+        button: ControlButton::WheelUp,
+        pressed: true,
+    };
+    assert_eq!(
+        process(&mut state, &scheme, event),
+        [
+            ActionEvent::Pressed(Action::NextWeapon),
+            ActionEvent::Released(Action::NextWeapon)
+        ]
+    );
+    assert!(!state.is_active(Action::NextWeapon));
+}
+
+#[test]
+fn activation_modes() {
+    let mut active = false;
+    ActivationMode::Hold.apply(true, &mut active);
+    assert!(active);
+    ActivationMode::Hold.apply(false, &mut active);
+    assert!(!active);
+
+    ActivationMode::Toggle.apply(true, &mut active);
+    assert!(active);
+    // Releasing the button keeps the action active until the next press.
+    ActivationMode::Toggle.apply(false, &mut active);
+    assert!(active);
+    ActivationMode::Toggle.apply(true, &mut active);
+    assert!(!active);
+
+    let mut scheme = ControlScheme::default();
+    assert_eq!(scheme.activation_mode(Action::Aim), ActivationMode::Hold);
+    assert_eq!(
+        scheme.activation_mode(Action::FlashLight),
+        ActivationMode::Toggle
+    );
+    scheme.set_activation_mode(Action::Aim, ActivationMode::Toggle);
+    assert_eq!(scheme.activation_mode(Action::Aim), ActivationMode::Toggle);
+}
+
+#[test]
+fn aim_sensitivity_is_copied_on_upgrade() {
+    let mut scheme: ControlScheme = ron::from_str(
+        r#"(
+            version: 2,
+            mouse_sens: 0.7,
+        )"#,
+    )
+    .unwrap();
+    scheme.upgrade();
+    assert_eq!(scheme.version, ControlScheme::VERSION);
+    assert_eq!(scheme.mouse_sensitivity(false), 0.7);
+    assert_eq!(scheme.mouse_sensitivity(true), 0.7);
+    assert!(!scheme.mouse_x_inverse);
+    assert_eq!(scheme.activation_mode(Action::Run), ActivationMode::Hold);
+}
+
+fn look_total(frames: usize, dt: f32, smoothing: f32) -> f32 {
+    let mut filter = MouseFilter::default();
+    filter.add_motion(Axis::LookX, 100.0);
+    (0..frames)
+        .map(|_| filter.update(smoothing, 0.0, dt).x)
+        .sum()
+}
+
+#[test]
+fn mouse_smoothing_preserves_motion() {
+    let mut filter = MouseFilter::default();
+    filter.add_motion(Axis::LookX, 10.0);
+    filter.add_motion(Axis::LookY, -5.0);
+    // Movement axes are not the mouse.
+    filter.add_motion(Axis::MoveX, 1.0);
+    let motion = filter.update(0.0, 0.0, 0.016);
+    assert_eq!((motion.x, motion.y), (10.0, -5.0));
+    assert_eq!(filter.update(0.0, 0.0, 0.016).norm(), 0.0);
+
+    // Smoothing spreads the motion over several frames.
+    filter.add_motion(Axis::LookX, 10.0);
+    let first = filter.update(0.1, 0.0, 0.016).x;
+    assert!(first > 0.0 && first < 10.0);
+
+    // The total motion does not depend on the frame rate.
+    let slow = look_total(30, 1.0 / 30.0, 0.1);
+    let fast = look_total(144, 1.0 / 144.0, 0.1);
+    assert!((slow - 100.0).abs() < 0.01, "{slow}");
+    assert!((fast - 100.0).abs() < 0.01, "{fast}");
+    // Neither does the time it takes to turn.
+    let slow_half = look_total(3, 1.0 / 30.0, 0.1);
+    let fast_half = look_total(12, 1.0 / 120.0, 0.1);
+    assert!(
+        (slow_half - fast_half).abs() < 0.01,
+        "{slow_half} != {fast_half}"
+    );
+}
+
+#[test]
+fn mouse_acceleration_boosts_fast_motion() {
+    let mut filter = MouseFilter::default();
+    filter.add_motion(Axis::LookX, 1.0);
+    let slow = filter.update(0.0, 1.0, 0.016).x;
+    filter.add_motion(Axis::LookX, 100.0);
+    let fast = filter.update(0.0, 1.0, 0.016).x / 100.0;
+    assert!(slow >= 1.0);
+    assert!(fast > slow);
+}
+
+fn key_binding(code: KeyCode) -> Binding {
+    Binding::new(ControlButton::Key(code))
+}
+
+#[test]
+fn default_scheme_has_no_conflicts() {
+    let scheme = ControlScheme::default();
+    for action in Action::ALL {
+        for binding in scheme.bindings(action) {
+            assert!(
+                scheme.conflicts(action, *binding).is_empty(),
+                "{action:?}: {}",
+                binding.name()
+            );
+            assert!(!binding.button.is_reserved());
+        }
+    }
+}
+
+#[test]
+fn rebinding_conflicts_are_resolved() {
+    let mut scheme = ControlScheme::default();
+    let space = key_binding(KeyCode::Space);
+    assert_eq!(
+        scheme.conflicts(Action::Interact, space),
+        [(Action::Jump, 0)]
+    );
+    // Modifiers make a different binding.
+    let ctrl_space = space.with_modifiers(Modifiers {
+        ctrl: true,
+        ..Default::default()
+    });
+    assert!(scheme.conflicts(Action::Interact, ctrl_space).is_empty());
+
+    let mut swapped = scheme.clone();
+    swapped.rebind(Action::Interact, 0, space, ConflictResolution::Swap);
+    assert_eq!(swapped.bindings(Action::Interact)[0], space);
+    assert_eq!(
+        swapped.bindings(Action::Jump)[0],
+        key_binding(KeyCode::KeyE)
+    );
+
+    scheme.rebind(Action::Interact, 0, space, ConflictResolution::Clear);
+    assert_eq!(scheme.bindings(Action::Interact)[0], space);
+    assert_eq!(
+        scheme.bindings(Action::Jump),
+        [Binding::new(ControlButton::Gamepad(GamepadButton::South))]
+    );
+
+    // Swapping with an empty slot clears the other action.
+    let mut scheme = ControlScheme::default();
+    scheme.rebind(
+        Action::Journal,
+        2,
+        key_binding(KeyCode::KeyI),
+        ConflictResolution::Swap,
+    );
+    assert_eq!(
+        scheme.bindings(Action::Inventory),
+        [Binding::new(ControlButton::Gamepad(GamepadButton::Select))]
+    );
+}
+
+#[test]
+fn actions_are_reset_to_defaults() {
+    let mut scheme = ControlScheme::default();
+    scheme.set_binding(Action::Run, 0, Some(key_binding(KeyCode::KeyC)));
+    scheme.set_binding(Action::Run, 1, None);
+    scheme.set_activation_mode(Action::Run, ActivationMode::Toggle);
+    scheme.set_binding(Action::Jump, 0, Some(key_binding(KeyCode::KeyC)));
+
+    scheme.reset_action(Action::Run);
+    assert_eq!(
+        scheme.bindings(Action::Run),
+        ControlScheme::default().bindings(Action::Run)
+    );
+    assert_eq!(scheme.activation_mode(Action::Run), ActivationMode::Hold);
+    // Other actions are left as is.
+    assert_eq!(scheme.bindings(Action::Jump)[0], key_binding(KeyCode::KeyC));
+}
+
+#[test]
+fn button_labels() {
+    assert!(ControlButton::Key(KeyCode::Escape).is_reserved());
+    assert!(ControlButton::Gamepad(GamepadButton::Start).is_reserved());
+    assert!(!ControlButton::Mouse(0).is_reserved());
+
+    assert_eq!(ControlButton::Mouse(0).name(), "LMB");
+    assert_eq!(ControlButton::Mouse(4).name(), "MB5");
+    assert_eq!(ControlButton::Mouse(7).name(), "MB8");
+}
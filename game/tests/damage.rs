@@ -0,0 +1,72 @@
+//! Tests for the damage types and resistances.
+
+mod common;
+
+use station_iapetus::damage::{DamageType, Resistances};
+
+#[test]
+fn no_resistances_pass_everything() {
+    let resistances = Resistances::default();
+    for damage_type in [
+        DamageType::Kinetic,
+        DamageType::Plasma,
+        DamageType::Explosive,
+        DamageType::Fire,
+        DamageType::Blunt,
+        DamageType::Environmental,
+        DamageType::Bleeding,
+        DamageType::Poison,
+    ] {
+        common::assert_near(resistances.apply(42.0, damage_type), 42.0);
+    }
+    assert_eq!(resistances.apply(-10.0, DamageType::Kinetic), 0.0);
+}
+
+#[test]
+fn resistances_depend_on_damage_type() {
+    let resistances = Resistances {
+        plasma: 0.5.into(),
+        fire: (-0.5).into(),
+        blunt: 1.0.into(),
+        ..Default::default()
+    };
+    common::assert_near(resistances.apply(100.0, DamageType::Plasma), 50.0);
+    common::assert_near(resistances.apply(100.0, DamageType::Fire), 150.0);
+    common::assert_near(resistances.apply(100.0, DamageType::Blunt), 0.0);
+    common::assert_near(resistances.apply(100.0, DamageType::Kinetic), 100.0);
+}
+
+#[test]
+fn armor_stops_weak_hits() {
+    let resistances = Resistances {
+        armor: 20.0.into(),
+        ..Default::default()
+    };
+    // A strong hit barely notices the armor.
+    common::assert_near(resistances.apply(480.0, DamageType::Kinetic), 460.0);
+    common::assert_near(resistances.apply(30.0, DamageType::Kinetic), 10.0);
+    // A part of a weak hit always gets through.
+    common::assert_near(
+        resistances.apply(10.0, DamageType::Kinetic),
+        10.0 * (1.0 - Resistances::MAX_ARMOR_ABSORPTION),
+    );
+
+    // The armor is applied after the resistance.
+    let resistances = Resistances {
+        armor: 20.0.into(),
+        plasma: 0.5.into(),
+        ..Default::default()
+    };
+    common::assert_near(resistances.apply(100.0, DamageType::Plasma), 30.0);
+}
+
+#[test]
+fn armor_does_not_stop_internal_damage() {
+    let resistances = Resistances {
+        armor: 20.0.into(),
+        poison: 0.5.into(),
+        ..Default::default()
+    };
+    common::assert_near(resistances.apply(10.0, DamageType::Bleeding), 10.0);
+    common::assert_near(resistances.apply(10.0, DamageType::Poison), 5.0);
+}
@@ -0,0 +1,133 @@
+//! Tests for the recording and the playback of demos, the game runs headless on the testbed level.
+
+mod common;
+
+use station_iapetus::{
+    character::Character,
+    control_scheme::ControlButton,
+    demo::{Demo, DemoSource, InputEvent},
+    difficulty::Difficulty,
+    fyrox::{core::algebra::Vector3, graph::SceneGraph, keyboard::KeyCode},
+    input::InputSource,
+    message::Message,
+    simulation::Simulation,
+};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+const TESTBED: &str = "data/levels/testbed.rgs";
+
+/// Gives the events of a frame on every poll.
+#[derive(Debug, Default)]
+struct ScriptedInput {
+    frames: VecDeque<Vec<InputEvent>>,
+}
+
+impl InputSource for ScriptedInput {
+    fn poll(&mut self, output: &mut Vec<InputEvent>) {
+        if let Some(events) = self.frames.pop_front() {
+            output.extend(events);
+        }
+    }
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("iapetus_{name}_{}", std::process::id()))
+}
+
+fn player_position(simulation: &Simulation) -> Vector3<f32> {
+    let graph = &simulation.scene().unwrap().graph;
+    graph
+        .try_get_script_component_of::<Character>(simulation.level().unwrap().player)
+        .unwrap()
+        .position(graph)
+}
+
+fn walk_forward(pressed: bool) -> Vec<InputEvent> {
+    vec![InputEvent::Button {
+        button: ControlButton::Key(KeyCode::KeyW),
+        pressed,
+    }]
+}
+
+fn play(path: &Path) -> (Simulation, bool) {
+    let mut simulation = Simulation::new(Simulation::DEFAULT_DT).unwrap();
+    let played = simulation.play_demo(path, 1200).is_ok();
+    (simulation, played)
+}
+
+#[test]
+fn recorded_demo_is_replayed() {
+    common::enter_workspace_root();
+    let path = temp_path("replay.demo");
+
+    let mut simulation = Simulation::new(Simulation::DEFAULT_DT).unwrap();
+    simulation.load_level(TESTBED, 600).unwrap();
+    simulation.send(Message::RecordDemo(path.clone()));
+    assert!(simulation.run_until(600, |simulation| simulation.game().is_demo_recording()));
+    let start = player_position(&simulation);
+
+    // Walk for a while and stand still until the player stops.
+    let mut frames = VecDeque::new();
+    frames.push_back(walk_forward(true));
+    frames.extend(std::iter::repeat_with(Vec::new).take(90));
+    frames.push_back(walk_forward(false));
+    simulation
+        .game_mut()
+        .add_input_source(Box::new(ScriptedInput { frames }));
+    simulation.run_frames(210).unwrap();
+
+    simulation.send(Message::StopDemo);
+    simulation.step().unwrap();
+    assert!(!simulation.game().is_demo_recording());
+    let recorded = player_position(&simulation);
+    assert!(recorded.metric_distance(&start) > 0.5);
+
+    let (playback, played) = play(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(played);
+    assert!(player_position(&playback).metric_distance(&recorded) < 0.05);
+}
+
+#[test]
+fn demo_of_missing_level_is_rejected() {
+    common::enter_workspace_root();
+    let path = temp_path("missing_level.demo");
+    Demo::new(
+        DemoSource::level(Path::new("data/levels/missing.rgs")),
+        0,
+        Difficulty::default(),
+    )
+    .save(&path)
+    .unwrap();
+
+    let (simulation, played) = play(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(!played);
+    assert!(!simulation.game().is_demo_playing());
+    assert!(simulation.level().is_none());
+}
+
+#[test]
+fn demo_of_overwritten_saved_game_is_rejected() {
+    common::enter_workspace_root();
+    let saved_game = temp_path("overwritten.save");
+    std::fs::write(&saved_game, b"first").unwrap();
+    let path = temp_path("overwritten.demo");
+    Demo::new(
+        DemoSource::saved_game(&saved_game).unwrap(),
+        0,
+        Difficulty::default(),
+    )
+    .save(&path)
+    .unwrap();
+    std::fs::write(&saved_game, b"second").unwrap();
+
+    let (simulation, played) = play(&path);
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&saved_game).unwrap();
+    assert!(!played);
+    assert!(simulation.level().is_none());
+}
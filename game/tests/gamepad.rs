@@ -0,0 +1,221 @@
+//! Tests for the gamepad input and the aim assist, the gamepad is driven with synthetic events.
+
+mod common;
+
+use station_iapetus::{
+    aim_assist::{self, AimAssistTarget},
+    control_scheme::{Action, Axis, ControlAxis, ControlScheme, GamepadButton, ResponseCurve},
+    fyrox::core::algebra::Vector3,
+    gamepad::{Gamepad, GamepadEvent},
+    input::{ActionEvent, ActionState, InputSource},
+};
+
+struct Controller {
+    gamepad: Gamepad,
+    state: ActionState,
+    scheme: ControlScheme,
+}
+
+impl Controller {
+    fn new() -> Self {
+        Self {
+            gamepad: Gamepad::default(),
+            state: ActionState::default(),
+            scheme: ControlScheme::default(),
+        }
+    }
+
+    fn send(&mut self, events: &[GamepadEvent]) -> Vec<ActionEvent> {
+        for event in events {
+            self.gamepad.push_event(*event);
+        }
+        let mut input_events = Vec::new();
+        self.gamepad.poll(&mut input_events);
+
+        let mut actions = Vec::new();
+        for input_event in input_events {
+            self.state.process(input_event, &self.scheme, &mut actions);
+        }
+        actions
+    }
+
+    fn axis(&self, axis: Axis) -> f32 {
+        self.state.axis(axis, &self.scheme)
+    }
+}
+
+fn button(button: GamepadButton, pressed: bool) -> GamepadEvent {
+    GamepadEvent::Button { button, pressed }
+}
+
+fn axis(axis: ControlAxis, value: f32) -> GamepadEvent {
+    GamepadEvent::Axis { axis, value }
+}
+
+#[test]
+fn sticks_have_dead_zone_and_response_curve() {
+    let mut controller = Controller::new();
+
+    controller.send(&[axis(ControlAxis::LeftStickY, 0.1)]);
+    assert_eq!(controller.axis(Axis::MoveY), 0.0);
+
+    // Halfway between the dead zone and the full deflection.
+    controller.send(&[axis(ControlAxis::LeftStickY, 0.575)]);
+    common::assert_near(controller.axis(Axis::MoveY), 0.25);
+
+    controller.scheme.gamepad.response_curve = ResponseCurve::Linear;
+    common::assert_near(controller.axis(Axis::MoveY), 0.5);
+
+    controller.send(&[axis(ControlAxis::LeftStickY, 1.0)]);
+    common::assert_near(controller.axis(Axis::MoveY), 1.0);
+    assert_eq!(controller.axis(Axis::MoveX), 0.0);
+
+    // The dead zone is radial, a diagonal stick moves along both axes.
+    controller.send(&[
+        axis(ControlAxis::LeftStickX, 0.1),
+        axis(ControlAxis::LeftStickY, 0.1),
+    ]);
+    assert_eq!(controller.axis(Axis::MoveY), 0.0);
+    controller.send(&[
+        axis(ControlAxis::LeftStickX, 0.5),
+        axis(ControlAxis::LeftStickY, 0.5),
+    ]);
+    assert!(controller.axis(Axis::MoveX) > 0.0);
+    common::assert_near(controller.axis(Axis::MoveX), controller.axis(Axis::MoveY));
+}
+
+#[test]
+fn right_stick_looks_around() {
+    let mut controller = Controller::new();
+    controller.send(&[
+        axis(ControlAxis::RightStickX, 1.0),
+        axis(ControlAxis::RightStickY, 1.0),
+    ]);
+    assert!(controller.axis(Axis::LookX) > 0.0);
+    // Pushing the stick up looks up, the pitch grows downwards.
+    assert!(controller.axis(Axis::LookY) < 0.0);
+    assert_eq!(controller.axis(Axis::MoveX), 0.0);
+}
+
+#[test]
+fn triggers_aim_and_shoot() {
+    let mut controller = Controller::new();
+    assert!(controller
+        .send(&[axis(ControlAxis::LeftTrigger, 0.3)])
+        .is_empty());
+    assert_eq!(
+        controller.send(&[axis(ControlAxis::LeftTrigger, 0.8)]),
+        [ActionEvent::Pressed(Action::Aim)]
+    );
+    assert_eq!(
+        controller.send(&[axis(ControlAxis::RightTrigger, 1.0)]),
+        [ActionEvent::Pressed(Action::Shoot)]
+    );
+    // Small changes of a pressed trigger do not press it again.
+    assert!(controller
+        .send(&[axis(ControlAxis::RightTrigger, 0.9)])
+        .is_empty());
+    assert_eq!(
+        controller.send(&[axis(ControlAxis::RightTrigger, 0.1)]),
+        [ActionEvent::Released(Action::Shoot)]
+    );
+    assert!(controller.state.is_active(Action::Aim));
+}
+
+#[test]
+fn bumpers_cycle_weapons() {
+    let mut controller = Controller::new();
+    assert_eq!(
+        controller.send(&[
+            button(GamepadButton::RightBumper, true),
+            button(GamepadButton::RightBumper, false),
+            button(GamepadButton::LeftBumper, true),
+        ]),
+        [
+            ActionEvent::Pressed(Action::NextWeapon),
+            ActionEvent::Released(Action::NextWeapon),
+            ActionEvent::Pressed(Action::PrevWeapon),
+        ]
+    );
+}
+
+#[test]
+fn disconnect_releases_everything() {
+    let mut controller = Controller::new();
+    controller.send(&[
+        button(GamepadButton::South, true),
+        axis(ControlAxis::LeftStickX, 1.0),
+        axis(ControlAxis::RightTrigger, 1.0),
+    ]);
+    assert!(controller.state.is_active(Action::Jump));
+    assert!(controller.state.is_active(Action::Shoot));
+    common::assert_near(controller.axis(Axis::MoveX), 1.0);
+
+    let mut released = controller.send(&[GamepadEvent::Disconnected]);
+    released.sort_by_key(|event| format!("{event:?}"));
+    assert_eq!(
+        released,
+        [
+            ActionEvent::Released(Action::Jump),
+            ActionEvent::Released(Action::Shoot),
+        ]
+    );
+    assert_eq!(controller.axis(Axis::MoveX), 0.0);
+}
+
+#[test]
+fn aim_assist_finds_closest_target() {
+    let eye = Vector3::new(0.0, 1.0, 0.0);
+    let look = Vector3::z();
+
+    // Too far from the aim, too far from the eye.
+    assert_eq!(
+        aim_assist::find_target(
+            eye,
+            look,
+            [Vector3::new(5.0, 1.0, 10.0), Vector3::new(0.0, 1.0, 100.0)]
+        ),
+        None
+    );
+
+    let target = aim_assist::find_target(
+        eye,
+        look,
+        [Vector3::new(0.5, 1.0, 10.0), Vector3::new(0.0, 0.8, 10.0)],
+    )
+    .unwrap();
+    // The second one is closer to the aim, it is below the aim.
+    common::assert_near(target.yaw_error, 0.0);
+    assert!(target.pitch_error > 0.0);
+    assert!(target.angle < aim_assist::MAX_ANGLE);
+}
+
+#[test]
+fn aim_assist_slows_down_and_pulls() {
+    let target = aim_assist::find_target(
+        Vector3::default(),
+        Vector3::z(),
+        [Vector3::new(0.5, 0.0, 10.0)],
+    )
+    .unwrap();
+    assert!(target.yaw_error > 0.0);
+
+    assert!(target.slowdown(1.0) < 1.0);
+    assert!(target.slowdown(1.0) < target.slowdown(0.5));
+    assert_eq!(target.slowdown(0.0), 1.0);
+
+    // A released stick is never moved.
+    assert_eq!(target.magnetism(1.0, 0.0, 0.016).norm(), 0.0);
+    let correction = target.magnetism(1.0, 1.0, 0.016);
+    assert!(correction.x > 0.0 && correction.x < target.yaw_error);
+
+    // The aim never overshoots the target.
+    let correction = target.magnetism(1.0, 1.0, 10.0);
+    common::assert_near(correction.x, target.yaw_error);
+
+    let far = AimAssistTarget {
+        angle: aim_assist::MAX_ANGLE,
+        ..target
+    };
+    assert_eq!(far.slowdown(1.0), 1.0);
+}
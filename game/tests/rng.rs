@@ -0,0 +1,38 @@
+//! Tests for the game-owned random number generator.
+
+use station_iapetus::{
+    fyrox::{
+        core::visitor::{Visit, Visitor},
+        rand::Rng,
+    },
+    rng::GameRng,
+};
+
+fn sequence(mut rng: &GameRng) -> Vec<f32> {
+    (0..32).map(|_| rng.gen_range(0.0..1.0)).collect()
+}
+
+#[test]
+fn same_seed_gives_same_sequence() {
+    assert_eq!(sequence(&GameRng::new(42)), sequence(&GameRng::new(42)));
+    assert_ne!(sequence(&GameRng::new(42)), sequence(&GameRng::new(43)));
+}
+
+#[test]
+fn state_survives_save_and_load() {
+    let mut rng = GameRng::new(1234);
+    sequence(&rng);
+
+    let mut visitor = Visitor::new();
+    rng.visit("Rng", &mut visitor).unwrap();
+    let data = visitor.save_binary_to_vec().unwrap();
+
+    let expected = sequence(&rng);
+
+    let mut visitor = Visitor::load_from_memory(&data).unwrap();
+    let mut loaded = GameRng::default();
+    loaded.visit("Rng", &mut visitor).unwrap();
+
+    assert_eq!(loaded.seed(), 1234);
+    assert_eq!(sequence(&loaded), expected);
+}
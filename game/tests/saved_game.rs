@@ -0,0 +1,26 @@
+//! Tests for the saved game container.
+
+use station_iapetus::{
+    fyrox::core::visitor::{Visit, Visitor},
+    level::Level,
+    saved_game::SavedGame,
+    Game,
+};
+
+#[test]
+fn version_1_save_without_rng_is_loaded() {
+    // Version 1 saves are bare visitors, the oldest of them have only the level state.
+    let mut visitor = Visitor::new();
+    Level::default().visit("Level", &mut visitor).unwrap();
+    let payload = visitor.save_binary_to_vec().unwrap();
+
+    let path = std::env::temp_dir().join(format!("iapetus_v1_save_{}.rgs", std::process::id()));
+    std::fs::write(&path, &payload).unwrap();
+    let saved_game = SavedGame::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let saved_game = saved_game.unwrap();
+    assert_eq!(saved_game.header.format_version, SavedGame::FORMAT_VERSION);
+    assert_eq!(saved_game.payload, payload);
+    assert!(Game::read_saved_game_payload(&saved_game.payload).is_ok());
+}
@@ -0,0 +1,79 @@
+//! Tests for the stamina of characters.
+
+mod common;
+
+use station_iapetus::stamina::Stamina;
+
+#[test]
+fn actions_spend_stamina() {
+    let mut stamina = Stamina::default();
+    assert_eq!(stamina.fraction(), 1.0);
+
+    stamina.spend_on_jump();
+    common::assert_near(stamina.value(), *stamina.max - *stamina.jump_cost);
+    stamina.spend_on_melee();
+    common::assert_near(
+        stamina.value(),
+        *stamina.max - *stamina.jump_cost - *stamina.melee_cost,
+    );
+    stamina.spend_on_run(1.0);
+    common::assert_near(
+        stamina.value(),
+        *stamina.max - *stamina.jump_cost - *stamina.melee_cost - *stamina.run_cost,
+    );
+    assert!(!stamina.is_exhausted());
+}
+
+#[test]
+fn stamina_regenerates_after_delay() {
+    let mut stamina = Stamina::default();
+    stamina.spend(50.0);
+
+    // Nothing is restored until the delay is over.
+    stamina.update(*stamina.regeneration_delay * 0.5);
+    common::assert_near(stamina.value(), 50.0);
+    stamina.update(*stamina.regeneration_delay * 0.5);
+    stamina.update(1.0);
+    common::assert_near(stamina.value(), 50.0 + *stamina.regeneration_rate);
+
+    // Spending restarts the delay.
+    stamina.spend(1.0);
+    let value = stamina.value();
+    stamina.update(0.1);
+    common::assert_near(stamina.value(), value);
+
+    // The stamina never exceeds the max.
+    common::simulate(100.0, 1.0, |dt| stamina.update(dt));
+    assert_eq!(stamina.fraction(), 1.0);
+}
+
+#[test]
+fn exhaustion_blocks_running_and_slows_melee() {
+    let mut stamina = Stamina::default();
+    assert!(stamina.can_run());
+    assert_eq!(stamina.melee_speed(), 1.0);
+
+    let mut time = 0.0;
+    while !stamina.is_exhausted() {
+        stamina.update(0.1);
+        stamina.spend_on_run(0.1);
+        time += 0.1;
+        assert!(time < 100.0, "running never exhausts");
+    }
+    assert_eq!(stamina.value(), 0.0);
+    assert!(!stamina.can_run());
+    assert!(stamina.melee_speed() < 1.0);
+
+    // Regeneration alone does not end the exhaustion.
+    stamina.update(*stamina.regeneration_delay + 0.01);
+    stamina.update(0.1);
+    assert!(stamina.value() > 0.0);
+    assert!(stamina.is_exhausted());
+
+    while stamina.is_exhausted() {
+        stamina.update(0.1);
+    }
+    assert!(stamina.fraction() >= *stamina.recovery_threshold);
+    assert!(stamina.can_run());
+    assert_eq!(stamina.melee_speed(), 1.0);
+}
@@ -0,0 +1,144 @@
+//! Tests for the status effects of characters.
+
+mod common;
+
+use station_iapetus::{
+    damage::DamageType,
+    fyrox::core::{
+        pool::Handle,
+        visitor::{Visit, Visitor},
+    },
+    status_effect::{StatusEffectDamage, StatusEffectKind, StatusEffects},
+};
+
+/// Runs the effects for the given time, returns the damage of every tick.
+fn run(effects: &mut StatusEffects, time: f32, dt: f32) -> Vec<StatusEffectDamage> {
+    let mut output = Vec::new();
+    common::simulate(time, dt, |dt| effects.update(dt, &mut output));
+    output
+}
+
+fn total(damages: &[StatusEffectDamage], damage_type: DamageType) -> f32 {
+    damages
+        .iter()
+        .filter(|damage| damage.damage_type == damage_type)
+        .map(|damage| damage.amount)
+        .sum()
+}
+
+#[test]
+fn effects_tick_until_they_are_over() {
+    let mut effects = StatusEffects::default();
+    effects.apply(StatusEffectKind::Poison, Handle::NONE);
+
+    let kind = StatusEffectKind::Poison;
+    let damages = run(&mut effects, kind.duration() + 1.0, 0.25);
+    common::assert_near(
+        total(&damages, DamageType::Poison),
+        kind.damage_per_second() * kind.duration(),
+    );
+    assert_eq!(
+        damages.len() as f32,
+        kind.duration() / StatusEffects::TICK_INTERVAL
+    );
+    assert!(effects.is_empty());
+
+    // The damage does not depend on the frame rate.
+    effects.apply(StatusEffectKind::Poison, Handle::NONE);
+    let damages = run(&mut effects, kind.duration() + 1.0, 1.5);
+    common::assert_near(
+        total(&damages, DamageType::Poison),
+        kind.damage_per_second() * kind.duration(),
+    );
+}
+
+#[test]
+fn effects_stack_by_rules() {
+    let mut effects = StatusEffects::default();
+    for _ in 0..10 {
+        effects.apply(StatusEffectKind::Bleeding, Handle::NONE);
+        effects.apply(StatusEffectKind::Burning, Handle::NONE);
+    }
+    assert_eq!(
+        effects.get(StatusEffectKind::Bleeding).unwrap().stacks,
+        StatusEffectKind::Bleeding.max_stacks()
+    );
+    assert_eq!(effects.get(StatusEffectKind::Burning).unwrap().stacks, 1);
+
+    // Stacks multiply the damage.
+    let damages = run(&mut effects, StatusEffects::TICK_INTERVAL, 0.25);
+    common::assert_near(
+        total(&damages, DamageType::Bleeding),
+        StatusEffectKind::Bleeding.damage_per_second()
+            * StatusEffectKind::Bleeding.max_stacks() as f32
+            * StatusEffects::TICK_INTERVAL,
+    );
+
+    // A new application restarts the effect.
+    effects.apply(StatusEffectKind::Burning, Handle::NONE);
+    common::assert_near(
+        effects.get(StatusEffectKind::Burning).unwrap().remaining,
+        StatusEffectKind::Burning.duration(),
+    );
+}
+
+#[test]
+fn stun_deals_no_damage() {
+    let mut effects = StatusEffects::default();
+    assert!(!effects.is_stunned());
+    effects.apply(StatusEffectKind::Stun, Handle::NONE);
+    assert!(effects.is_stunned());
+
+    let damages = run(&mut effects, StatusEffectKind::Stun.duration() + 0.5, 0.25);
+    assert!(damages.is_empty());
+    assert!(!effects.is_stunned());
+}
+
+#[test]
+fn effects_are_cleared() {
+    let mut effects = StatusEffects::default();
+    effects.apply(StatusEffectKind::Bleeding, Handle::NONE);
+    effects.apply(StatusEffectKind::Poison, Handle::NONE);
+
+    effects.clear(StatusEffectKind::Bleeding);
+    assert!(!effects.has(StatusEffectKind::Bleeding));
+    assert!(effects.has(StatusEffectKind::Poison));
+
+    effects.clear_all();
+    assert!(effects.is_empty());
+}
+
+#[test]
+fn hits_cause_effects() {
+    assert_eq!(
+        StatusEffectKind::caused_by(DamageType::Plasma),
+        Some(StatusEffectKind::Burning)
+    );
+    assert_eq!(
+        StatusEffectKind::caused_by(DamageType::Explosive),
+        Some(StatusEffectKind::Stun)
+    );
+    assert_eq!(StatusEffectKind::caused_by(DamageType::Kinetic), None);
+}
+
+#[test]
+fn effects_are_saved() {
+    let mut effects = StatusEffects::default();
+    effects.apply(StatusEffectKind::Bleeding, Handle::NONE);
+    effects.apply(StatusEffectKind::Bleeding, Handle::NONE);
+    effects.apply(StatusEffectKind::Stun, Handle::NONE);
+    run(&mut effects, 0.5, 0.25);
+
+    let mut visitor = Visitor::new();
+    effects.visit("StatusEffects", &mut visitor).unwrap();
+    let data = visitor.save_binary_to_vec().unwrap();
+
+    let mut visitor = Visitor::load_from_memory(&data).unwrap();
+    let mut loaded = StatusEffects::default();
+    loaded.visit("StatusEffects", &mut visitor).unwrap();
+
+    assert_eq!(
+        loaded.iter().cloned().collect::<Vec<_>>(),
+        effects.iter().cloned().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,255 @@
+//! Gameplay tests that run the game headless on the testbed level.
+
+mod common;
+
+use station_iapetus::{
+    bot::Bot,
+    character::{Character, DamageDealer},
+    damage::DamageType,
+    door::{door_mut, door_ref, Door},
+    fyrox::{
+        core::{algebra::Vector3, pool::Handle},
+        graph::SceneGraph,
+        scene::{
+            base::BaseBuilder, collider::Collider, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    },
+    level::{
+        hit_box::{HitBox, HitBoxDamage, HitBoxMessage},
+        trigger::{Trigger, TriggerAction},
+    },
+    player::Player,
+    simulation::Simulation,
+    status_effect::StatusEffectKind,
+};
+
+const TESTBED: &str = "data/levels/testbed.rgs";
+
+fn testbed() -> Simulation {
+    common::enter_workspace_root();
+
+    let mut simulation = Simulation::new(Simulation::DEFAULT_DT).unwrap();
+    simulation.load_level(TESTBED, 600).unwrap();
+    simulation
+}
+
+fn character(simulation: &Simulation, handle: Handle<Node>) -> &Character {
+    simulation
+        .scene()
+        .unwrap()
+        .graph
+        .try_get_script_component_of::<Character>(handle)
+        .unwrap()
+}
+
+fn teleport_player(simulation: &mut Simulation, position: Vector3<f32>) {
+    let player = simulation.level().unwrap().player;
+    let body = character(simulation, player).body;
+    simulation.scene_mut().unwrap().graph[body]
+        .local_transform_mut()
+        .set_position(position);
+}
+
+/// Returns the first bot spawned on the level, if any.
+fn find_bot(simulation: &Simulation) -> Option<Handle<Node>> {
+    let graph = &simulation.scene()?.graph;
+    simulation
+        .level()?
+        .actors
+        .iter()
+        .copied()
+        .find(|actor| graph.try_get_script_of::<Bot>(*actor).is_ok())
+}
+
+/// Deals the damage to the hit box on behalf of the player, the same way as the player's weapons
+/// (or the status effects they caused) do it.
+fn damage_by_player(
+    simulation: &Simulation,
+    hit_box: Handle<Collider>,
+    damage: f32,
+    is_status_effect: bool,
+) {
+    let player = simulation.level().unwrap().player;
+    let graph = &simulation.scene().unwrap().graph;
+    graph
+        .try_get_script_of::<Player>(player)
+        .unwrap()
+        .script_message_sender
+        .as_ref()
+        .unwrap()
+        .send_to_target(
+            hit_box.transmute(),
+            HitBoxMessage::Damage(HitBoxDamage {
+                hit_box,
+                damage,
+                damage_type: DamageType::Kinetic,
+                dealer: DamageDealer { entity: player },
+                position: None,
+                is_melee: false,
+                is_status_effect,
+            }),
+        );
+}
+
+#[test]
+fn testbed_registers_level_entities() {
+    let mut simulation = testbed();
+    // Let the scripts initialize and register themselves in the level.
+    simulation.run_frames(2).unwrap();
+
+    let level = simulation.level().unwrap();
+    assert!(level.player.is_some());
+    assert!(level.actors.contains(&level.player));
+    assert!(!level.hit_boxes.is_empty());
+    assert!(!level.doors_container.doors.is_empty());
+}
+
+#[test]
+fn testbed_runs_for_several_seconds() {
+    let mut simulation = testbed();
+    simulation.run_frames(300).unwrap();
+
+    assert!(simulation.is_running());
+    assert!(simulation.frame() >= 300);
+
+    let level = simulation.level().unwrap();
+    let scene = simulation.scene().unwrap();
+    assert!(scene
+        .graph
+        .try_get_script_component_of::<Character>(level.player)
+        .is_ok());
+}
+
+#[test]
+fn door_opens_when_player_is_in_range() {
+    let mut simulation = testbed();
+    simulation.run_frames(2).unwrap();
+
+    let graph = &simulation.scene().unwrap().graph;
+    let door = simulation
+        .level()
+        .unwrap()
+        .doors_container
+        .doors
+        .iter()
+        .copied()
+        .find(|door| !*door_ref(*door, graph).locked)
+        .expect("Testbed must have an unlocked door!");
+    let door_position = door_ref(door, graph).initial_position();
+
+    teleport_player(&mut simulation, door_position);
+    simulation.run_frames(2).unwrap();
+    let player = simulation.level().unwrap().player;
+    assert!(
+        character(&simulation, player)
+            .position(&simulation.scene().unwrap().graph)
+            .metric_distance(&door_position)
+            < Door::INTERACTION_RADIUS
+    );
+
+    door_mut(door, &mut simulation.scene_mut().unwrap().graph).try_open(None);
+
+    assert!(simulation.run_until(300, |simulation| {
+        let door = door_ref(door, &simulation.scene().unwrap().graph);
+        door.actual_position(&simulation.scene().unwrap().graph)
+            .metric_distance(&door.initial_position())
+            > 0.5
+    }));
+}
+
+#[test]
+fn damage_kills_bot() {
+    let mut simulation = testbed();
+    assert!(simulation.run_until(600, |simulation| find_bot(simulation).is_some()));
+    let bot = find_bot(&simulation).unwrap();
+
+    let graph = &simulation.scene().unwrap().graph;
+    let health = character(&simulation, bot).combined_health(graph);
+    let hit_boxes = character(&simulation, bot)
+        .hit_box_iter(graph)
+        .map(|(hit_box, _)| hit_box)
+        .collect::<Vec<_>>();
+    assert!(!hit_boxes.is_empty());
+
+    damage_by_player(&simulation, hit_boxes[0], 10.0, false);
+    simulation.run_frames(2).unwrap();
+    let graph = &simulation.scene().unwrap().graph;
+    assert!(character(&simulation, bot).combined_health(graph) < health);
+    assert!(!character(&simulation, bot).is_dead(graph));
+
+    for hit_box in hit_boxes {
+        damage_by_player(&simulation, hit_box, 10000.0, false);
+    }
+    simulation.run_frames(2).unwrap();
+    let graph = &simulation.scene().unwrap().graph;
+    assert!(character(&simulation, bot).is_dead(graph));
+
+    let statistics = simulation.level().unwrap().statistics.current().unwrap();
+    assert_eq!(statistics.total_kills(), 1);
+}
+
+#[test]
+fn status_effect_tick_is_not_counted_as_hit() {
+    let mut simulation = testbed();
+    assert!(simulation.run_until(600, |simulation| find_bot(simulation).is_some()));
+    let bot = find_bot(&simulation).unwrap();
+
+    let graph = &simulation.scene().unwrap().graph;
+    let (limb, _) = character(&simulation, bot)
+        .hit_box_iter(graph)
+        .find(|(_, hit_box)| hit_box.limb_type.can_be_sliced_off())
+        .expect("Bots must have limbs!");
+
+    damage_by_player(&simulation, limb, 10000.0, true);
+    simulation.run_frames(2).unwrap();
+
+    let graph = &simulation.scene().unwrap().graph;
+    let bot = character(&simulation, bot);
+    assert!(graph
+        .try_get_script_component_of::<HitBox>(limb)
+        .unwrap()
+        .is_sliced_off());
+    assert!(!bot.status_effects.has(StatusEffectKind::Bleeding));
+
+    let statistics = simulation.level().unwrap().statistics.current().unwrap();
+    assert_eq!(statistics.limbs_severed, 0);
+    assert_eq!(statistics.headshots, 0);
+}
+
+#[test]
+fn secret_trigger_fires_when_player_is_inside() {
+    let mut simulation = testbed();
+    simulation.run_frames(2).unwrap();
+
+    let player = simulation.level().unwrap().player;
+    let position = character(&simulation, player).position(&simulation.scene().unwrap().graph);
+
+    // Testbed has no triggers, so put one around the player.
+    PivotBuilder::new(
+        BaseBuilder::new()
+            .with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .with_local_scale(Vector3::repeat(4.0))
+                    .build(),
+            )
+            .with_script(Trigger::new(TriggerAction::Secret { found: false })),
+    )
+    .build(&mut simulation.scene_mut().unwrap().graph);
+
+    let secrets_found = |simulation: &Simulation| {
+        simulation
+            .level()
+            .unwrap()
+            .statistics
+            .current()
+            .unwrap()
+            .secrets_found
+    };
+    assert!(simulation.run_until(60, |simulation| secrets_found(simulation) > 0));
+
+    // The secret is counted once.
+    simulation.run_frames(30).unwrap();
+    assert_eq!(secrets_found(&simulation), 1);
+}